@@ -29,11 +29,13 @@ use crate::{
     },
     BucketName, EndPoint,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use http::header::AsHeaderName;
 use http::{
-    header::{HeaderMap, HeaderValue, IntoHeaderName, InvalidHeaderValue, CONTENT_TYPE},
+    header::{
+        HeaderMap, HeaderName, HeaderValue, IntoHeaderName, InvalidHeaderValue, CONTENT_TYPE,
+    },
     Method,
 };
 #[cfg(test)]
@@ -41,9 +43,12 @@ use mockall::automock;
 use std::fmt::{Debug, Display};
 use std::{borrow::Cow, convert::TryInto};
 
+pub mod policy;
 pub mod query;
+pub(crate) mod v4;
 
-pub use query::QueryAuth;
+pub use policy::{PostPolicy, PostPolicyForm};
+pub use query::{QueryAuth, SignUrlOptions};
 
 #[cfg(test)]
 mod test;
@@ -58,10 +63,27 @@ pub struct InnerAuth<'a> {
     date: InnerDate<'a>,
     canonicalized_resource: InnerCanonicalizedResource<'a>,
     headers: HeaderMap,
+    version: SignVersion,
+    region: Cow<'a, str>,
+    additional_headers: Vec<HeaderName>,
+    url: Option<Url>,
 }
 /// 静态作用域的 InnerAuth
 pub type Auth = InnerAuth<'static>;
 
+/// # 签名版本
+///
+/// V4 版本需要同时提供 region，参考 [aliyun 文档](https://help.aliyun.com/zh/oss/developer-reference/recommend-to-use-signature-version-4)
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignVersion {
+    /// `OSS key:signature` 格式的签名（HMAC-SHA1）
+    #[default]
+    V1,
+    /// `OSS4-HMAC-SHA256` 格式的签名
+    V4,
+}
+
 impl<'a> InnerAuth<'a> {
     fn set_key(&mut self, access_key_id: InnerKeyId<'a>) {
         self.access_key_id = access_key_id;
@@ -99,6 +121,22 @@ impl<'a> InnerAuth<'a> {
     fn headers_clear(&mut self) {
         self.headers.clear();
     }
+    #[cfg(feature = "core")]
+    fn header_remove(&mut self, key: &str) {
+        self.headers.remove(key);
+    }
+    fn set_version(&mut self, version: SignVersion) {
+        self.version = version;
+    }
+    fn set_region(&mut self, region: Cow<'a, str>) {
+        self.region = region;
+    }
+    fn set_additional_headers(&mut self, headers: Vec<HeaderName>) {
+        self.additional_headers = headers;
+    }
+    fn set_url(&mut self, url: Url) {
+        self.url = Some(url);
+    }
 
     #[cfg(test)]
     fn get_header<K>(self, key: K) -> Option<HeaderValue>
@@ -194,6 +232,10 @@ impl AuthToOssHeader for InnerAuth<'_> {
 impl InnerAuth<'_> {
     /// 返回携带了签名信息的 headers
     pub fn get_headers(self) -> AuthResult<HeaderMap> {
+        if self.version == SignVersion::V4 {
+            return self.get_headers_v4();
+        }
+
         let mut map = HeaderMap::from_auth(&self)?;
 
         let oss_header = self.to_oss_header();
@@ -211,6 +253,62 @@ impl InnerAuth<'_> {
 
         Ok(())
     }
+
+    /// 返回携带了 V4 签名信息的 headers
+    fn get_headers_v4(self) -> AuthResult<HeaderMap> {
+        let datetime = DateTime::parse_from_rfc2822(self.date.as_ref())?.with_timezone(&Utc);
+
+        let mut map = self.get_original_header();
+        map.insert(DATE, self.get_header_date()?);
+        if let Some(md5) = self.get_header_md5() {
+            map.insert(CONTENT_MD5, md5);
+        }
+        map.insert(
+            v4::OSS_DATE,
+            datetime.format(v4::ISO8601).to_string().try_into()?,
+        );
+        map.entry(v4::CONTENT_SHA256)
+            .or_insert(HeaderValue::from_static(v4::UNSIGNED_PAYLOAD));
+
+        let canonical = v4::CanonicalRequest {
+            method: &self.method,
+            resource: self.canonicalized_resource.as_ref(),
+            url: self.url.as_ref(),
+            headers: &map,
+            additional_headers: &self.additional_headers,
+        };
+        let additional = canonical.additional_headers().join(";");
+        let scope = v4::scope(&datetime, &self.region);
+        let string_to_sign =
+            v4::string_to_sign(&datetime, &scope, &canonical.to_canonical_string());
+        let signature = self.access_key_secret.encryption_v4(
+            &v4::short_date(&datetime),
+            &self.region,
+            string_to_sign.as_bytes(),
+        )?;
+
+        let authorization = if additional.is_empty() {
+            format!(
+                "{} Credential={}/{},Signature={}",
+                v4::ALGORITHM,
+                self.access_key_id.as_ref(),
+                scope,
+                signature
+            )
+        } else {
+            format!(
+                "{} Credential={}/{},AdditionalHeaders={},Signature={}",
+                v4::ALGORITHM,
+                self.access_key_id.as_ref(),
+                scope,
+                additional,
+                signature
+            )
+        };
+        map.append_sign(authorization)?;
+
+        Ok(map)
+    }
 }
 
 trait AuthHeader {
@@ -362,11 +460,19 @@ impl<'a> SignString<'a> {
                     unreachable!("HeaderValue always is a rightful ContentType")
                 })
             });
+        // 未单独设置 content_md5 时，使用 headers 中的值
+        let content_md5 = match &content_md5 {
+            Some(md5) => md5.as_ref(),
+            None => headers
+                .get(CONTENT_MD5)
+                .and_then(|md5| md5.to_str().ok())
+                .unwrap_or_default(),
+        };
         let method = method.to_string();
 
         let data = method
             + LINE_BREAK
-            + content_md5.unwrap_or_default().as_ref()
+            + content_md5
             + LINE_BREAK
             + content_type.as_ref()
             + LINE_BREAK
@@ -462,6 +568,17 @@ impl AuthBuilder {
     pub(crate) fn get_secret(&self) -> &KeySecret {
         &self.auth.access_key_secret
     }
+    #[cfg(feature = "core")]
+    pub(crate) fn get_sign_version(&self) -> SignVersion {
+        self.auth.version
+    }
+    #[cfg(feature = "core")]
+    pub(crate) fn get_security_token(&self) -> Option<&str> {
+        self.auth
+            .headers
+            .get(crate::credentials::SECURITY_TOKEN)
+            .and_then(|token| token.to_str().ok())
+    }
 
     /// 给 verb 赋值
     #[inline]
@@ -525,6 +642,38 @@ impl AuthBuilder {
         self.auth.headers_clear();
     }
 
+    /// 移除 header
+    #[cfg(feature = "core")]
+    #[inline]
+    pub(crate) fn header_remove(&mut self, key: &str) {
+        self.auth.header_remove(key);
+    }
+
+    /// 设置签名版本，默认为 V1
+    #[inline]
+    pub fn sign_version(&mut self, version: SignVersion) {
+        self.auth.set_version(version);
+    }
+
+    /// 设置 V4 签名使用的 region，如 `cn-hangzhou`
+    #[inline]
+    pub fn region<R: Into<Cow<'static, str>>>(&mut self, region: R) {
+        self.auth.set_region(region.into());
+    }
+
+    /// 设置 V4 签名中额外参与签名的 header 名称
+    #[inline]
+    pub fn additional_headers<I: IntoIterator<Item = HeaderName>>(&mut self, headers: I) {
+        self.auth
+            .set_additional_headers(headers.into_iter().collect());
+    }
+
+    /// 设置请求的 url，V4 签名会用到其中的 query 参数和 host
+    #[inline]
+    pub fn url(&mut self, url: &Url) {
+        self.auth.set_url(url.to_owned());
+    }
+
     #[allow(dead_code)]
     #[inline]
     fn build(self) -> Auth {
@@ -547,6 +696,14 @@ pub trait RequestWithOSS: private::Sealed {
     /// 等信息，计算 OSS 签名
     /// 并把签名后的 header 信息，传递给 self
     fn with_oss(&mut self, key: InnerKeyId, secret: InnerKeySecret) -> AuthResult<()>;
+
+    /// 与 `with_oss` 相同，但使用 V4 签名，`region` 形如 `cn-hangzhou`
+    fn with_oss_v4(
+        &mut self,
+        key: InnerKeyId,
+        secret: InnerKeySecret,
+        region: &str,
+    ) -> AuthResult<()>;
 }
 
 mod private {
@@ -575,6 +732,34 @@ impl RequestWithOSS for Request {
 
         Ok(())
     }
+
+    fn with_oss_v4(
+        &mut self,
+        key: InnerKeyId,
+        secret: InnerKeySecret,
+        region: &str,
+    ) -> AuthResult<()> {
+        let mut auth = InnerAuth {
+            access_key_id: key,
+            access_key_secret: secret,
+            method: self.method().clone(),
+            date: Utc::now().into(),
+            headers: self.headers().clone(),
+            version: SignVersion::V4,
+            region: Cow::Borrowed(region),
+            url: Some(self.url().clone()),
+            ..Default::default()
+        };
+
+        auth.set_canonicalized_resource(self.url().canonicalized_resource().ok_or(AuthError {
+            kind: AuthErrorKind::InvalidCanonicalizedResource,
+        })?);
+
+        let headers = auth.get_headers()?;
+        self.headers_mut().extend(headers);
+
+        Ok(())
+    }
 }
 
 /// 根据 Url 计算 [`CanonicalizedResource`]
@@ -757,6 +942,10 @@ pub(crate) enum AuthErrorKind {
     Hmac(hmac::digest::crypto_common::InvalidLength),
     #[doc(hidden)]
     InvalidCanonicalizedResource,
+    #[doc(hidden)]
+    Date(chrono::ParseError),
+    #[doc(hidden)]
+    InvalidExpires,
 }
 
 impl std::error::Error for AuthError {
@@ -766,6 +955,8 @@ impl std::error::Error for AuthError {
             HeaderValue(e) => Some(e),
             Hmac(e) => Some(e),
             InvalidCanonicalizedResource => None,
+            Date(e) => Some(e),
+            InvalidExpires => None,
         }
     }
 }
@@ -777,6 +968,8 @@ impl Display for AuthError {
             HeaderValue(_) => f.write_str("failed to parse header value"),
             Hmac(_) => f.write_str("invalid aliyun secret length"),
             InvalidCanonicalizedResource => f.write_str("invalid canonicalized-resource"),
+            Date(_) => f.write_str("invalid date"),
+            InvalidExpires => f.write_str("expires must be between 1 second and 7 days"),
         }
     }
 }
@@ -810,6 +1003,14 @@ impl From<hmac::digest::crypto_common::InvalidLength> for AuthError {
     }
 }
 
+impl From<chrono::ParseError> for AuthError {
+    fn from(value: chrono::ParseError) -> Self {
+        Self {
+            kind: AuthErrorKind::Date(value),
+        }
+    }
+}
+
 type AuthResult<T> = Result<T, AuthError>;
 
 #[cfg(test)]
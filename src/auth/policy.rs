@@ -0,0 +1,395 @@
+//! # PostObject 表单上传的签名
+//!
+//! 生成浏览器直传 OSS 所需的 policy，签名以及其他表单字段
+//!
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/postobject)
+//!
+//! ## 用法
+//! ```
+//! # use aliyun_oss_client::auth::PostPolicy;
+//! # use chrono::{Duration, Utc};
+//! let key = "key".into();
+//! let secret = "secret".into();
+//! let mut policy = PostPolicy::new(Utc::now() + Duration::hours(1));
+//! policy
+//!     .bucket("bucket".parse().unwrap())
+//!     .key_starts_with("user/")
+//!     .content_length_range(1, 10 * 1024 * 1024)
+//!     .success_action_status(201);
+//! let form = policy.sign(&key, &secret).unwrap();
+//! for (name, value) in form.fields() {
+//!     println!("{name}: {value}");
+//! }
+//! ```
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+
+use super::{v4, AuthError, SignVersion};
+#[cfg(feature = "core")]
+use crate::credentials::Credentials;
+use crate::{BucketName, KeyId, KeySecret};
+
+const EXPIRATION_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3fZ";
+
+const ACCESS_KEY_ID: &str = "OSSAccessKeyId";
+const POLICY: &str = "policy";
+const SIGNATURE: &str = "Signature";
+const SUCCESS_ACTION_STATUS: &str = "success_action_status";
+const CALLBACK: &str = "callback";
+const SECURITY_TOKEN: &str = "x-oss-security-token";
+const SIGNATURE_VERSION: &str = "x-oss-signature-version";
+const CREDENTIAL: &str = "x-oss-credential";
+const SIGNATURE_V4: &str = "x-oss-signature";
+
+/// PostObject 的 policy 构建器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostPolicy {
+    expiration: DateTime<Utc>,
+    bucket: Option<BucketName>,
+    key_prefix: Option<String>,
+    content_length_range: Option<(u64, u64)>,
+    success_action_status: Option<u16>,
+    callback: Option<String>,
+    security_token: Option<String>,
+    version: SignVersion,
+    region: String,
+}
+
+impl PostPolicy {
+    /// 使用 policy 的过期时间初始化
+    pub fn new(expiration: DateTime<Utc>) -> Self {
+        Self {
+            expiration,
+            bucket: None,
+            key_prefix: None,
+            content_length_range: None,
+            success_action_status: None,
+            callback: None,
+            security_token: None,
+            version: SignVersion::V1,
+            region: String::new(),
+        }
+    }
+
+    /// 限制上传的 bucket
+    pub fn bucket(&mut self, bucket: BucketName) -> &mut Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// 限制上传文件的路径前缀
+    pub fn key_starts_with<P: Into<String>>(&mut self, prefix: P) -> &mut Self {
+        self.key_prefix = Some(prefix.into());
+        self
+    }
+
+    /// 限制上传文件的大小范围，单位字节
+    pub fn content_length_range(&mut self, min: u64, max: u64) -> &mut Self {
+        self.content_length_range = Some((min, max));
+        self
+    }
+
+    /// 上传成功后返回的 HTTP 状态码，可选 200，201，204
+    pub fn success_action_status(&mut self, status: u16) -> &mut Self {
+        self.success_action_status = Some(status);
+        self
+    }
+
+    /// 上传回调的参数，即回调的 json 字符串，会被 base64 编码后放入表单
+    pub fn callback<C: Into<String>>(&mut self, callback: C) -> &mut Self {
+        self.callback = Some(callback.into());
+        self
+    }
+
+    /// 使用 STS 临时凭证时，需要提供 security token
+    pub fn security_token<T: Into<String>>(&mut self, token: T) -> &mut Self {
+        self.security_token = Some(token.into());
+        self
+    }
+
+    /// 设置签名版本，默认为 V1
+    pub fn sign_version(&mut self, version: SignVersion) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    /// 设置 V4 签名使用的 region，如 `cn-hangzhou`
+    pub fn region<R: Into<String>>(&mut self, region: R) -> &mut Self {
+        self.region = region.into();
+        self
+    }
+
+    /// 计算签名，返回表单需要的所有字段（不含 `key` 和 `file`）
+    pub fn sign(&self, key: &KeyId, secret: &KeySecret) -> Result<PostPolicyForm, AuthError> {
+        self.sign_at(key, secret, Utc::now())
+    }
+
+    /// # 使用凭证计算签名
+    /// 凭证中包含 STS token 时，会一同写入 policy 和表单
+    #[cfg(feature = "core")]
+    pub fn sign_with_credentials(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<PostPolicyForm, AuthError> {
+        match credentials.security_token() {
+            Some(token) => {
+                let mut policy = self.clone();
+                policy.security_token(token);
+                policy.sign(credentials.key(), credentials.secret())
+            }
+            None => self.sign(credentials.key(), credentials.secret()),
+        }
+    }
+
+    fn sign_at(
+        &self,
+        key: &KeyId,
+        secret: &KeySecret,
+        now: DateTime<Utc>,
+    ) -> Result<PostPolicyForm, AuthError> {
+        let mut fields = Vec::with_capacity(8);
+        if let Some(status) = self.success_action_status {
+            fields.push((SUCCESS_ACTION_STATUS.to_owned(), status.to_string()));
+        }
+        if let Some(callback) = &self.callback {
+            fields.push((CALLBACK.to_owned(), STANDARD.encode(callback)));
+        }
+        if let Some(token) = &self.security_token {
+            fields.push((SECURITY_TOKEN.to_owned(), token.to_owned()));
+        }
+
+        let (policy, signature) = match self.version {
+            SignVersion::V1 => {
+                let policy = STANDARD.encode(self.to_json(&[]));
+                let signature = secret.encryption(policy.as_bytes())?;
+
+                fields.push((ACCESS_KEY_ID.to_owned(), key.as_ref().to_owned()));
+                fields.push((POLICY.to_owned(), policy.clone()));
+                fields.push((SIGNATURE.to_owned(), signature.clone()));
+                (policy, signature)
+            }
+            SignVersion::V4 => {
+                let credential = format!("{}/{}", key.as_ref(), v4::scope(&now, &self.region));
+                let date = now.format(v4::ISO8601).to_string();
+                let v4_fields = [
+                    (SIGNATURE_VERSION, v4::ALGORITHM),
+                    (CREDENTIAL, credential.as_str()),
+                    (v4::OSS_DATE, date.as_str()),
+                ];
+                let policy = STANDARD.encode(self.to_json(&v4_fields));
+                let signature =
+                    secret.encryption_v4(&v4::short_date(&now), &self.region, policy.as_bytes())?;
+
+                for (name, value) in v4_fields {
+                    fields.push((name.to_owned(), value.to_owned()));
+                }
+                fields.push((POLICY.to_owned(), policy.clone()));
+                fields.push((SIGNATURE_V4.to_owned(), signature.clone()));
+                (policy, signature)
+            }
+        };
+
+        Ok(PostPolicyForm {
+            policy,
+            signature,
+            fields,
+        })
+    }
+
+    /// 转化为 policy 的 json 文本
+    fn to_json(&self, extra: &[(&str, &str)]) -> String {
+        let mut conditions = Vec::new();
+        if let Some(bucket) = &self.bucket {
+            conditions.push(format!(r#"{{"bucket":"{}"}}"#, escape(bucket.as_ref())));
+        }
+        if let Some(prefix) = &self.key_prefix {
+            conditions.push(format!(r#"["starts-with","$key","{}"]"#, escape(prefix)));
+        }
+        if let Some((min, max)) = self.content_length_range {
+            conditions.push(format!(r#"["content-length-range",{min},{max}]"#));
+        }
+        if let Some(status) = self.success_action_status {
+            conditions.push(format!(r#"{{"{SUCCESS_ACTION_STATUS}":"{status}"}}"#));
+        }
+        if let Some(token) = &self.security_token {
+            conditions.push(format!(r#"{{"{SECURITY_TOKEN}":"{}"}}"#, escape(token)));
+        }
+        for (name, value) in extra {
+            conditions.push(format!(r#"{{"{name}":"{}"}}"#, escape(value)));
+        }
+
+        format!(
+            r#"{{"expiration":"{}","conditions":[{}]}}"#,
+            self.expiration.format(EXPIRATION_FORMAT),
+            conditions.join(",")
+        )
+    }
+}
+
+/// json 字符串转义
+fn escape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// PostObject 签名后的表单数据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostPolicyForm {
+    policy: String,
+    signature: String,
+    fields: Vec<(String, String)>,
+}
+
+impl PostPolicyForm {
+    /// base64 编码后的 policy
+    pub fn policy(&self) -> &str {
+        &self.policy
+    }
+
+    /// policy 的签名
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// 表单需要的所有字段，包括 policy 和签名
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// 转化为表单字段
+    pub fn into_fields(self) -> Vec<(String, String)> {
+        self.fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn policy() -> PostPolicy {
+        let mut policy = PostPolicy::new(Utc.with_ymd_and_hms(2023, 12, 3, 13, 0, 0).unwrap());
+        policy
+            .bucket("examplebucket".parse().unwrap())
+            .key_starts_with("user/eric/")
+            .content_length_range(1, 10485760)
+            .success_action_status(201);
+        policy
+    }
+
+    #[test]
+    fn to_json() {
+        assert_eq!(
+            policy().to_json(&[]),
+            r#"{"expiration":"2023-12-03T13:00:00.000Z","conditions":[{"bucket":"examplebucket"},["starts-with","$key","user/eric/"],["content-length-range",1,10485760],{"success_action_status":"201"}]}"#
+        );
+
+        let mut policy = PostPolicy::new(Utc.with_ymd_and_hms(2023, 12, 3, 13, 0, 0).unwrap());
+        policy.key_starts_with("a\"b");
+        assert_eq!(
+            policy.to_json(&[]),
+            r#"{"expiration":"2023-12-03T13:00:00.000Z","conditions":[["starts-with","$key","a\"b"]]}"#
+        );
+    }
+
+    #[test]
+    fn sign_v1() {
+        let mut policy = policy();
+        policy
+            .callback("{\"callbackUrl\":\"http://a.com\"}")
+            .security_token("token");
+        let form = policy
+            .sign_at(&"ak".into(), &"sk".into(), Utc::now())
+            .unwrap();
+
+        let fields: Vec<_> = form.fields().iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "success_action_status",
+                "callback",
+                "x-oss-security-token",
+                "OSSAccessKeyId",
+                "policy",
+                "Signature"
+            ]
+        );
+        assert_eq!(
+            form.fields()[1].1,
+            "eyJjYWxsYmFja1VybCI6Imh0dHA6Ly9hLmNvbSJ9"
+        );
+        assert_eq!(
+            form.policy(),
+            STANDARD.encode(policy.to_json(&[]).as_bytes())
+        );
+        assert_eq!(form.signature(), "R72pf+k4ctlCV6/iwqu20F7ZP/Q=");
+    }
+
+    #[cfg(feature = "core")]
+    #[test]
+    fn sign_with_credentials() {
+        use crate::credentials::Credentials;
+
+        let credentials = Credentials::new_with_sts("ak".into(), "sk".into(), "token", None);
+        let form = policy().sign_with_credentials(&credentials).unwrap();
+        assert!(form
+            .fields()
+            .contains(&("x-oss-security-token".to_owned(), "token".to_owned())));
+        let json = String::from_utf8(STANDARD.decode(form.policy()).unwrap()).unwrap();
+        assert!(json.contains(r#"{"x-oss-security-token":"token"}"#));
+
+        let form = policy()
+            .sign_with_credentials(&Credentials::new("ak".into(), "sk".into()))
+            .unwrap();
+        assert!(form
+            .fields()
+            .iter()
+            .all(|(k, _)| k != "x-oss-security-token"));
+    }
+
+    #[test]
+    fn sign_v4() {
+        let mut policy = policy();
+        policy.sign_version(SignVersion::V4).region("cn-hangzhou");
+        let form = policy
+            .sign_at(
+                &"ak".into(),
+                &"sk".into(),
+                Utc.with_ymd_and_hms(2023, 12, 3, 12, 0, 0).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            form.fields()[1..4],
+            [
+                (
+                    "x-oss-signature-version".to_owned(),
+                    "OSS4-HMAC-SHA256".to_owned()
+                ),
+                (
+                    "x-oss-credential".to_owned(),
+                    "ak/20231203/cn-hangzhou/oss/aliyun_v4_request".to_owned()
+                ),
+                ("x-oss-date".to_owned(), "20231203T120000Z".to_owned()),
+            ]
+        );
+        assert_eq!(form.fields()[5].0, "x-oss-signature");
+        assert_eq!(
+            form.signature(),
+            "35e53d36e904f258c4bb544c617929d6c82a8f2434a9a594c20821283419f195"
+        );
+    }
+}
@@ -11,13 +11,35 @@
 //! let bucket = "bucket".parse().unwrap();
 //! let auth = QueryAuth::new(&key, &secret, &EndPoint::CN_QINGDAO, &bucket);
 //! let time = Utc::now().timestamp() + 3600;
-//! let url = auth.to_url(&"pretty.png".parse().unwrap(), time);
+//! let url = auth.to_url(&"pretty.png".parse().unwrap(), time).unwrap();
 //! ```
+//!
+//! ## V4 签名
+//! ```
+//! # use aliyun_oss_client::{auth::{QueryAuth, SignVersion}, EndPoint};
+//! # use chrono::Utc;
+//! # let key = "key".into();
+//! # let secret = "secret".into();
+//! # let bucket = "bucket".parse().unwrap();
+//! let mut auth = QueryAuth::new(&key, &secret, &EndPoint::CN_QINGDAO, &bucket);
+//! auth.sign_version(SignVersion::V4);
+//! let time = Utc::now().timestamp() + 3600;
+//! let url = auth.to_url(&"pretty.png".parse().unwrap(), time).unwrap();
+//! assert!(url.query().unwrap().contains("x-oss-signature="));
+//! ```
+
+use std::borrow::Cow;
 
+use chrono::{DateTime, Utc};
+use http::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
+    Method,
+};
 use url::Url;
 
+use super::{v4, AuthError, AuthErrorKind, AuthResult, SignVersion};
 use crate::{
-    types::{object::SetObjectPath, url_from_bucket, CanonicalizedResource},
+    types::{object::SetObjectPath, url_from_bucket, CanonicalizedResource, ContentType},
     BucketName, EndPoint, KeyId, KeySecret, ObjectPath,
 };
 
@@ -27,36 +49,48 @@ pub struct QueryAuth<'a> {
     access_secret_key: &'a KeySecret,
     endpoint: &'a EndPoint,
     bucket: &'a BucketName,
+    version: SignVersion,
+    security_token: Option<&'a str>,
 }
 
 #[cfg(feature = "core")]
 use crate::{
     client::Client,
     config::{BucketBase, Config},
+    credentials::Credentials,
 };
 
 #[cfg(feature = "core")]
 impl<'a> From<&'a Config> for QueryAuth<'a> {
     #[inline]
     fn from(config: &'a Config) -> Self {
-        Self::new(
+        let mut auth = Self::new(
             config.as_ref(),
             config.as_ref(),
             config.as_ref(),
             config.as_ref(),
-        )
+        );
+        auth.security_token = config.get_security_token().and_then(|t| t.to_str().ok());
+        auth
     }
 }
 #[cfg(feature = "core")]
 impl<'a, M: Default + Clone> From<&'a Client<M>> for QueryAuth<'a> {
+    /// 使用 Client 初始化时的 key，secret 和 STS token
+    ///
+    /// 设置了 `credentials_provider` 的 Client，需要先通过 `get_credentials` 获取当前的凭证，
+    /// 再使用 [`QueryAuth::from_credentials`]
     #[inline]
     fn from(client: &'a Client<M>) -> Self {
-        Self::new(
+        let mut auth = Self::new(
             client.get_key(),
             client.get_secret(),
             client.as_ref(),
             client.as_ref(),
-        )
+        );
+        auth.sign_version(client.get_sign_version());
+        auth.security_token = client.get_security_token();
+        auth
     }
 }
 
@@ -74,9 +108,43 @@ impl<'a> QueryAuth<'a> {
             access_secret_key,
             endpoint,
             bucket,
+            version: SignVersion::default(),
+            security_token: None,
         }
     }
 
+    /// # 使用凭证初始化
+    /// 凭证中包含 STS token 时，会一同附加到 url 中
+    ///
+    /// ```
+    /// # use aliyun_oss_client::{auth::QueryAuth, credentials::Credentials, EndPoint};
+    /// let credentials = Credentials::new_with_sts("key".into(), "secret".into(), "token", None);
+    /// let bucket = "bucket".parse().unwrap();
+    /// let auth = QueryAuth::from_credentials(&credentials, &EndPoint::CN_QINGDAO, &bucket);
+    /// let url = auth.to_url(&"pretty.png".parse().unwrap(), 1700000000).unwrap();
+    /// assert!(url.query().unwrap().contains("security-token=token"));
+    /// ```
+    #[cfg(feature = "core")]
+    pub fn from_credentials(
+        credentials: &'a Credentials,
+        endpoint: &'a EndPoint,
+        bucket: &'a BucketName,
+    ) -> Self {
+        let mut auth = Self::new(credentials.key(), credentials.secret(), endpoint, bucket);
+        auth.security_token = credentials.security_token();
+        auth
+    }
+
+    /// 设置签名版本，默认为 V1
+    pub fn sign_version(&mut self, version: SignVersion) {
+        self.version = version;
+    }
+
+    /// 使用 STS 临时凭证签名时，需要提供 security token
+    pub fn security_token(&mut self, token: &'a str) {
+        self.security_token = Some(token);
+    }
+
     /// 通过 BucketBase 初始化
     #[cfg(feature = "core")]
     #[inline]
@@ -101,53 +169,457 @@ impl<'a> QueryAuth<'a> {
         url
     }
 
+    #[cfg(test)]
     fn sign_string(&self, path: &ObjectPath, expires: i64) -> String {
-        const METHOD: &str = "GET";
-        const LN3: &str = "\n\n\n";
+        self.sign_string_with(path, &SignUrlOptions::default(), expires)
+    }
+    fn sign_string_with(
+        &self,
+        path: &ObjectPath,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> String {
         const LN: &str = "\n";
 
-        let p = self.get_resource(path);
-
-        const fn len(path: &str) -> usize {
-            METHOD.len() + LN.len() + LN3.len() + 10 + path.len()
+        let mut string = String::with_capacity(64);
+        string += options.method.as_str();
+        string += LN;
+        string += LN;
+        if let Some(content_type) = &options.content_type {
+            string += content_type.as_ref();
         }
-
-        let mut string = String::with_capacity(len(p.as_ref()));
-        string += METHOD;
-        string += LN3;
+        string += LN;
         string += &expires.to_string();
         string += LN;
-        string += p.as_ref();
+        string += &options.oss_header_string();
+        string += self.get_resource(path).as_ref();
+        string += &options.sub_resource_string();
         string
     }
-    fn signature(&self, path: &ObjectPath, expires: i64) -> String {
-        #![allow(clippy::unwrap_used)]
-        self.access_secret_key
-            .encryption_string(self.sign_string(path, expires))
-            .unwrap()
+    #[cfg(test)]
+    fn signature(&self, path: &ObjectPath, expires: i64) -> AuthResult<String> {
+        self.signature_with(path, &SignUrlOptions::default(), expires)
+    }
+    fn signature_with(
+        &self,
+        path: &ObjectPath,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> AuthResult<String> {
+        Ok(self
+            .access_secret_key
+            .encryption_string(self.sign_string_with(path, options, expires))?)
     }
 
     /// 转化为带签名完整 url
-    pub fn to_url(&self, path: &ObjectPath, expires: i64) -> Url {
+    ///
+    /// `expires` 为过期时间的时间戳，V4 签名会将其转化为相对当前时间的秒数，
+    /// 超出 1 秒到 7 天的范围时返回错误
+    pub fn to_url(&self, path: &ObjectPath, expires: i64) -> AuthResult<Url> {
         let mut url = self.get_url(path);
-        self.signature_url(&mut url, path, expires);
-        url
+        self.signature_url(&mut url, path, expires)?;
+        Ok(url)
     }
 
-    /// 为指定的 url 附加签名信息
-    pub fn signature_url(&self, url: &mut Url, path: &ObjectPath, expires: i64) {
+    /// 为指定的 url 附加签名信息，url 中原有的 query 参数会被保留
+    pub fn signature_url(&self, url: &mut Url, path: &ObjectPath, expires: i64) -> AuthResult<()> {
+        self.signature_url_with(url, path, &SignUrlOptions::default(), expires)
+    }
+
+    /// 根据自定义的请求方式，header 及子资源，转化为带签名完整 url
+    ///
+    /// ```
+    /// # use aliyun_oss_client::{auth::{QueryAuth, SignUrlOptions}, EndPoint, Method};
+    /// # let key = "key".into();
+    /// # let secret = "secret".into();
+    /// # let bucket = "bucket".parse().unwrap();
+    /// let auth = QueryAuth::new(&key, &secret, &EndPoint::CN_QINGDAO, &bucket);
+    /// let mut options = SignUrlOptions::new(Method::PUT);
+    /// options.content_type("image/png");
+    /// let url = auth.to_url_with(&"pretty.png".parse().unwrap(), &options, 1700000000).unwrap();
+    /// ```
+    pub fn to_url_with(
+        &self,
+        path: &ObjectPath,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> AuthResult<Url> {
+        let mut url = self.get_url(path);
+        self.signature_url_with(&mut url, path, options, expires)?;
+        Ok(url)
+    }
+
+    /// 根据自定义的请求方式，header 及子资源，为指定的 url 附加签名信息
+    ///
+    /// url 中原有的 query 参数会被保留，V1 签名只对其中 OSS 的子资源签名，V4 签名对全部参数签名
+    pub fn signature_url_with(
+        &self,
+        url: &mut Url,
+        path: &ObjectPath,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> AuthResult<()> {
+        let mut options = Cow::Borrowed(options);
+        // V4 对全部 query 参数签名，V1 只对 OSS 的子资源签名，其他参数原样保留在 url 中
+        let (signed, unsigned): (Vec<_>, Vec<_>) = url
+            .query_pairs()
+            .into_owned()
+            .partition(|(key, _)| self.version == SignVersion::V4 || is_sub_resource(key));
+        if !signed.is_empty() {
+            options.to_mut().sub_resource.splice(0..0, signed);
+        }
+        // V1 签名中，STS token 作为子资源参与签名
+        if let (SignVersion::V1, Some(token)) = (self.version, self.security_token) {
+            options.to_mut().sub_resource(SECURITY_TOKEN_V1, token);
+        }
+        let options = options.as_ref();
+
+        set_query(url, unsigned.iter().chain(&options.sub_resource));
+
+        if self.version == SignVersion::V4 {
+            let now = Utc::now();
+            return self.signature_url_v4(
+                url,
+                &options.method,
+                path,
+                &options.to_headers(),
+                &[],
+                now,
+                expires - now.timestamp(),
+            );
+        }
+
         const KEY: &str = "OSSAccessKeyId";
         const EXPIRES: &str = "Expires";
         const SIGNATURE: &str = "Signature";
 
         url.query_pairs_mut()
-            .clear()
             .append_pair(KEY, self.access_key_id.as_ref())
             .append_pair(EXPIRES, &expires.to_string())
-            .append_pair(SIGNATURE, &self.signature(path, expires));
+            .append_pair(SIGNATURE, &self.signature_with(path, options, expires)?);
+        Ok(())
+    }
+
+    /// # 附加 V4 签名信息
+    /// 保留 url 中原有的 query 参数，它们同样参与签名
+    ///
+    /// - `datetime` 签名时间
+    /// - `expires` 有效时长，单位秒，须在 1 秒到 7 天之间
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn signature_url_v4(
+        &self,
+        url: &mut Url,
+        method: &Method,
+        path: &ObjectPath,
+        headers: &HeaderMap,
+        additional_headers: &[HeaderName],
+        datetime: DateTime<Utc>,
+        expires: i64,
+    ) -> AuthResult<()> {
+        if expires <= 0 || expires > V4_MAX_EXPIRES {
+            return Err(AuthError {
+                kind: AuthErrorKind::InvalidExpires,
+            });
+        }
+
+        const VERSION: &str = "x-oss-signature-version";
+        const CREDENTIAL: &str = "x-oss-credential";
+        const EXPIRES: &str = "x-oss-expires";
+        const ADDITIONAL_HEADERS: &str = "x-oss-additional-headers";
+        const SIGNATURE: &str = "x-oss-signature";
+
+        let region = self.endpoint.as_ref();
+        let scope = v4::scope(&datetime, region);
+        let resource = self.get_resource(path);
+
+        let origin_url = url.clone();
+        let additional = v4::CanonicalRequest {
+            method,
+            resource: resource.as_ref(),
+            url: Some(&origin_url),
+            headers,
+            additional_headers,
+        }
+        .additional_headers()
+        .join(";");
+
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair(VERSION, v4::ALGORITHM)
+                .append_pair(
+                    CREDENTIAL,
+                    &format!("{}/{}", self.access_key_id.as_ref(), scope),
+                )
+                .append_pair(v4::OSS_DATE, &datetime.format(v4::ISO8601).to_string())
+                .append_pair(EXPIRES, &expires.to_string());
+            if !additional.is_empty() {
+                query.append_pair(ADDITIONAL_HEADERS, &additional);
+            }
+            if let Some(token) = self.security_token {
+                query.append_pair(SECURITY_TOKEN_V4, token);
+            }
+        }
+
+        let canonical = v4::CanonicalRequest {
+            method,
+            resource: resource.as_ref(),
+            url: Some(url),
+            headers,
+            additional_headers,
+        };
+        let string_to_sign =
+            v4::string_to_sign(&datetime, &scope, &canonical.to_canonical_string());
+        let signature = self.access_secret_key.encryption_v4(
+            &v4::short_date(&datetime),
+            region,
+            string_to_sign.as_bytes(),
+        )?;
+
+        url.query_pairs_mut().append_pair(SIGNATURE, &signature);
+        Ok(())
+    }
+}
+
+/// # 带签名 url 的自定义参数
+///
+/// 默认为 `GET` 请求，不包含 Content-Type，header 及子资源
+///
+/// ```
+/// # use aliyun_oss_client::{auth::SignUrlOptions, Method};
+/// let mut options = SignUrlOptions::new(Method::GET);
+/// options
+///     .response_content_disposition("attachment; filename=\"a.png\"")
+///     .sub_resource("x-oss-process", "image/resize,w_100");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignUrlOptions {
+    method: Method,
+    content_type: Option<ContentType>,
+    headers: HeaderMap,
+    sub_resource: Vec<(String, String)>,
+}
+
+impl SignUrlOptions {
+    /// 使用请求方式初始化
+    pub fn new(method: Method) -> Self {
+        Self {
+            method,
+            ..Default::default()
+        }
+    }
+
+    /// 设置请求方式
+    pub fn method(&mut self, method: Method) -> &mut Self {
+        self.method = method;
+        self
+    }
+
+    /// 设置 Content-Type，使用该 url 请求时，需携带相同的 Content-Type
+    pub fn content_type<C: Into<Cow<'static, str>>>(&mut self, content_type: C) -> &mut Self {
+        self.content_type = Some(ContentType::new(content_type));
+        self
+    }
+
+    /// # 添加参与签名的 header
+    /// 只有以 `x-oss-` 开头的 header 才会参与签名，使用该 url 请求时，需携带相同的 header
+    pub fn header(&mut self, key: HeaderName, value: HeaderValue) -> &mut Self {
+        self.headers.insert(key, value);
+        self
+    }
+
+    /// # 添加参与签名的子资源
+    /// 如 `response-content-type`，`x-oss-process` 等，它们会被附加到 url 的 query 中
+    pub fn sub_resource<K: Into<String>, V: Into<String>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> &mut Self {
+        self.sub_resource.push((key.into(), value.into()));
+        self
+    }
+
+    /// 覆盖响应中的 Content-Type
+    pub fn response_content_type<V: Into<String>>(&mut self, value: V) -> &mut Self {
+        self.sub_resource(RESPONSE_CONTENT_TYPE, value)
+    }
+
+    /// 覆盖响应中的 Content-Disposition
+    pub fn response_content_disposition<V: Into<String>>(&mut self, value: V) -> &mut Self {
+        self.sub_resource(RESPONSE_CONTENT_DISPOSITION, value)
+    }
+
+    fn oss_headers(&self) -> Vec<(&str, &str)> {
+        const X_OSS_PRE: &str = "x-oss-";
+        let mut list: Vec<_> = self
+            .headers
+            .iter()
+            .filter(|(k, _)| k.as_str().starts_with(X_OSS_PRE))
+            .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.as_str(), v)))
+            .collect();
+        list.sort_unstable();
+        list
+    }
+
+    /// V1 签名中的 CanonicalizedOSSHeaders
+    fn oss_header_string(&self) -> String {
+        self.oss_headers()
+            .iter()
+            .map(|(k, v)| format!("{k}:{v}\n"))
+            .collect()
+    }
+
+    /// V1 签名中 CanonicalizedResource 的子资源部分
+    fn sub_resource_string(&self) -> String {
+        if self.sub_resource.is_empty() {
+            return String::new();
+        }
+        let mut list: Vec<_> = self.sub_resource.iter().collect();
+        list.sort();
+        let list: Vec<_> = list
+            .iter()
+            .map(|(k, v)| {
+                if v.is_empty() {
+                    k.to_owned()
+                } else {
+                    format!("{k}={v}")
+                }
+            })
+            .collect();
+        format!("?{}", list.join("&"))
+    }
+
+    /// V4 签名中参与签名的 header
+    fn to_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        if let Some(value) = self
+            .content_type
+            .clone()
+            .and_then(|ct| TryInto::<HeaderValue>::try_into(ct).ok())
+        {
+            headers.insert(CONTENT_TYPE, value);
+        }
+        headers
     }
 }
 
+/// 用编码后的键值对替换 url 的 query，值为空时只保留键
+fn set_query<'a, I>(url: &mut Url, pairs: I)
+where
+    I: IntoIterator<Item = &'a (String, String)>,
+{
+    let query: Vec<_> = pairs
+        .into_iter()
+        .map(|(k, v)| {
+            if v.is_empty() {
+                v4::uri_encode(k)
+            } else {
+                format!("{}={}", v4::uri_encode(k), v4::uri_encode(v))
+            }
+        })
+        .collect();
+    url.set_query((!query.is_empty()).then(|| query.join("&")).as_deref());
+}
+
+/// 是否为 V1 签名中需要参与签名的子资源
+fn is_sub_resource(key: &str) -> bool {
+    SUB_RESOURCES.contains(&key)
+}
+
+/// V1 签名中参与签名的子资源
+const SUB_RESOURCES: &[&str] = &[
+    "acl",
+    "uploads",
+    "location",
+    "cors",
+    "logging",
+    "website",
+    "referer",
+    "lifecycle",
+    "delete",
+    "append",
+    "tagging",
+    "objectMeta",
+    "uploadId",
+    "partNumber",
+    "security-token",
+    "position",
+    "img",
+    "style",
+    "styleName",
+    "replication",
+    "replicationProgress",
+    "replicationLocation",
+    "cname",
+    "bucketInfo",
+    "comp",
+    "qos",
+    "live",
+    "status",
+    "vod",
+    "startTime",
+    "endTime",
+    "symlink",
+    "x-oss-process",
+    "response-content-type",
+    "x-oss-traffic-limit",
+    "response-content-language",
+    "response-expires",
+    "response-cache-control",
+    "response-content-disposition",
+    "response-content-encoding",
+    "udf",
+    "udfName",
+    "udfImage",
+    "udfId",
+    "udfImageDesc",
+    "udfApplication",
+    "udfApplicationLog",
+    "restore",
+    "callback",
+    "callback-var",
+    "qosInfo",
+    "policy",
+    "stat",
+    "encryption",
+    "versions",
+    "versioning",
+    "versionId",
+    "requestPayment",
+    "x-oss-request-payer",
+    "sequential",
+    "inventory",
+    "inventoryId",
+    "continuation-token",
+    "asyncFetch",
+    "worm",
+    "wormId",
+    "wormExtend",
+    "withHashContext",
+    "x-oss-enable-md5",
+    "x-oss-enable-sha1",
+    "x-oss-enable-sha256",
+    "x-oss-hash-ctx",
+    "x-oss-md5-ctx",
+    "transferAcceleration",
+    "regionList",
+    "cloudboxes",
+    "x-oss-ac-source-ip",
+    "x-oss-ac-subnet-mask",
+    "x-oss-ac-vpc-id",
+    "x-oss-ac-forward-allow",
+    "metaQuery",
+    "resourceGroup",
+    "rtc",
+];
+
+/// V4 签名 url 的最长有效时长，7 天
+const V4_MAX_EXPIRES: i64 = 7 * 24 * 3600;
+const SECURITY_TOKEN_V1: &str = "security-token";
+const SECURITY_TOKEN_V4: &str = "x-oss-security-token";
+const RESPONSE_CONTENT_TYPE: &str = "response-content-type";
+const RESPONSE_CONTENT_DISPOSITION: &str = "response-content-disposition";
+
 #[cfg(feature = "core")]
 #[cfg(test)]
 mod test {
@@ -159,6 +631,8 @@ mod test {
     };
 
     use super::QueryAuth;
+    use crate::auth::SignVersion;
+    use http::header::HeaderName;
 
     fn init_config() -> Config {
         Config::new(
@@ -183,6 +657,12 @@ mod test {
         assert_eq!(auth.access_secret_key.as_str(), "foo2");
         assert_eq!(auth.endpoint, &EndPoint::CN_QINGDAO);
         assert_eq!(auth.bucket.as_ref(), "aaa");
+        assert_eq!(auth.version, SignVersion::V1);
+
+        let mut client = client;
+        client.sign_version(SignVersion::V4);
+        let auth = QueryAuth::from(&client);
+        assert_eq!(auth.version, SignVersion::V4);
     }
 
     #[test]
@@ -229,7 +709,7 @@ mod test {
     fn signature() {
         let config = init_config();
         let auth = QueryAuth::from(&config);
-        let string = auth.signature(&"img.png".parse().unwrap(), 1200);
+        let string = auth.signature(&"img.png".parse().unwrap(), 1200).unwrap();
         assert_eq!(string, "EQQzNJZptBDl8xJ6n2mQRG7oxkY=");
     }
 
@@ -237,7 +717,7 @@ mod test {
     fn to_url() {
         let config = init_config();
         let auth = QueryAuth::from(&config);
-        let string = auth.to_url(&"img.png".parse().unwrap(), 1200);
+        let string = auth.to_url(&"img.png".parse().unwrap(), 1200).unwrap();
         assert_eq!(string.as_str(), "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?OSSAccessKeyId=foo&Expires=1200&Signature=EQQzNJZptBDl8xJ6n2mQRG7oxkY%3D");
     }
 
@@ -246,7 +726,282 @@ mod test {
         let config = init_config();
         let auth = QueryAuth::from(&config);
         let mut url: Url = "https://example.com/image2.png".parse().unwrap();
-        auth.signature_url(&mut url, &"img.png".parse().unwrap(), 1200);
+        auth.signature_url(&mut url, &"img.png".parse().unwrap(), 1200)
+            .unwrap();
         assert_eq!(url.as_str(), "https://example.com/image2.png?OSSAccessKeyId=foo&Expires=1200&Signature=EQQzNJZptBDl8xJ6n2mQRG7oxkY%3D");
     }
+
+    #[test]
+    fn signature_url_v4() {
+        use chrono::{TimeZone, Utc};
+        use http::{HeaderMap, HeaderValue, Method};
+
+        let key = "ak".into();
+        let secret = "sk".into();
+        let bucket = "bucket".parse().unwrap();
+        let mut auth = QueryAuth::new(&key, &secret, &EndPoint::CN_HANGZHOU, &bucket);
+        auth.sign_version(SignVersion::V4);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-head1", HeaderValue::from_static("value"));
+        headers.insert("abc", HeaderValue::from_static("value"));
+        headers.insert("ZAbc", HeaderValue::from_static("value"));
+        headers.insert("XYZ", HeaderValue::from_static("value"));
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/octet-stream"),
+        );
+
+        let mut url: Url = "http://bucket.oss-cn-hangzhou.aliyuncs.com/1234%2B-/123/1.txt?param1=value1&%2Bparam1=value3&%7Cparam1=value4&%2Bparam2&%7Cparam2&param2".parse().unwrap();
+        auth.signature_url_v4(
+            &mut url,
+            &Method::PUT,
+            &"1234+-/123/1.txt".parse().unwrap(),
+            &headers,
+            &[],
+            Utc.timestamp_opt(1702781677, 0).unwrap(),
+            599,
+        )
+        .unwrap();
+
+        let query: Vec<_> = url.query_pairs().collect();
+        let get = |key: &str| {
+            query
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.to_string())
+        };
+        assert_eq!(get("x-oss-signature-version").unwrap(), "OSS4-HMAC-SHA256");
+        assert_eq!(get("x-oss-date").unwrap(), "20231217T025437Z");
+        assert_eq!(get("x-oss-expires").unwrap(), "599");
+        assert_eq!(
+            get("x-oss-credential").unwrap(),
+            "ak/20231217/cn-hangzhou/oss/aliyun_v4_request"
+        );
+        assert_eq!(
+            get("x-oss-signature").unwrap(),
+            "a39966c61718be0d5b14e668088b3fa07601033f6518ac7b523100014269c0fe"
+        );
+
+        let mut url: Url = "http://bucket.oss-cn-hangzhou.aliyuncs.com/1234%2B-/123/1.txt?param1=value1&%2Bparam1=value3&%7Cparam1=value4&%2Bparam2&%7Cparam2&param2".parse().unwrap();
+        auth.signature_url_v4(
+            &mut url,
+            &Method::PUT,
+            &"1234+-/123/1.txt".parse().unwrap(),
+            &headers,
+            &[
+                HeaderName::from_static("zabc"),
+                HeaderName::from_static("abc"),
+            ],
+            Utc.timestamp_opt(1702781677, 0).unwrap(),
+            599,
+        )
+        .unwrap();
+        let query: Vec<_> = url.query_pairs().collect();
+        assert!(query
+            .iter()
+            .any(|(k, v)| k == "x-oss-additional-headers" && v == "abc;zabc"));
+        assert!(query.iter().any(|(k, v)| k == "x-oss-signature"
+            && v == "7cd7a19d6b211cc90963db94b1167ab033c104a58f41580222e473c380f137b8"));
+    }
+
+    #[test]
+    fn to_url_v4() {
+        let config = init_config();
+        let mut auth = QueryAuth::from(&config);
+        auth.sign_version(SignVersion::V4);
+        let expires = chrono::Utc::now().timestamp() + 100;
+        let url = auth.to_url(&"img.png".parse().unwrap(), expires).unwrap();
+
+        assert_eq!(url.path(), "/img.png");
+        let query: Vec<_> = url.query_pairs().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(
+            query,
+            vec![
+                "x-oss-signature-version",
+                "x-oss-credential",
+                "x-oss-date",
+                "x-oss-expires",
+                "x-oss-signature"
+            ]
+        );
+        assert!(url.query().unwrap().contains("x-oss-credential=foo%2F"));
+    }
+
+    #[test]
+    fn sign_string_with() {
+        use super::SignUrlOptions;
+        use http::{HeaderValue, Method};
+
+        let config = init_config();
+        let auth = QueryAuth::from(&config);
+
+        let mut options = SignUrlOptions::new(Method::PUT);
+        options.content_type("image/png").header(
+            HeaderName::from_static("x-oss-meta-a"),
+            HeaderValue::from_static("1"),
+        );
+        options.header(
+            HeaderName::from_static("cache-control"),
+            HeaderValue::from_static("no-cache"),
+        );
+        let path = "img.png".parse().unwrap();
+        assert_eq!(
+            auth.sign_string_with(&path, &options, 1200),
+            "PUT\n\nimage/png\n1200\nx-oss-meta-a:1\n/aaa/img.png"
+        );
+        let url = auth.to_url_with(&path, &options, 1200).unwrap();
+        assert_eq!(url.as_str(), "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?OSSAccessKeyId=foo&Expires=1200&Signature=KdekqXUZenTUjz7AjLB562yZpmM%3D");
+
+        let mut options = SignUrlOptions::default();
+        options
+            .sub_resource("x-oss-process", "image/resize,w_100")
+            .response_content_disposition("attachment; filename=\"a b.png\"");
+        assert_eq!(
+            auth.sign_string_with(&path, &options, 1200),
+            "GET\n\n\n1200\n/aaa/img.png?response-content-disposition=attachment; filename=\"a b.png\"&x-oss-process=image/resize,w_100"
+        );
+        let url = auth.to_url_with(&path, &options, 1200).unwrap();
+        assert_eq!(url.as_str(), "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?x-oss-process=image%2Fresize%2Cw_100&response-content-disposition=attachment%3B%20filename%3D%22a%20b.png%22&OSSAccessKeyId=foo&Expires=1200&Signature=hnfK6oxwxzKTDK15sFcCgSZIqNE%3D");
+    }
+
+    #[test]
+    fn to_url_with_v4() {
+        use super::SignUrlOptions;
+        use http::Method;
+
+        let config = init_config();
+        let mut auth = QueryAuth::from(&config);
+        auth.sign_version(SignVersion::V4);
+
+        let mut options = SignUrlOptions::new(Method::PUT);
+        options
+            .content_type("image/png")
+            .response_content_type("text/plain");
+        let expires = chrono::Utc::now().timestamp() + 100;
+        let url = auth
+            .to_url_with(&"img.png".parse().unwrap(), &options, expires)
+            .unwrap();
+
+        let query: Vec<_> = url.query_pairs().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(
+            query,
+            vec![
+                "response-content-type",
+                "x-oss-signature-version",
+                "x-oss-credential",
+                "x-oss-date",
+                "x-oss-expires",
+                "x-oss-signature"
+            ]
+        );
+    }
+
+    #[test]
+    fn signature_url_keep_query() {
+        use super::SignUrlOptions;
+
+        let config = init_config();
+        let mut auth = QueryAuth::from(&config);
+        let path = "img.png".parse().unwrap();
+        let mut options = SignUrlOptions::default();
+        options.sub_resource("x-oss-process", "image/resize,w_100");
+
+        let mut url: Url =
+            "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?x-oss-process=image%2Fresize%2Cw_100"
+                .parse()
+                .unwrap();
+        auth.signature_url(&mut url, &path, 1200).unwrap();
+        assert_eq!(url, auth.to_url_with(&path, &options, 1200).unwrap());
+        assert!(url.query().unwrap().starts_with("x-oss-process="));
+
+        // 普通参数保留在 url 中，但不参与 V1 签名
+        let mut url: Url = "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?x-param=1&prefix=a%2F&x-oss-process=image%2Fresize%2Cw_100"
+            .parse()
+            .unwrap();
+        auth.signature_url(&mut url, &path, 1200).unwrap();
+        let signed = auth.to_url_with(&path, &options, 1200).unwrap();
+        assert_eq!(
+            url.query().unwrap(),
+            format!("x-param=1&prefix=a%2F&{}", signed.query().unwrap())
+        );
+
+        auth.sign_version(SignVersion::V4);
+        let expires = chrono::Utc::now().timestamp() + 100;
+        let mut url: Url =
+            "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?x-oss-process=image%2Fresize%2Cw_100"
+                .parse()
+                .unwrap();
+        auth.signature_url(&mut url, &path, expires).unwrap();
+        let query: Vec<_> = url.query_pairs().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(query[0], "x-oss-process");
+        assert_eq!(query.last().unwrap(), "x-oss-signature");
+    }
+
+    #[test]
+    fn to_url_v4_expires() {
+        let config = init_config();
+        let mut auth = QueryAuth::from(&config);
+        auth.sign_version(SignVersion::V4);
+        let path = "img.png".parse().unwrap();
+        let now = chrono::Utc::now().timestamp();
+
+        let err = auth.to_url(&path, now - 10).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expires must be between 1 second and 7 days"
+        );
+        assert!(auth.to_url(&path, now + 8 * 24 * 3600).is_err());
+        assert!(auth.to_url(&path, now + 3600).is_ok());
+
+        let mut url: Url = "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png"
+            .parse()
+            .unwrap();
+        assert!(auth
+            .signature_url_v4(
+                &mut url,
+                &http::Method::GET,
+                &path,
+                &http::HeaderMap::new(),
+                &[],
+                chrono::Utc::now(),
+                0,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn security_token() {
+        use crate::credentials::Credentials;
+
+        let credentials = Credentials::new_with_sts("foo".into(), "foo2".into(), "token", None);
+        let bucket = "aaa".parse().unwrap();
+        let mut auth = QueryAuth::from_credentials(&credentials, &EndPoint::CN_QINGDAO, &bucket);
+        let url = auth.to_url(&"img.png".parse().unwrap(), 1200).unwrap();
+        assert_eq!(url.as_str(), "https://aaa.oss-cn-qingdao.aliyuncs.com/img.png?security-token=token&OSSAccessKeyId=foo&Expires=1200&Signature=nU%2BhRDkWxP8iU7pc8XIlMsdkN0U%3D");
+
+        auth.sign_version(SignVersion::V4);
+        let expires = chrono::Utc::now().timestamp() + 100;
+        let url = auth.to_url(&"img.png".parse().unwrap(), expires).unwrap();
+        let query: Vec<_> = url.query_pairs().map(|(k, _)| k.to_string()).collect();
+        assert_eq!(
+            query,
+            vec![
+                "x-oss-signature-version",
+                "x-oss-credential",
+                "x-oss-date",
+                "x-oss-expires",
+                "x-oss-security-token",
+                "x-oss-signature"
+            ]
+        );
+
+        let mut config = init_config();
+        config.security_token = Some("token".parse().unwrap());
+        let auth = QueryAuth::from(&config);
+        assert_eq!(auth.security_token, Some("token"));
+        let client = Client::from_config(config);
+        let auth = QueryAuth::from(&client);
+        assert_eq!(auth.security_token, Some("token"));
+    }
 }
@@ -511,6 +511,25 @@ mod get_headers {
         );
     }
 
+    #[test]
+    fn test_content_md5_in_headers() {
+        let mut builder = AuthBuilder::default();
+        builder.key("foo1");
+        builder.secret("foo2");
+        builder.method(&Method::POST);
+        builder.date(unsafe { Date::from_static("foo_date") });
+        builder.canonicalized_resource("foo5");
+        builder.header_insert("Content-Type", "foo6".try_into().unwrap());
+        builder.header_insert("Content-MD5", "foo4".try_into().unwrap());
+        let map = builder.build().get_headers().unwrap();
+
+        assert_eq!(map.get("content-md5").unwrap(), &"foo4");
+        assert_eq!(
+            map.get("authorization").unwrap(),
+            &"OSS foo1:67qpyspFaWOYrWwahWKgNN+ngUY="
+        );
+    }
+
     #[test]
     fn test_append_headers() {
         let mut builder = AuthBuilder::default();
@@ -0,0 +1,330 @@
+//! # V4 签名
+//! 计算 `OSS4-HMAC-SHA256` 签名需要的规范请求（CanonicalRequest）和待签名字符串
+//!
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/recommend-to-use-signature-version-4)
+
+use chrono::{DateTime, Utc};
+use http::{
+    header::{HeaderMap, HeaderName, HOST},
+    Method,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use crate::types::to_hex;
+
+pub(crate) const ALGORITHM: &str = "OSS4-HMAC-SHA256";
+pub(crate) const OSS_DATE: &str = "x-oss-date";
+pub(crate) const CONTENT_SHA256: &str = "x-oss-content-sha256";
+pub(crate) const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+pub(crate) const ISO8601: &str = "%Y%m%dT%H%M%SZ";
+const SHORT_DATE: &str = "%Y%m%d";
+const PRODUCT: &str = "oss";
+const REQUEST: &str = "aliyun_v4_request";
+
+const X_OSS_PRE: &str = "x-oss-";
+const CONTENT_TYPE: &str = "content-type";
+const CONTENT_MD5: &str = "content-md5";
+
+/// RFC 3986 中除去非保留字符外，全部需要编码
+const URI_ENCODE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+/// 编码 path 时，保留 `/`
+const PATH_ENCODE: &AsciiSet = &URI_ENCODE.remove(b'/');
+
+/// 计算 V4 签名所需的请求信息
+pub(crate) struct CanonicalRequest<'a> {
+    pub(crate) method: &'a Method,
+    /// 形如 `/bucket/object`，其中 `?` 之后的部分会被忽略
+    pub(crate) resource: &'a str,
+    pub(crate) url: Option<&'a Url>,
+    pub(crate) headers: &'a HeaderMap,
+    pub(crate) additional_headers: &'a [HeaderName],
+}
+
+impl CanonicalRequest<'_> {
+    /// 参与签名的额外 header 名称，已排序，去重
+    ///
+    /// 不存在于 headers 中的会被忽略（host 除外，它可从 url 中获取）
+    pub(crate) fn additional_headers(&self) -> Vec<&str> {
+        let mut list: Vec<&str> = self
+            .additional_headers
+            .iter()
+            .map(HeaderName::as_str)
+            .filter(|name| !is_default_signed(name))
+            .filter(|name| {
+                self.headers.contains_key(*name) || (*name == HOST && self.host().is_some())
+            })
+            .collect();
+        list.sort_unstable();
+        list.dedup();
+        list
+    }
+
+    fn host(&self) -> Option<String> {
+        self.url.and_then(|url| {
+            url.host_str().map(|host| match url.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_owned(),
+            })
+        })
+    }
+
+    fn canonical_uri(&self) -> String {
+        let path = match self.resource.find('?') {
+            Some(index) => &self.resource[..index],
+            None => self.resource,
+        };
+        utf8_percent_encode(path, PATH_ENCODE).to_string()
+    }
+
+    fn canonical_query(&self) -> String {
+        let query = match self.url.and_then(Url::query) {
+            Some(q) => q,
+            None => return String::new(),
+        };
+        let mut list: Vec<_> = query
+            .split('&')
+            .filter(|item| !item.is_empty())
+            .map(|item| {
+                let (key, value) = item.split_once('=').unwrap_or((item, ""));
+                (uri_reencode(key), uri_reencode(value))
+            })
+            .collect();
+        list.sort();
+
+        list.iter()
+            .map(|(key, value)| {
+                if value.is_empty() {
+                    key.to_owned()
+                } else {
+                    format!("{key}={value}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    fn canonical_headers(&self, additional: &[&str]) -> String {
+        let mut names: Vec<&str> = self
+            .headers
+            .keys()
+            .map(HeaderName::as_str)
+            .filter(|name| is_default_signed(name) || additional.contains(name))
+            .collect();
+        if additional.contains(&HOST.as_str()) && !self.headers.contains_key(HOST) {
+            names.push(HOST.as_str());
+        }
+        names.sort_unstable();
+
+        names
+            .iter()
+            .map(|name| {
+                let value = if *name == HOST && !self.headers.contains_key(HOST) {
+                    self.host().unwrap_or_default()
+                } else {
+                    self.headers
+                        .get_all(*name)
+                        .iter()
+                        .filter_map(|v| v.to_str().ok())
+                        .map(str::trim)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                };
+                format!("{name}:{value}\n")
+            })
+            .collect()
+    }
+
+    fn hashed_payload(&self) -> &str {
+        self.headers
+            .get(CONTENT_SHA256)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or(UNSIGNED_PAYLOAD)
+    }
+
+    /// 拼接规范请求
+    pub(crate) fn to_canonical_string(&self) -> String {
+        let additional = self.additional_headers();
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            self.method.as_str(),
+            self.canonical_uri(),
+            self.canonical_query(),
+            self.canonical_headers(&additional),
+            additional.join(";"),
+            self.hashed_payload(),
+        )
+    }
+}
+
+fn is_default_signed(name: &str) -> bool {
+    name == CONTENT_TYPE || name == CONTENT_MD5 || name.starts_with(X_OSS_PRE)
+}
+
+/// 先解码，再按照 V4 签名的规则编码
+fn uri_reencode(input: &str) -> String {
+    let decoded = percent_decode_str(input).decode_utf8_lossy();
+    uri_encode(&decoded)
+}
+
+/// 按照 RFC 3986 编码，只保留非保留字符
+pub(crate) fn uri_encode(input: &str) -> String {
+    utf8_percent_encode(input, URI_ENCODE).to_string()
+}
+
+/// 按照 RFC 3986 编码，保留 `/`
+pub(crate) fn path_encode(input: &str) -> String {
+    utf8_percent_encode(input, PATH_ENCODE).to_string()
+}
+
+/// 签名的有效范围，形如 `20231203/cn-hangzhou/oss/aliyun_v4_request`
+pub(crate) fn scope(datetime: &DateTime<Utc>, region: &str) -> String {
+    format!(
+        "{}/{}/{}/{}",
+        datetime.format(SHORT_DATE),
+        region,
+        PRODUCT,
+        REQUEST
+    )
+}
+
+/// 待签名的字符串
+pub(crate) fn string_to_sign(datetime: &DateTime<Utc>, scope: &str, canonical: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n{}",
+        ALGORITHM,
+        datetime.format(ISO8601),
+        scope,
+        to_hex(&Sha256::digest(canonical.as_bytes()))
+    )
+}
+
+/// V4 签名中使用的短日期，形如 `20231203`
+pub(crate) fn short_date(datetime: &DateTime<Utc>) -> String {
+    datetime.format(SHORT_DATE).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use http::{HeaderMap, HeaderValue, Method};
+
+    use super::*;
+    use crate::auth::{AuthBuilder, SignVersion};
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-head1", HeaderValue::from_static("value"));
+        headers.insert("abc", HeaderValue::from_static("value"));
+        headers.insert("ZAbc", HeaderValue::from_static("value"));
+        headers.insert("XYZ", HeaderValue::from_static("value"));
+        headers.insert("content-type", HeaderValue::from_static("text/plain"));
+        headers.insert(CONTENT_SHA256, HeaderValue::from_static(UNSIGNED_PAYLOAD));
+        headers
+    }
+
+    fn url() -> Url {
+        "http://bucket.oss-cn-hangzhou.aliyuncs.com/1234%2B-/123/1.txt?param1=value1&%2Bparam1=value3&%7Cparam1=value4&%2Bparam2&%7Cparam2&param2"
+            .parse()
+            .unwrap()
+    }
+
+    fn builder() -> AuthBuilder {
+        let mut builder = AuthBuilder::default();
+        builder.key("ak");
+        builder.secret("sk");
+        builder.sign_version(SignVersion::V4);
+        builder.region("cn-hangzhou");
+        builder.method(&Method::PUT);
+        builder.date(Utc.timestamp_opt(1702743657, 0).unwrap());
+        builder.canonicalized_resource("/bucket/1234+-/123/1.txt");
+        builder.url(&url());
+        builder.headers(headers());
+        builder
+    }
+
+    #[test]
+    fn canonical_string() {
+        let mut headers = headers();
+        headers.insert(OSS_DATE, HeaderValue::from_static("20231216T162057Z"));
+        let url = url();
+        let req = CanonicalRequest {
+            method: &Method::PUT,
+            resource: "/bucket/1234+-/123/1.txt",
+            url: Some(&url),
+            headers: &headers,
+            additional_headers: &[],
+        };
+
+        assert_eq!(
+            req.to_canonical_string(),
+            "PUT\n/bucket/1234%2B-/123/1.txt\n%2Bparam1=value3&%2Bparam2&%7Cparam1=value4&%7Cparam2&param1=value1&param2\ncontent-type:text/plain\nx-oss-content-sha256:UNSIGNED-PAYLOAD\nx-oss-date:20231216T162057Z\nx-oss-head1:value\n\n\nUNSIGNED-PAYLOAD"
+        );
+    }
+
+    #[test]
+    fn additional_headers() {
+        let headers = headers();
+        let url = url();
+        let req = CanonicalRequest {
+            method: &Method::PUT,
+            resource: "/bucket/",
+            url: Some(&url),
+            headers: &headers,
+            additional_headers: &[
+                HeaderName::from_static("zabc"),
+                HeaderName::from_static("abc"),
+                HeaderName::from_static("x-oss-head1"),
+                HeaderName::from_static("not-exists"),
+                HeaderName::from_static("abc"),
+                HOST,
+            ],
+        };
+
+        assert_eq!(req.additional_headers(), vec!["abc", "host", "zabc"]);
+        assert!(req
+            .to_canonical_string()
+            .contains("host:bucket.oss-cn-hangzhou.aliyuncs.com\n"));
+    }
+
+    #[test]
+    fn test_scope() {
+        let time = Utc.timestamp_opt(1702743657, 0).unwrap();
+        assert_eq!(
+            scope(&time, "cn-hangzhou"),
+            "20231216/cn-hangzhou/oss/aliyun_v4_request"
+        );
+    }
+
+    #[test]
+    fn sign_header() {
+        let headers = builder().get_headers().unwrap();
+
+        assert_eq!(
+            headers.get("authorization").unwrap(),
+            "OSS4-HMAC-SHA256 Credential=ak/20231216/cn-hangzhou/oss/aliyun_v4_request,Signature=e21d18daa82167720f9b1047ae7e7f1ce7cb77a31e8203a7d5f4624fa0284afe"
+        );
+        assert_eq!(headers.get(OSS_DATE).unwrap(), "20231216T162057Z");
+        assert_eq!(headers.get(CONTENT_SHA256).unwrap(), UNSIGNED_PAYLOAD);
+    }
+
+    #[test]
+    fn sign_header_with_additional() {
+        let mut builder = builder();
+        builder.additional_headers([
+            HeaderName::from_static("zabc"),
+            HeaderName::from_static("abc"),
+        ]);
+        let headers = builder.get_headers().unwrap();
+
+        assert_eq!(
+            headers.get("authorization").unwrap(),
+            "OSS4-HMAC-SHA256 Credential=ak/20231216/cn-hangzhou/oss/aliyun_v4_request,AdditionalHeaders=abc;zabc,Signature=47c9e374f165ecb7e92d150f3e9639082b74d3578a56e388f81a2ad652f4d254"
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{builder::BuilderError, errors::OssService};
+use crate::{
+    builder::BuilderError,
+    credentials::{Credentials, CredentialsCache, CredentialsError},
+    errors::OssService,
+};
 use http::{
     header::{HeaderMap, HeaderName, HeaderValue},
     Method,
@@ -7,12 +11,16 @@ use reqwest::{
     blocking::{self, Body, Request, Response},
     IntoUrl,
 };
-use std::{rc::Rc, time::Duration};
+use std::{rc::Rc, sync::Arc, time::Duration};
+use tokio::runtime::{Builder, Runtime};
 
 #[derive(Default, Clone, Debug)]
 pub struct ClientWithMiddleware {
     inner: blocking::Client,
     middleware: Option<Rc<dyn Middleware>>,
+    credentials: Option<Arc<CredentialsCache>>,
+    /// provider 可能使用 reqwest 的异步请求，需要在 tokio runtime 中获取凭证
+    runtime: Option<Rc<Runtime>>,
 }
 
 pub trait Middleware: 'static + std::fmt::Debug {
@@ -24,6 +32,8 @@ impl ClientWithMiddleware {
         Self {
             inner,
             middleware: None,
+            credentials: None,
+            runtime: None,
         }
     }
 
@@ -37,6 +47,27 @@ impl ClientWithMiddleware {
     pub fn middleware(&mut self, middleware: Rc<dyn Middleware>) {
         self.middleware = Some(middleware);
     }
+
+    pub(crate) fn credentials(&mut self, credentials: CredentialsCache) {
+        self.credentials = Some(Arc::new(credentials));
+        self.runtime = new_runtime().ok().map(Rc::new);
+    }
+
+    /// 设置了 provider 时，在单线程的 tokio runtime 中获取凭证
+    pub(crate) fn get_credentials(&self) -> Option<Result<Credentials, CredentialsError>> {
+        let cache = self.credentials.as_ref()?;
+        let res = match &self.runtime {
+            Some(runtime) => runtime.block_on(cache.get()),
+            None => new_runtime()
+                .map_err(CredentialsError::new)
+                .and_then(|runtime| runtime.block_on(cache.get())),
+        };
+        Some(res)
+    }
+}
+
+fn new_runtime() -> std::io::Result<Runtime> {
+    Builder::new_current_thread().enable_all().build()
 }
 
 pub struct RequestBuilder {
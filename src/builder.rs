@@ -14,12 +14,13 @@ use std::{
 };
 use std::{fmt::Display, sync::Arc, time::Duration};
 
-use crate::auth::AuthError;
+use crate::auth::{AuthBuilder, AuthError};
 #[cfg(feature = "blocking")]
 use crate::blocking::builder::ClientWithMiddleware as BlockingClientWithMiddleware;
 use crate::{
     client::Client as AliClient,
     config::{BucketBase, InvalidConfig},
+    credentials::{CredentialsCache, CredentialsError},
     errors::OssService,
 };
 use reqwest::{Client, Request, Response};
@@ -67,6 +68,7 @@ impl PointerFamily for RcPointer {
 pub struct ClientWithMiddleware {
     inner: Client,
     middleware: Option<Arc<dyn Middleware>>,
+    credentials: Option<Arc<CredentialsCache>>,
 }
 
 #[async_trait]
@@ -79,6 +81,7 @@ impl ClientWithMiddleware {
         Self {
             inner,
             middleware: None,
+            credentials: None,
         }
     }
 
@@ -86,17 +89,28 @@ impl ClientWithMiddleware {
         RequestBuilder {
             inner: self.inner.request(method, url),
             middleware: self.middleware.clone(),
+            auth: None,
         }
     }
 
     pub fn middleware(&mut self, middleware: Arc<dyn Middleware>) {
         self.middleware = Some(middleware);
     }
+
+    pub(crate) fn credentials(&mut self, credentials: CredentialsCache) {
+        self.credentials = Some(Arc::new(credentials));
+    }
+
+    pub(crate) fn get_credentials(&self) -> Option<&Arc<CredentialsCache>> {
+        self.credentials.as_ref()
+    }
 }
 
 pub struct RequestBuilder {
     inner: reqwest::RequestBuilder,
     middleware: Option<Arc<dyn Middleware>>,
+    /// 待签名的信息，在发送请求前，使用 provider 提供的凭证签名
+    auth: Option<(AuthBuilder, Arc<CredentialsCache>)>,
 }
 
 impl RequestBuilder {
@@ -135,30 +149,48 @@ impl RequestBuilder {
         }
     }
 
+    /// 延迟到发送请求前再签名
+    pub(crate) fn auth(self, auth: AuthBuilder, credentials: Arc<CredentialsCache>) -> Self {
+        RequestBuilder {
+            auth: Some((auth, credentials)),
+            ..self
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn build(self) -> reqwest::Result<Request> {
         self.inner.build()
     }
 
+    /// 使用 provider 提供的凭证完成签名
+    async fn sign(
+        self,
+    ) -> Result<(reqwest::RequestBuilder, Option<Arc<dyn Middleware>>), BuilderError> {
+        let inner = match self.auth {
+            Some((mut auth, credentials)) => {
+                credentials.get().await?.apply(&mut auth)?;
+                self.inner.headers(auth.get_headers()?)
+            }
+            None => self.inner,
+        };
+        Ok((inner, self.middleware))
+    }
+
     /// 发送请求，获取响应后，直接返回 Response
     pub async fn send(self) -> Result<Response, BuilderError> {
-        match self.middleware {
-            Some(m) => {
-                m.handle(self.inner.build().map_err(BuilderError::from)?)
-                    .await
-            }
-            None => self.inner.send().await.map_err(BuilderError::from),
+        let (inner, middleware) = self.sign().await?;
+        match middleware {
+            Some(m) => m.handle(inner.build().map_err(BuilderError::from)?).await,
+            None => inner.send().await.map_err(BuilderError::from),
         }
     }
 
     /// 发送请求，获取响应后，解析 xml 文件，如果有错误，返回 Err 否则返回 Response
     pub async fn send_adjust_error(self) -> Result<Response, BuilderError> {
-        match self.middleware {
-            Some(m) => {
-                m.handle(self.inner.build().map_err(BuilderError::from)?)
-                    .await
-            }
-            None => check_http_status(self.inner.send().await.map_err(BuilderError::from)?)
+        let (inner, middleware) = self.sign().await?;
+        match middleware {
+            Some(m) => m.handle(inner.build().map_err(BuilderError::from)?).await,
+            None => check_http_status(inner.send().await.map_err(BuilderError::from)?)
                 .await
                 .map_err(BuilderError::from),
         }
@@ -197,6 +229,8 @@ pub(crate) enum BuilderErrorKind {
 
     Config(Box<InvalidConfig>),
 
+    Credentials(Box<CredentialsError>),
+
     #[cfg(test)]
     Bar,
 }
@@ -209,6 +243,7 @@ impl Display for BuilderError {
             OssService(_) => "http status is not success".fmt(f),
             Auth(_) => "aliyun auth failed".fmt(f),
             Config(_) => "oss config error".fmt(f),
+            Credentials(_) => "get credentials failed".fmt(f),
             #[cfg(test)]
             Bar => "bar".fmt(f),
         }
@@ -223,6 +258,7 @@ impl Error for BuilderError {
             OssService(e) => Some(e),
             Auth(e) => Some(e),
             Config(e) => Some(e),
+            Credentials(e) => Some(e),
             #[cfg(test)]
             Bar => None,
         }
@@ -259,6 +295,14 @@ impl From<InvalidConfig> for BuilderError {
     }
 }
 
+impl From<CredentialsError> for BuilderError {
+    fn from(value: CredentialsError) -> Self {
+        Self {
+            kind: BuilderErrorKind::Credentials(Box::new(value)),
+        }
+    }
+}
+
 impl From<BuilderError> for io::Error {
     fn from(BuilderError { kind }: BuilderError) -> Self {
         match kind {
@@ -266,6 +310,7 @@ impl From<BuilderError> for io::Error {
             BuilderErrorKind::OssService(e) => Self::from(*e),
             BuilderErrorKind::Auth(auth) => Self::new(ErrorKind::PermissionDenied, auth),
             BuilderErrorKind::Config(conf) => Self::new(ErrorKind::InvalidInput, conf),
+            BuilderErrorKind::Credentials(e) => Self::new(ErrorKind::PermissionDenied, e),
             #[cfg(test)]
             BuilderErrorKind::Bar => unreachable!("only used in tests"),
         }
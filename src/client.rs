@@ -1,6 +1,6 @@
 //! # 对 reqwest 进行了简单的封装，加上了 OSS 的签名验证功能
 
-use crate::auth::AuthBuilder;
+use crate::auth::{AuthBuilder, SignVersion};
 #[cfg(feature = "blocking")]
 use crate::blocking::builder::ClientWithMiddleware as BlockingClientWithMiddleware;
 #[cfg(test)]
@@ -8,6 +8,9 @@ use crate::builder::Middleware;
 use crate::builder::{ArcPointer, BuilderError, ClientWithMiddleware, RequestBuilder};
 use crate::config::{get_bucket, get_endpoint, get_env, BucketBase, Config, InvalidConfig};
 use crate::consts::{TRUE1, TRUE2, TRUE3, TRUE4};
+use crate::credentials::{
+    Credentials, CredentialsCache, CredentialsError, CredentialsProvider, SECURITY_TOKEN,
+};
 use crate::file::AlignBuilder;
 use crate::types::{
     object::{InvalidObjectPath, ObjectBase, ObjectPath},
@@ -36,6 +39,8 @@ pub struct Client<M = ClientWithMiddleware> {
     pub(crate) endpoint: EndPoint,
     pub(crate) bucket: BucketName,
     timeout: Option<Duration>,
+    crc64: bool,
+    content_md5: bool,
 }
 
 impl<M> AsMut<Option<Duration>> for Client<M> {
@@ -91,11 +96,15 @@ impl<M: Default> Client<M> {
     ///
     /// [`Config`]: crate::config::Config
     pub fn from_config(config: Config) -> Self {
+        let token = config.get_security_token().cloned();
         let (key, secret, bucket, endpoint) = config.get_all();
 
         let mut auth_builder = AuthBuilder::default();
         auth_builder.key(key);
         auth_builder.secret(secret);
+        if let Some(token) = token {
+            auth_builder.header_insert(SECURITY_TOKEN, token);
+        }
 
         Self::from_builder(auth_builder, endpoint, bucket)
     }
@@ -156,6 +165,8 @@ impl<M: Default> Client<M> {
             endpoint,
             bucket,
             timeout: None,
+            crc64: false,
+            content_md5: false,
         }
     }
 }
@@ -180,6 +191,24 @@ impl<M> Client<M> {
     pub(crate) fn get_secret(&self) -> &KeySecret {
         self.auth_builder.get_secret()
     }
+    pub(crate) fn get_sign_version(&self) -> SignVersion {
+        self.auth_builder.get_sign_version()
+    }
+    pub(crate) fn get_security_token(&self) -> Option<&str> {
+        self.auth_builder.get_security_token()
+    }
+    /// 初始化时设置的 key，secret 和 STS token
+    fn static_credentials(&self) -> Credentials {
+        match self.get_security_token() {
+            Some(token) => Credentials::new_with_sts(
+                self.get_key().clone(),
+                self.get_secret().clone(),
+                token,
+                None,
+            ),
+            None => Credentials::new(self.get_key().clone(), self.get_secret().clone()),
+        }
+    }
 
     // pub(crate) fn get_endpoint(&self) -> &EndPoint {
     //     &self.endpoint
@@ -205,6 +234,60 @@ impl<M> Client<M> {
         self.timeout = Some(timeout);
     }
 
+    /// # 开启 CRC64 校验
+    /// 开启后，上传文件（包括追加上传和分片上传）和完整下载文件时，会将本地计算的 CRC64 值
+    /// 与 OSS 返回的 `x-oss-hash-crc64ecma` 比对，不一致时返回错误
+    pub fn enable_crc64(&mut self, enable: bool) {
+        self.crc64 = enable;
+    }
+
+    /// # 自动计算 Content-MD5
+    /// 开启后，上传文件和上传分片时，会计算请求体的 Content-MD5 并参与签名，
+    /// 内容在传输中损坏时，OSS 会拒绝这次上传（批量删除始终会携带 Content-MD5）
+    pub fn enable_content_md5(&mut self, enable: bool) {
+        self.content_md5 = enable;
+    }
+
+    /// # 设置签名版本
+    /// 默认使用 V1 签名，设置为 V4 后，会使用 endpoint 对应的 region 计算签名
+    ///
+    /// ```
+    /// # use aliyun_oss_client::{auth::SignVersion, Client};
+    /// let mut client = Client::new(
+    ///     "key1".into(),
+    ///     "secret1".into(),
+    ///     "qingdao".parse().unwrap(),
+    ///     "my-bucket".parse().unwrap(),
+    /// );
+    /// client.sign_version(SignVersion::V4);
+    /// ```
+    pub fn sign_version(&mut self, version: SignVersion) {
+        self.auth_builder.sign_version(version);
+    }
+
+    /// 设置 V4 签名中额外参与签名的 header，如 `host`
+    pub fn additional_headers<I: IntoIterator<Item = HeaderName>>(&mut self, headers: I) {
+        self.auth_builder.additional_headers(headers);
+    }
+
+    /// 填充签名需要的请求信息
+    fn request_auth<H: IntoIterator<Item = (HeaderName, HeaderValue)>>(
+        &self,
+        method: &Method,
+        url: &Url,
+        resource: CanonicalizedResource,
+        headers: H,
+    ) -> AuthBuilder {
+        let mut auth_builder = self.auth_builder.clone();
+        auth_builder.method(method);
+        auth_builder.date(now());
+        auth_builder.canonicalized_resource(resource);
+        auth_builder.extend_headers(HeaderMap::from_iter(headers));
+        auth_builder.region(self.endpoint.as_ref().to_owned());
+        auth_builder.url(url);
+        auth_builder
+    }
+
     /// 根据默认的 bucket，endpoint 和提供的文件路径，获取 ObjectBase
     #[inline]
     pub fn get_object_base<P>(&self, path: P) -> Result<ObjectBase, InvalidObjectPath>
@@ -233,6 +316,32 @@ fn now() -> DateTime<Utc> {
 pub type ClientArc = Client<ClientWithMiddleware>;
 
 impl Client {
+    /// # 设置凭证的提供者
+    /// 设置后，每次请求都会从 provider 获取 key，secret 和 STS token 来签名，
+    /// 凭证会被缓存，并在过期前自动刷新，详见 [`credentials`]
+    ///
+    /// [`credentials`]: crate::credentials
+    pub fn credentials_provider<P: CredentialsProvider>(&mut self, provider: P) {
+        self.credentials_cache(CredentialsCache::new(provider));
+    }
+
+    pub(crate) fn credentials_cache(&mut self, cache: CredentialsCache) {
+        self.client_middleware.credentials(cache);
+    }
+
+    /// # 获取当前使用的凭证
+    /// 设置了 provider 时，返回缓存或重新获取的凭证，否则返回初始化时的 key，secret 和 STS token
+    ///
+    /// 可用于生成带签名的 url 或 PostObject 表单，见 [`QueryAuth::from_credentials`]
+    ///
+    /// [`QueryAuth::from_credentials`]: crate::auth::QueryAuth::from_credentials
+    pub async fn get_credentials(&self) -> Result<Credentials, CredentialsError> {
+        match self.client_middleware.get_credentials() {
+            Some(cache) => cache.get().await,
+            None => Ok(self.static_credentials()),
+        }
+    }
+
     /// # 用于模拟请求 OSS 接口
     /// 默认直接请求 OSS 接口，如果设置中间件，则可以中断请求，对 Request 做一些断言，对 Response 做一些模拟操作
     #[cfg(test)]
@@ -296,16 +405,13 @@ impl AlignBuilder for Client<ClientWithMiddleware> {
         resource: CanonicalizedResource,
         headers: H,
     ) -> Result<RequestBuilder, BuilderError> {
-        let mut auth_builder = self.auth_builder.clone();
-        auth_builder.method(&method);
-        auth_builder.date(now());
-        auth_builder.canonicalized_resource(resource);
-        auth_builder.extend_headers(HeaderMap::from_iter(headers));
+        let auth_builder = self.request_auth(&method, &url, resource, headers);
 
-        let mut builder = self
-            .client_middleware
-            .request(method, url)
-            .headers(auth_builder.get_headers()?);
+        let mut builder = self.client_middleware.request(method, url);
+        builder = match self.client_middleware.get_credentials() {
+            Some(credentials) => builder.auth(auth_builder, credentials.clone()),
+            None => builder.headers(auth_builder.get_headers()?),
+        };
 
         if let Some(timeout) = self.timeout {
             builder = builder.timeout(timeout);
@@ -313,6 +419,14 @@ impl AlignBuilder for Client<ClientWithMiddleware> {
 
         Ok(builder)
     }
+
+    fn crc64_enabled(&self) -> bool {
+        self.crc64
+    }
+
+    fn content_md5_enabled(&self) -> bool {
+        self.content_md5
+    }
 }
 
 #[cfg(all(feature = "blocking", test))]
@@ -326,6 +440,24 @@ pub type ClientRc = Client<BlockingClientWithMiddleware>;
 
 #[cfg(feature = "blocking")]
 impl Client<BlockingClientWithMiddleware> {
+    /// # 设置凭证的提供者
+    /// 设置后，每次请求都会从 provider 获取 key，secret 和 STS token 来签名，
+    /// 凭证会被缓存，并在过期前自动刷新，详见 [`credentials`]
+    ///
+    /// [`credentials`]: crate::credentials
+    pub fn credentials_provider<P: CredentialsProvider>(&mut self, provider: P) {
+        self.client_middleware
+            .credentials(CredentialsCache::new(provider));
+    }
+
+    /// # 获取当前使用的凭证
+    /// 设置了 provider 时，返回缓存或重新获取的凭证，否则返回初始化时的 key，secret 和 STS token
+    pub fn get_credentials(&self) -> Result<Credentials, CredentialsError> {
+        self.client_middleware
+            .get_credentials()
+            .unwrap_or_else(|| Ok(self.static_credentials()))
+    }
+
     /// # 用于模拟请求 OSS 接口
     /// 默认直接请求 OSS 接口，如果设置中间件，则可以中断请求，对 Request 做一些断言，对 Response 做一些模拟操作
     #[cfg(test)]
@@ -356,17 +488,13 @@ impl crate::file::blocking::AlignBuilder for Client<BlockingClientWithMiddleware
         resource: CanonicalizedResource,
         headers: H,
     ) -> Result<BlockingRequestBuilder, BuilderError> {
-        let method = method;
-        let mut auth_builder = self.auth_builder.clone();
-        auth_builder.method(&method);
-        auth_builder.date(now());
-        auth_builder.canonicalized_resource(resource);
-        auth_builder.extend_headers(HeaderMap::from_iter(headers));
+        let mut auth_builder = self.request_auth(&method, &url, resource, headers);
+        if let Some(credentials) = self.client_middleware.get_credentials() {
+            credentials?.apply(&mut auth_builder)?;
+        }
+        let headers = auth_builder.get_headers()?;
 
-        let mut builder = self
-            .client_middleware
-            .request(method, url)
-            .headers(auth_builder.get_headers()?);
+        let mut builder = self.client_middleware.request(method, url).headers(headers);
 
         if let Some(timeout) = self.timeout {
             builder = builder.timeout(timeout);
@@ -374,6 +502,14 @@ impl crate::file::blocking::AlignBuilder for Client<BlockingClientWithMiddleware
 
         Ok(builder)
     }
+
+    fn crc64_enabled(&self) -> bool {
+        self.crc64
+    }
+
+    fn content_md5_enabled(&self) -> bool {
+        self.content_md5
+    }
 }
 
 #[cfg(test)]
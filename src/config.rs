@@ -11,15 +11,23 @@ use crate::{
     },
     Query,
 };
+use http::HeaderValue;
 use reqwest::Url;
 use std::{
     env::{self, VarError},
     error::Error,
     fmt::Display,
+    io,
+    path::Path,
     str::FromStr,
+    sync::Arc,
 };
 use thiserror::Error;
 
+mod profile;
+
+use profile::{normalize_endpoint, Profile};
+
 const HTTPS: &str = "https://";
 const OSS_HYPHEN: &str = "oss-";
 
@@ -30,6 +38,7 @@ pub struct Config {
     pub(crate) secret: KeySecret,
     pub(crate) endpoint: EndPoint,
     pub(crate) bucket: BucketName,
+    pub(crate) security_token: Option<HeaderValue>,
 }
 
 impl AsRef<KeyId> for Config {
@@ -70,6 +79,7 @@ impl Config {
             secret: secret.into(),
             endpoint: endpoint.into(),
             bucket: bucket.into(),
+            security_token: None,
         }
     }
 
@@ -90,9 +100,66 @@ impl Config {
                 source: String::default(),
                 kind: InvalidConfigKind::BucketName(e),
             })?,
+            security_token: None,
         })
     }
 
+    /// # 使用配置文件中的 profile 初始化
+    ///
+    /// 依次读取 `~/.ossutilconfig`，`~/.alibabacloud/credentials`，`~/.aliyun/config.json`，
+    /// 使用第一个包含该 profile 的文件，配置项的优先级为：环境变量 > profile > 默认值
+    ///
+    /// 支持的环境变量有 `ALIYUN_KEY_ID`，`ALIYUN_KEY_SECRET`，`ALIYUN_STS_TOKEN`，
+    /// `ALIYUN_ENDPOINT`，`ALIYUN_BUCKET`，`ALIYUN_OSS_INTERNAL`
+    ///
+    /// ossutil 中的 `[Credentials]` 对应名称为 `default` 的 profile
+    pub fn from_profile(name: &str) -> Result<Self, InvalidConfig> {
+        let profile = Profile::from_default_files(name).map_err(|e| InvalidConfig {
+            source: name.to_owned(),
+            kind: InvalidConfigKind::File(ReadFileError(Arc::new(e))),
+        })?;
+        Self::from_profile_inner(profile, name)
+    }
+
+    /// 使用指定配置文件中的 profile 初始化，文件可以是 ini 或 json 格式
+    ///
+    /// 优先级与 [`from_profile`] 相同
+    ///
+    /// [`from_profile`]: Config::from_profile
+    pub fn from_profile_file<P: AsRef<Path>>(path: P, name: &str) -> Result<Self, InvalidConfig> {
+        let profile = Profile::from_file(path.as_ref(), name).map_err(|e| InvalidConfig {
+            source: path.as_ref().display().to_string(),
+            kind: InvalidConfigKind::File(ReadFileError(Arc::new(e))),
+        })?;
+        Self::from_profile_inner(profile, name)
+    }
+
+    fn from_profile_inner(profile: Option<Profile>, name: &str) -> Result<Self, InvalidConfig> {
+        let env = Profile::from_env();
+        let profile = match profile {
+            Some(profile) => env.or(profile),
+            // 环境变量中有完整配置时，允许 profile 不存在
+            None if env.key.is_some() && env.secret.is_some() && env.bucket.is_some() => env,
+            None => {
+                return Err(InvalidConfig {
+                    source: name.to_owned(),
+                    kind: InvalidConfigKind::Profile,
+                })
+            }
+        };
+        Self::try_from(profile).map_err(|mut e| {
+            if e.kind == InvalidConfigKind::Profile {
+                e.source = format!("{name}.{}", e.source);
+            }
+            e
+        })
+    }
+
+    /// 使用 STS 临时凭证时的 token
+    pub fn get_security_token(&self) -> Option<&HeaderValue> {
+        self.security_token.as_ref()
+    }
+
     /// 初始化 OSS 配置信息
     ///
     /// [未稳定] 暂不公开
@@ -124,6 +191,7 @@ impl Config {
                 source: bucket.to_string(),
                 kind: InvalidConfigKind::BucketName(e.into()),
             })?,
+            security_token: None,
         })
     }
 
@@ -137,6 +205,43 @@ impl Config {
     }
 }
 
+impl TryFrom<Profile> for Config {
+    type Error = InvalidConfig;
+    fn try_from(profile: Profile) -> Result<Self, Self::Error> {
+        let missing = |item: &str| InvalidConfig {
+            source: item.to_owned(),
+            kind: InvalidConfigKind::Profile,
+        };
+
+        let key = profile.key.ok_or_else(|| missing("access_key_id"))?;
+        let secret = profile.secret.ok_or_else(|| missing("access_key_secret"))?;
+        let bucket = get_bucket(&profile.bucket.ok_or_else(|| missing("bucket"))?)?;
+        let mut endpoint = match profile.endpoint {
+            Some(endpoint) => get_endpoint(normalize_endpoint(&endpoint))?,
+            None => EndPoint::default(),
+        };
+        if let Some(internal) = profile.internal {
+            endpoint.set_internal(internal);
+        }
+        let security_token = match profile.security_token {
+            Some(token) => {
+                let mut token = HeaderValue::from_str(&token).map_err(|_| missing("sts_token"))?;
+                token.set_sensitive(true);
+                Some(token)
+            }
+            None => None,
+        };
+
+        Ok(Config {
+            key: key.into(),
+            secret: secret.into(),
+            endpoint,
+            bucket,
+            security_token,
+        })
+    }
+}
+
 pub(crate) fn get_env(name: &str) -> Result<String, InvalidConfig> {
     env::var(name).map_err(|e| InvalidConfig {
         source: name.to_owned(),
@@ -192,6 +297,8 @@ impl Display for InvalidConfig {
         match &self.kind {
             EndPoint(_) | BucketName(_) => write!(f, "get config failed, source: {}", self.source),
             VarError(_) => write!(f, "get config failed, env name: {}", self.source),
+            Profile => write!(f, "get config failed, profile: {}", self.source),
+            File(_) => write!(f, "read config file failed, source: {}", self.source),
         }
     }
 }
@@ -202,6 +309,8 @@ impl Error for InvalidConfig {
             EndPoint(e) => Some(e),
             BucketName(e) => Some(e),
             VarError(e) => Some(e),
+            File(e) => Some(e.0.as_ref()),
+            Profile => None,
         }
     }
 }
@@ -217,6 +326,22 @@ pub(crate) enum InvalidConfigKind {
 
     /// 非法的环境变量
     VarError(VarError),
+
+    /// profile 不存在，或缺少必要的配置项
+    Profile,
+
+    /// 读取配置文件失败
+    File(ReadFileError),
+}
+
+/// 读取配置文件时的 io 错误，用 Arc 包装以便 [`InvalidConfig`] 可以 Clone，按错误类型比较
+#[derive(Debug, Clone)]
+pub(crate) struct ReadFileError(Arc<io::Error>);
+
+impl PartialEq for ReadFileError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind()
+    }
 }
 
 /// # Bucket 元信息
@@ -699,6 +824,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_from_profile() {
+        let profile = Profile {
+            key: Some("foo1".to_owned()),
+            secret: Some("foo2".to_owned()),
+            security_token: Some("token".to_owned()),
+            endpoint: Some("https://oss-cn-qingdao.aliyuncs.com".to_owned()),
+            bucket: Some("foo4".to_owned()),
+            internal: None,
+        };
+        let env = Profile {
+            bucket: Some("bar".to_owned()),
+            internal: Some(true),
+            ..Default::default()
+        };
+        let config = Config::try_from(env.or(profile.clone())).unwrap();
+        assert_eq!(config.key.as_ref(), "foo1");
+        assert_eq!(config.bucket.as_ref(), "bar");
+        assert_eq!(config.endpoint.kind, EndPointKind::CnQingdao);
+        assert!(config.endpoint.is_internal());
+        assert_eq!(config.get_security_token().unwrap(), "token");
+
+        let config = Config::try_from(Profile {
+            endpoint: None,
+            ..profile
+        })
+        .unwrap();
+        assert_eq!(config.endpoint, EndPoint::default());
+
+        let err = Config::try_from(Profile::default()).unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "get config failed, profile: access_key_id"
+        );
+    }
+
+    #[test]
+    fn config_from_profile_file() {
+        let path = env::temp_dir().join(format!(
+            "aliyun-oss-client-not-exists-{}",
+            std::process::id()
+        ));
+        let err = Config::from_profile_file(&path, "default").unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            format!("read config file failed, source: {}", path.display())
+        );
+        let source = err.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::NotFound);
+    }
+
     #[test]
     fn test_base_as() {
         fn assert_as_mut_endpoint<E: AsMut<EndPoint>>(e: &mut E) {
@@ -0,0 +1,391 @@
+//! 读取配置文件中的 profile
+//!
+//! 支持以下文件：
+//! - `~/.ossutilconfig` ossutil 的 ini 配置
+//! - `~/.alibabacloud/credentials` ini 或 json 格式的凭证配置
+//! - `~/.aliyun/config.json` aliyun cli 的 json 配置
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::consts::{TRUE1, TRUE2, TRUE3, TRUE4};
+
+/// ossutil 中默认配置的 section 名称
+const OSSUTIL_DEFAULT: &str = "Credentials";
+const DEFAULT: &str = "default";
+
+/// 配置文件中的一组配置，未配置的项为 `None`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Profile {
+    pub(crate) key: Option<String>,
+    pub(crate) secret: Option<String>,
+    pub(crate) security_token: Option<String>,
+    pub(crate) endpoint: Option<String>,
+    pub(crate) bucket: Option<String>,
+    pub(crate) internal: Option<bool>,
+}
+
+impl Profile {
+    /// 读取环境变量中的配置
+    pub(crate) fn from_env() -> Self {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+        Self {
+            key: var("ALIYUN_KEY_ID"),
+            secret: var("ALIYUN_KEY_SECRET"),
+            security_token: var("ALIYUN_STS_TOKEN"),
+            endpoint: var("ALIYUN_ENDPOINT"),
+            bucket: var("ALIYUN_BUCKET"),
+            internal: var("ALIYUN_OSS_INTERNAL").map(|v| is_true(&v)),
+        }
+    }
+
+    /// 依次读取默认的配置文件，使用第一个包含该 profile 的文件，不同文件中的配置项不会混用
+    pub(crate) fn from_default_files(name: &str) -> io::Result<Option<Self>> {
+        for path in default_files() {
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(profile) = Self::from_file(&path, name)? {
+                return Ok(Some(profile));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 读取指定的配置文件，根据内容自动识别 ini 或 json 格式
+    pub(crate) fn from_file<P: AsRef<Path>>(path: P, name: &str) -> io::Result<Option<Self>> {
+        let text = fs::read_to_string(path)?;
+        if text.trim_start().starts_with('{') {
+            Ok(Self::from_json(&text, name))
+        } else {
+            Ok(Self::from_ini(&text, name))
+        }
+    }
+
+    /// 解析 ini 格式，section 名称可以是 `name` 或者 `profile name`，
+    /// ossutil 的 `[Credentials]` 视为 `default`
+    pub(crate) fn from_ini(text: &str, name: &str) -> Option<Self> {
+        let mut profile: Option<Self> = None;
+        let mut in_section = false;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_section = is_section(section.trim(), name);
+                if in_section && profile.is_none() {
+                    profile = Some(Self::default());
+                }
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            if let (Some(profile), Some((key, value))) = (profile.as_mut(), line.split_once('=')) {
+                profile.set(key, value.trim());
+            }
+        }
+        profile
+    }
+
+    /// 解析 json 格式，支持 aliyun cli 的 `{"profiles": [{"name": ..}, ..]}`，
+    /// 以及只有一组配置的 json 对象
+    pub(crate) fn from_json(text: &str, name: &str) -> Option<Self> {
+        let config: JsonConfig = serde_json::from_str(text).ok()?;
+        let object = if config.profiles.is_empty() {
+            config.profile
+        } else {
+            config
+                .profiles
+                .into_iter()
+                .find(|o| o.name.as_deref() == Some(name))?
+        };
+        Some(object.into())
+    }
+
+    /// 以 `self` 为准，未配置的项使用 `other` 中的值
+    pub(crate) fn or(self, other: Self) -> Self {
+        Self {
+            key: self.key.or(other.key),
+            secret: self.secret.or(other.secret),
+            security_token: self.security_token.or(other.security_token),
+            endpoint: self.endpoint.or(other.endpoint),
+            bucket: self.bucket.or(other.bucket),
+            internal: self.internal.or(other.internal),
+        }
+    }
+
+    /// 配置项名称不区分大小写，并忽略 `_` 和 `-`
+    fn set(&mut self, key: &str, value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        let key: String = key
+            .trim()
+            .chars()
+            .filter(|c| *c != '_' && *c != '-')
+            .collect::<String>()
+            .to_lowercase();
+        let value = Some(value.to_owned());
+        match key.as_str() {
+            "accesskeyid" => self.key = value,
+            "accesskeysecret" => self.secret = value,
+            "ststoken" | "securitytoken" => self.security_token = value,
+            "endpoint" => self.endpoint = value,
+            "regionid" | "region" if self.endpoint.is_none() => self.endpoint = value,
+            "bucket" => self.bucket = value,
+            "internal" => self.internal = value.map(|v| is_true(&v)),
+            _ => (),
+        }
+    }
+}
+
+fn is_section(section: &str, name: &str) -> bool {
+    let section = section.strip_prefix("profile ").unwrap_or(section).trim();
+    section == name || (name == DEFAULT && section == OSSUTIL_DEFAULT)
+}
+
+fn is_true(value: &str) -> bool {
+    value == TRUE1 || value == TRUE2 || value == TRUE3 || value == TRUE4
+}
+
+/// 默认的配置文件路径
+fn default_files() -> Vec<PathBuf> {
+    let home = match env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+        Some(home) => PathBuf::from(home),
+        None => return Vec::new(),
+    };
+    vec![
+        home.join(".ossutilconfig"),
+        home.join(".alibabacloud").join("credentials"),
+        home.join(".aliyun").join("config.json"),
+    ]
+}
+
+/// 将 ossutil 中的 endpoint 转化为可识别的形式，
+/// 如 `https://oss-cn-hangzhou-internal.aliyuncs.com` 转化为 `cn-hangzhou-internal`
+pub(crate) fn normalize_endpoint(endpoint: &str) -> &str {
+    let endpoint = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, host)| host)
+        .trim_end_matches('/');
+    let endpoint = endpoint.strip_suffix(".aliyuncs.com").unwrap_or(endpoint);
+    endpoint.strip_prefix("oss-").unwrap_or(endpoint)
+}
+
+/// json 格式的配置文件，aliyun cli 的配置在 `profiles` 中，否则整个对象就是一组配置
+#[derive(Debug, Deserialize)]
+struct JsonConfig {
+    #[serde(default)]
+    profiles: Vec<JsonProfile>,
+    #[serde(flatten)]
+    profile: JsonProfile,
+}
+
+/// json 中的一组配置，忽略其他字段
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonProfile {
+    name: Option<String>,
+    access_key_id: Option<String>,
+    access_key_secret: Option<String>,
+    sts_token: Option<String>,
+    security_token: Option<String>,
+    endpoint: Option<String>,
+    bucket: Option<String>,
+    region_id: Option<String>,
+    /// 可以是 bool 或字符串
+    internal: Option<Value>,
+}
+
+impl From<JsonProfile> for Profile {
+    fn from(json: JsonProfile) -> Self {
+        let mut profile = Self::default();
+        for (key, value) in [
+            ("access_key_id", json.access_key_id),
+            ("access_key_secret", json.access_key_secret),
+            ("sts_token", json.sts_token),
+            ("security_token", json.security_token),
+            ("endpoint", json.endpoint),
+            ("bucket", json.bucket),
+            ("region_id", json.region_id),
+        ] {
+            if let Some(value) = value {
+                profile.set(key, &value);
+            }
+        }
+        match json.internal {
+            Some(Value::Bool(internal)) => profile.internal = Some(internal),
+            Some(Value::String(internal)) => profile.set("internal", &internal),
+            _ => (),
+        }
+        profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OSSUTIL: &str = r#"
+[Credentials]
+language=CH
+endpoint=https://oss-cn-qingdao.aliyuncs.com
+accessKeyID=key1
+accessKeySecret=secret1
+stsToken=token1
+
+[Bucket-Endpoint]
+bucket1=oss-cn-shanghai.aliyuncs.com
+
+[dev]
+accessKeyID = key2
+accessKeySecret = secret2
+endpoint = oss-cn-shanghai-internal.aliyuncs.com
+bucket = bucket2
+"#;
+
+    const CLI: &str = r#"{
+  "current": "default",
+  "profiles": [
+    {
+      "name": "default",
+      "mode": "AK",
+      "access_key_id": "key3",
+      "access_key_secret": "secret3",
+      "region_id": "cn-beijing",
+      "ram_role_arn": "",
+      "sts_token": ""
+    },
+    {
+      "name": "prod",
+      "mode": "StsToken",
+      "access_key_id": "key4",
+      "access_key_secret": "sec{ret}4",
+      "sts_token": "token4",
+      "region_id": "cn-hangzhou"
+    }
+  ],
+  "meta_path": ""
+}"#;
+
+    #[test]
+    fn from_ini() {
+        let profile = Profile::from_ini(OSSUTIL, "default").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key1"));
+        assert_eq!(profile.secret.as_deref(), Some("secret1"));
+        assert_eq!(profile.security_token.as_deref(), Some("token1"));
+        assert_eq!(
+            profile.endpoint.as_deref(),
+            Some("https://oss-cn-qingdao.aliyuncs.com")
+        );
+        assert!(profile.bucket.is_none());
+
+        let profile = Profile::from_ini(OSSUTIL, "dev").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key2"));
+        assert_eq!(profile.bucket.as_deref(), Some("bucket2"));
+        assert!(profile.security_token.is_none());
+
+        let credentials =
+            "[profile test]\ntype = access_key\naccess_key_id = foo\naccess_key_secret = bar\n";
+        let profile = Profile::from_ini(credentials, "test").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("foo"));
+        assert_eq!(profile.secret.as_deref(), Some("bar"));
+
+        assert!(Profile::from_ini(OSSUTIL, "not-exists").is_none());
+    }
+
+    #[test]
+    fn from_json() {
+        let profile = Profile::from_json(CLI, "default").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key3"));
+        assert_eq!(profile.endpoint.as_deref(), Some("cn-beijing"));
+        assert!(profile.security_token.is_none());
+
+        let profile = Profile::from_json(CLI, "prod").unwrap();
+        assert_eq!(profile.secret.as_deref(), Some("sec{ret}4"));
+        assert_eq!(profile.security_token.as_deref(), Some("token4"));
+
+        assert!(Profile::from_json(CLI, "not-exists").is_none());
+
+        let single = r#"{"access_key_id":"a","access_key_secret":"b"}"#;
+        let profile = Profile::from_json(single, "any").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("a"));
+
+        // 值中含有不成对的括号、引号，或者字段名出现在其他值中
+        let text = r#"{"profiles":[
+            {"name":"a}b","access_key_id":"name","access_key_secret":"x\"}]y"},
+            {"name":"dev","access_key_id":"key5","internal":true}
+        ]}"#;
+        let profile = Profile::from_json(text, "a}b").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("name"));
+        assert_eq!(profile.secret.as_deref(), Some("x\"}]y"));
+        let profile = Profile::from_json(text, "dev").unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key5"));
+        assert_eq!(profile.internal, Some(true));
+
+        assert!(Profile::from_json("{not json", "default").is_none());
+    }
+
+    #[test]
+    fn from_file() {
+        let temp = |ext: &str| {
+            env::temp_dir().join(format!(
+                "aliyun-oss-client-profile-{}.{ext}",
+                std::process::id()
+            ))
+        };
+
+        let path = temp("json");
+        fs::write(&path, CLI).unwrap();
+        let profile = Profile::from_file(&path, "prod").unwrap().unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key4"));
+        fs::remove_file(path).unwrap();
+
+        let path = temp("ini");
+        fs::write(&path, OSSUTIL).unwrap();
+        let profile = Profile::from_file(&path, "dev").unwrap().unwrap();
+        assert_eq!(profile.key.as_deref(), Some("key2"));
+        fs::remove_file(path).unwrap();
+
+        assert!(Profile::from_file(env::temp_dir().join("not-exists"), "dev").is_err());
+    }
+
+    #[test]
+    fn or() {
+        let first = Profile {
+            key: Some("a".to_owned()),
+            internal: Some(true),
+            ..Default::default()
+        };
+        let second = Profile {
+            key: Some("b".to_owned()),
+            secret: Some("c".to_owned()),
+            internal: Some(false),
+            ..Default::default()
+        };
+        let profile = first.or(second);
+        assert_eq!(profile.key.as_deref(), Some("a"));
+        assert_eq!(profile.secret.as_deref(), Some("c"));
+        assert_eq!(profile.internal, Some(true));
+    }
+
+    #[test]
+    fn test_normalize_endpoint() {
+        assert_eq!(
+            normalize_endpoint("https://oss-cn-qingdao.aliyuncs.com"),
+            "cn-qingdao"
+        );
+        assert_eq!(
+            normalize_endpoint("oss-cn-shanghai-internal.aliyuncs.com"),
+            "cn-shanghai-internal"
+        );
+        assert_eq!(normalize_endpoint("qingdao"), "qingdao");
+    }
+}
@@ -0,0 +1,171 @@
+//! # CRC64-ECMA 校验
+//!
+//! 与 OSS 返回的 `x-oss-hash-crc64ecma` 使用相同的算法（即 CRC-64/XZ）
+//!
+//! ```
+//! use aliyun_oss_client::crc64::{combine, Crc64};
+//!
+//! let mut crc = Crc64::new();
+//! crc.update(b"123456789");
+//! assert_eq!(crc.get(), 0x995D_C9BB_DF19_39FA);
+//!
+//! // 分片上传时，可以将各分片的值合并为整个文件的值
+//! let first = Crc64::checksum(b"1234");
+//! let second = Crc64::checksum(b"56789");
+//! assert_eq!(combine(first, second, 5), crc.get());
+//! ```
+
+/// ECMA-182 多项式（反转）
+const POLY: u64 = 0xC96C_5795_D787_0F42;
+
+const TABLE: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// 可分段计算的 CRC64 值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Crc64(u64);
+
+impl Crc64 {
+    /// 初始化
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 计算一段数据的 CRC64 值
+    pub fn checksum(data: &[u8]) -> u64 {
+        let mut crc = Self::new();
+        crc.update(data);
+        crc.get()
+    }
+
+    /// 追加数据
+    pub fn update(&mut self, data: &[u8]) {
+        let mut crc = !self.0;
+        for &byte in data {
+            crc = TABLE[((crc ^ byte as u64) & 0xFF) as usize] ^ (crc >> 8);
+        }
+        self.0 = !crc;
+    }
+
+    /// 当前的 CRC64 值
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+/// # 合并两段数据的 CRC64 值
+///
+/// `crc1`，`crc2` 分别为前后两段数据的值，`len2` 为后一段数据的长度，
+/// 返回两段数据拼接后的值，算法与 zlib 的 `crc32_combine` 相同
+pub fn combine(mut crc1: u64, crc2: u64, mut len2: u64) -> u64 {
+    if len2 == 0 {
+        return crc1;
+    }
+
+    // 奇数次幂的矩阵，对应追加一个 0 bit
+    let mut odd = [0u64; 64];
+    odd[0] = POLY;
+    let mut row = 1u64;
+    for item in odd.iter_mut().skip(1) {
+        *item = row;
+        row <<= 1;
+    }
+
+    let mut even = [0u64; 64];
+    // 追加两个 0 bit
+    gf2_matrix_square(&mut even, &odd);
+    // 追加四个 0 bit
+    gf2_matrix_square(&mut odd, &even);
+
+    // 每次循环追加 len2 的一个 bit 对应数量的 0 字节
+    loop {
+        gf2_matrix_square(&mut even, &odd);
+        if len2 & 1 == 1 {
+            crc1 = gf2_matrix_times(&even, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+
+        gf2_matrix_square(&mut odd, &even);
+        if len2 & 1 == 1 {
+            crc1 = gf2_matrix_times(&odd, crc1);
+        }
+        len2 >>= 1;
+        if len2 == 0 {
+            break;
+        }
+    }
+
+    crc1 ^ crc2
+}
+
+fn gf2_matrix_times(mat: &[u64; 64], mut vec: u64) -> u64 {
+    let mut sum = 0;
+    let mut index = 0;
+    while vec != 0 {
+        if vec & 1 == 1 {
+            sum ^= mat[index];
+        }
+        vec >>= 1;
+        index += 1;
+    }
+    sum
+}
+
+fn gf2_matrix_square(square: &mut [u64; 64], mat: &[u64; 64]) {
+    for (item, &row) in square.iter_mut().zip(mat.iter()) {
+        *item = gf2_matrix_times(mat, row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{combine, Crc64};
+
+    #[test]
+    fn crc64() {
+        let mut crc = Crc64::new();
+        assert_eq!(crc.get(), 0);
+
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.get(), 0x995D_C9BB_DF19_39FA);
+    }
+
+    #[test]
+    fn test_combine() {
+        let data: Vec<u8> = (0..=255u8).cycle().take(3000).collect();
+        let whole = Crc64::checksum(&data);
+
+        for mid in [0, 1, 7, 256, 1024, 2999, 3000] {
+            let (first, second) = data.split_at(mid);
+            assert_eq!(
+                combine(
+                    Crc64::checksum(first),
+                    Crc64::checksum(second),
+                    second.len() as u64
+                ),
+                whole
+            );
+        }
+    }
+}
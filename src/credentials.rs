@@ -0,0 +1,507 @@
+//! # 访问凭证
+//!
+//! 通过 [`CredentialsProvider`] 在每次请求时获取 key，secret 以及 STS token，
+//! 凭证会被缓存，并在过期前自动刷新
+//!
+//! 内置的 provider 有：
+//! - [`StaticProvider`] 固定的 key 和 secret
+//! - [`EnvProvider`] 从环境变量中读取
+//! - [`StsProvider`] 通过自定义的异步方法获取 STS 临时凭证
+//! - [`EcsRamRoleProvider`] 从 ECS 实例元数据服务获取 RAM 角色的临时凭证
+//!
+//! ## 用法
+//! ```
+//! # async fn run() {
+//! use aliyun_oss_client::{
+//!     credentials::{Credentials, StsProvider},
+//!     Client,
+//! };
+//! use chrono::{Duration, Utc};
+//!
+//! let mut client = Client::new(
+//!     "".into(),
+//!     "".into(),
+//!     "qingdao".parse().unwrap(),
+//!     "my-bucket".parse().unwrap(),
+//! );
+//! client.credentials_provider(StsProvider::new(|| async {
+//!     // 在这里请求 STS 服务
+//!     Ok(Credentials::new_with_sts(
+//!         "STS.key".into(),
+//!         "secret".into(),
+//!         "token",
+//!         Some(Utc::now() + Duration::hours(1)),
+//!     ))
+//! }));
+//!
+//! let buckets = client.get_bucket_list().await;
+//! # }
+//! ```
+
+use std::{
+    env::{self, VarError},
+    error::Error,
+    fmt::{self, Display},
+    future::Future,
+    sync::RwLock,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use futures::lock::Mutex;
+use http::{header::InvalidHeaderValue, HeaderValue};
+
+use crate::{auth::AuthBuilder, KeyId, KeySecret};
+
+pub mod ecs;
+
+pub use ecs::EcsRamRoleProvider;
+
+pub(crate) const SECURITY_TOKEN: &str = "x-oss-security-token";
+
+/// 凭证在过期前多久开始刷新
+const REFRESH_ADVANCE: i64 = 5 * 60;
+
+/// 访问 OSS 的凭证
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    key: KeyId,
+    secret: KeySecret,
+    security_token: Option<String>,
+    expiration: Option<DateTime<Utc>>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("key", &self.key)
+            .field("secret", &self.secret)
+            .field(
+                "security_token",
+                &self.security_token.as_ref().map(|_| "******"),
+            )
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// 使用固定的 key 和 secret 初始化，永不过期
+    pub fn new(key: KeyId, secret: KeySecret) -> Self {
+        Self {
+            key,
+            secret,
+            security_token: None,
+            expiration: None,
+        }
+    }
+
+    /// 使用 STS 临时凭证初始化
+    pub fn new_with_sts<T: Into<String>>(
+        key: KeyId,
+        secret: KeySecret,
+        security_token: T,
+        expiration: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            key,
+            secret,
+            security_token: Some(security_token.into()),
+            expiration,
+        }
+    }
+
+    /// 返回 key
+    pub fn key(&self) -> &KeyId {
+        &self.key
+    }
+
+    /// 返回 secret
+    pub fn secret(&self) -> &KeySecret {
+        &self.secret
+    }
+
+    /// 返回 STS token
+    pub fn security_token(&self) -> Option<&str> {
+        self.security_token.as_deref()
+    }
+
+    /// 返回过期时间，`None` 表示永不过期
+    pub fn expiration(&self) -> Option<&DateTime<Utc>> {
+        self.expiration.as_ref()
+    }
+
+    /// 是否需要刷新
+    fn need_refresh(&self, now: DateTime<Utc>) -> bool {
+        match self.expiration {
+            Some(exp) => now + Duration::seconds(REFRESH_ADVANCE) >= exp,
+            None => false,
+        }
+    }
+
+    /// 将凭证写入签名构建器
+    pub(crate) fn apply(&self, auth: &mut AuthBuilder) -> Result<(), CredentialsError> {
+        auth.key(self.key.clone());
+        auth.secret(self.secret.clone());
+        match &self.security_token {
+            Some(token) => {
+                let mut value = HeaderValue::from_str(token)?;
+                value.set_sensitive(true);
+                auth.header_insert(SECURITY_TOKEN, value);
+            }
+            None => auth.header_remove(SECURITY_TOKEN),
+        }
+        Ok(())
+    }
+}
+
+/// 凭证的提供者
+#[async_trait]
+pub trait CredentialsProvider: Send + Sync + 'static {
+    /// 获取凭证，返回的凭证会被缓存，直到临近过期时间
+    async fn credentials(&self) -> Result<Credentials, CredentialsError>;
+}
+
+/// 固定的 key 和 secret
+#[derive(Debug, Clone)]
+pub struct StaticProvider {
+    credentials: Credentials,
+}
+
+impl StaticProvider {
+    /// 初始化
+    pub fn new(key: KeyId, secret: KeySecret) -> Self {
+        Self {
+            credentials: Credentials::new(key, secret),
+        }
+    }
+}
+
+impl From<Credentials> for StaticProvider {
+    fn from(credentials: Credentials) -> Self {
+        Self { credentials }
+    }
+}
+
+#[async_trait]
+impl CredentialsProvider for StaticProvider {
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        Ok(self.credentials.clone())
+    }
+}
+
+/// 从环境变量中读取凭证
+///
+/// - `ALIYUN_KEY_ID`
+/// - `ALIYUN_KEY_SECRET`
+/// - `ALIYUN_STS_TOKEN` 可选
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct EnvProvider;
+
+impl EnvProvider {
+    /// 初始化
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl CredentialsProvider for EnvProvider {
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        let key = env::var("ALIYUN_KEY_ID")?;
+        let secret = env::var("ALIYUN_KEY_SECRET")?;
+        let credentials = match env::var("ALIYUN_STS_TOKEN") {
+            Ok(token) => Credentials::new_with_sts(key.into(), secret.into(), token, None),
+            Err(_) => Credentials::new(key.into(), secret.into()),
+        };
+        Ok(credentials)
+    }
+}
+
+/// 通过自定义的异步方法获取 STS 临时凭证
+///
+/// 返回的凭证需要带有过期时间，才会在过期前重新获取
+pub struct StsProvider<F> {
+    fetch: F,
+}
+
+impl<F> fmt::Debug for StsProvider<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StsProvider").finish_non_exhaustive()
+    }
+}
+
+impl<F, Fut> StsProvider<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Credentials, CredentialsError>> + Send,
+{
+    /// 使用获取凭证的方法初始化
+    pub fn new(fetch: F) -> Self {
+        Self { fetch }
+    }
+}
+
+#[async_trait]
+impl<F, Fut> CredentialsProvider for StsProvider<F>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Credentials, CredentialsError>> + Send,
+{
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        (self.fetch)().await
+    }
+}
+
+/// 缓存 provider 返回的凭证，临近过期时重新获取
+pub(crate) struct CredentialsCache {
+    provider: Box<dyn CredentialsProvider>,
+    cached: RwLock<Option<Credentials>>,
+    refresh: Mutex<()>,
+}
+
+impl fmt::Debug for CredentialsCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialsCache")
+            .field("cached", &self.cached)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialsCache {
+    pub(crate) fn new<P: CredentialsProvider>(provider: P) -> Self {
+        Self {
+            provider: Box::new(provider),
+            cached: RwLock::new(None),
+            refresh: Mutex::new(()),
+        }
+    }
+
+    /// 返回可用的凭证，如果没有缓存或临近过期，则从 provider 重新获取
+    pub(crate) async fn get(&self) -> Result<Credentials, CredentialsError> {
+        if let Some(cred) = self.fresh(Utc::now()) {
+            return Ok(cred);
+        }
+
+        // 同一时间只有一个请求去刷新，其他请求等待后直接使用新的凭证
+        let _guard = self.refresh.lock().await;
+        if let Some(cred) = self.fresh(Utc::now()) {
+            return Ok(cred);
+        }
+
+        let cred = self.provider.credentials().await?;
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some(cred.clone());
+        }
+        Ok(cred)
+    }
+
+    /// 直接写入已获取的凭证
+    #[cfg(feature = "sts")]
+    pub(crate) fn set(&self, cred: Credentials) {
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some(cred);
+        }
+    }
+
+    fn fresh(&self, now: DateTime<Utc>) -> Option<Credentials> {
+        let cached = self.cached.read().ok()?;
+        cached.as_ref().filter(|c| !c.need_refresh(now)).cloned()
+    }
+}
+
+/// 获取凭证时的错误
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CredentialsError {
+    kind: CredentialsErrorKind,
+}
+
+impl CredentialsError {
+    /// 自定义 provider 时，用于包装获取凭证时的错误
+    pub fn new<E: Into<Box<dyn Error + Send + Sync>>>(err: E) -> Self {
+        Self {
+            kind: CredentialsErrorKind::Provider(err.into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum CredentialsErrorKind {
+    Env(VarError),
+    Token(InvalidHeaderValue),
+    Reqwest(reqwest::Error),
+    Provider(Box<dyn Error + Send + Sync>),
+}
+
+impl Display for CredentialsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CredentialsErrorKind::*;
+        match &self.kind {
+            Env(_) => "get credentials from env failed".fmt(f),
+            Token(_) => "invalid security token".fmt(f),
+            Reqwest(_) => "request credentials failed".fmt(f),
+            Provider(_) => "get credentials failed".fmt(f),
+        }
+    }
+}
+
+impl Error for CredentialsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use CredentialsErrorKind::*;
+        match &self.kind {
+            Env(e) => Some(e),
+            Token(e) => Some(e),
+            Reqwest(e) => Some(e),
+            Provider(e) => Some(e.as_ref()),
+        }
+    }
+}
+
+impl From<VarError> for CredentialsError {
+    fn from(value: VarError) -> Self {
+        Self {
+            kind: CredentialsErrorKind::Env(value),
+        }
+    }
+}
+
+impl From<InvalidHeaderValue> for CredentialsError {
+    fn from(value: InvalidHeaderValue) -> Self {
+        Self {
+            kind: CredentialsErrorKind::Token(value),
+        }
+    }
+}
+
+impl From<reqwest::Error> for CredentialsError {
+    fn from(value: reqwest::Error) -> Self {
+        Self {
+            kind: CredentialsErrorKind::Reqwest(value),
+        }
+    }
+}
+
+/// 模拟 HTTP 服务，`handle` 接收原始请求文本，返回状态和响应内容，最终返回服务地址
+#[cfg(test)]
+pub(crate) fn mock_server<F>(handle: F) -> String
+where
+    F: Fn(&str) -> (&'static str, String) + Send + 'static,
+{
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut buf = [0; 4096];
+            let len = stream.read(&mut buf).unwrap();
+            let (status, body) = handle(&String::from_utf8_lossy(&buf[..len]));
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    format!("http://{addr}/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    #[test]
+    fn need_refresh() {
+        let now = Utc::now();
+        let cred = Credentials::new("a".into(), "b".into());
+        assert!(!cred.need_refresh(now));
+
+        let cred = Credentials::new_with_sts(
+            "a".into(),
+            "b".into(),
+            "c",
+            Some(now + Duration::seconds(100)),
+        );
+        assert!(cred.need_refresh(now));
+
+        let cred =
+            Credentials::new_with_sts("a".into(), "b".into(), "c", Some(now + Duration::hours(1)));
+        assert!(!cred.need_refresh(now));
+    }
+
+    #[test]
+    fn apply() {
+        let mut auth = AuthBuilder::default();
+        Credentials::new_with_sts("a".into(), "b".into(), "token", None)
+            .apply(&mut auth)
+            .unwrap();
+        assert_eq!(auth.get_key().as_ref(), "a");
+        assert_eq!(auth.get_secret().as_str(), "b");
+
+        Credentials::new("c".into(), "d".into())
+            .apply(&mut auth)
+            .unwrap();
+        let headers = auth.get_headers().unwrap();
+        assert!(headers.get(SECURITY_TOKEN).is_none());
+    }
+
+    #[tokio::test]
+    async fn cache_refresh() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let count2 = count.clone();
+        let cache = CredentialsCache::new(StsProvider::new(move || {
+            let count = count2.clone();
+            async move {
+                let n = count.fetch_add(1, Ordering::SeqCst);
+                // 第一次返回即将过期的凭证
+                let exp = if n == 0 {
+                    Utc::now() + Duration::seconds(10)
+                } else {
+                    Utc::now() + Duration::hours(1)
+                };
+                Ok(Credentials::new_with_sts(
+                    format!("key{n}").into(),
+                    "secret".into(),
+                    "token",
+                    Some(exp),
+                ))
+            }
+        }));
+
+        assert_eq!(cache.get().await.unwrap().key().as_ref(), "key0");
+        assert_eq!(cache.get().await.unwrap().key().as_ref(), "key1");
+        assert_eq!(cache.get().await.unwrap().key().as_ref(), "key1");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn provider_error() {
+        let cache = CredentialsCache::new(StsProvider::new(|| async {
+            Err(CredentialsError::new("sts failed"))
+        }));
+        let err = cache.get().await.unwrap_err();
+        assert_eq!(err.to_string(), "get credentials failed");
+        assert_eq!(err.source().unwrap().to_string(), "sts failed");
+    }
+
+    #[tokio::test]
+    async fn static_provider() {
+        let provider = StaticProvider::new("a".into(), "b".into());
+        let cred = provider.credentials().await.unwrap();
+        assert_eq!(cred.key().as_ref(), "a");
+        assert!(cred.security_token().is_none());
+    }
+}
@@ -0,0 +1,250 @@
+//! # ECS 实例 RAM 角色
+//!
+//! 通过 ECS 实例元数据服务获取绑定的 RAM 角色的临时凭证，默认使用加固模式（IMDSv2）
+//!
+//! [aliyun docs](https://help.aliyun.com/zh/ecs/user-guide/attach-an-instance-ram-role-to-an-ecs-instance)
+//!
+//! ## 用法
+//! ```
+//! # async fn run() {
+//! use aliyun_oss_client::{credentials::EcsRamRoleProvider, Client};
+//!
+//! let mut client = Client::new(
+//!     "".into(),
+//!     "".into(),
+//!     "qingdao".parse().unwrap(),
+//!     "my-bucket".parse().unwrap(),
+//! );
+//! let mut provider = EcsRamRoleProvider::new();
+//! provider.role("my-role");
+//! client.credentials_provider(provider);
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+
+use super::{Credentials, CredentialsError, CredentialsProvider};
+
+const METADATA_URL: &str = "http://100.100.100.200";
+const TOKEN_PATH: &str = "/latest/api/token";
+const CREDENTIALS_PATH: &str = "/latest/meta-data/ram/security-credentials/";
+const TOKEN_HEADER: &str = "X-aliyun-ecs-metadata-token";
+const TOKEN_TTL_HEADER: &str = "X-aliyun-ecs-metadata-token-ttl-seconds";
+const TOKEN_TTL: u32 = 21600;
+
+/// 不在 ECS 实例中时元数据服务无法连接，需要尽快失败
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 从 ECS 实例元数据服务获取 RAM 角色的临时凭证
+#[derive(Debug, Clone)]
+pub struct EcsRamRoleProvider {
+    client: Client,
+    metadata_url: String,
+    role: Option<String>,
+}
+
+impl Default for EcsRamRoleProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EcsRamRoleProvider {
+    /// 初始化，未设置角色名称时，会从元数据服务中获取
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            metadata_url: METADATA_URL.to_owned(),
+            role: None,
+        }
+    }
+
+    /// 设置 RAM 角色名称
+    pub fn role<R: Into<String>>(&mut self, role: R) -> &mut Self {
+        self.role = Some(role.into());
+        self
+    }
+
+    /// 设置元数据服务的地址，默认为 `http://100.100.100.200`
+    pub fn metadata_url<U: Into<String>>(&mut self, url: U) -> &mut Self {
+        self.metadata_url = url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    fn url(&self, path: &str) -> Result<Url, CredentialsError> {
+        format!("{}{}", self.metadata_url, path)
+            .parse()
+            .map_err(CredentialsError::new)
+    }
+
+    /// 获取加固模式的 token，获取失败时退回到普通模式
+    async fn token(&self) -> Option<String> {
+        let response = self
+            .client
+            .put(self.url(TOKEN_PATH).ok()?)
+            .header(TOKEN_TTL_HEADER, TOKEN_TTL)
+            .send()
+            .await
+            .ok()?
+            .error_for_status()
+            .ok()?;
+        response.text().await.ok()
+    }
+
+    async fn get(&self, path: &str, token: Option<&str>) -> Result<String, CredentialsError> {
+        let mut builder = self.client.get(self.url(path)?);
+        if let Some(token) = token {
+            builder = builder.header(TOKEN_HEADER, token);
+        }
+        let text = builder.send().await?.error_for_status()?.text().await?;
+        Ok(text)
+    }
+}
+
+#[async_trait]
+impl CredentialsProvider for EcsRamRoleProvider {
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        let token = self.token().await;
+        let role = match &self.role {
+            Some(role) => role.to_owned(),
+            None => self
+                .get(CREDENTIALS_PATH, token.as_deref())
+                .await?
+                .trim()
+                .to_owned(),
+        };
+        if role.is_empty() {
+            return Err(CredentialsError::new("not found ram role of ecs instance"));
+        }
+
+        let text = self
+            .get(&format!("{CREDENTIALS_PATH}{role}"), token.as_deref())
+            .await?;
+        parse(&text)
+    }
+}
+
+/// 元数据服务返回的凭证，失败时只有 `Code` 字段
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct EcsCredentials {
+    code: String,
+    access_key_id: Option<String>,
+    access_key_secret: Option<String>,
+    security_token: Option<String>,
+    expiration: Option<String>,
+}
+
+/// 解析元数据服务返回的凭证
+fn parse(text: &str) -> Result<Credentials, CredentialsError> {
+    let body: EcsCredentials = serde_json::from_str(text).map_err(CredentialsError::new)?;
+    if body.code != "Success" {
+        return Err(CredentialsError::new(format!(
+            "get ecs ram role credentials failed, code: {}",
+            body.code
+        )));
+    }
+
+    let field = |value: Option<String>, name: &str| {
+        value.ok_or_else(|| CredentialsError::new(format!("not found {name} in ecs metadata")))
+    };
+    let expiration = DateTime::parse_from_rfc3339(&field(body.expiration, "Expiration")?)
+        .map_err(CredentialsError::new)?
+        .with_timezone(&Utc);
+
+    Ok(Credentials::new_with_sts(
+        field(body.access_key_id, "AccessKeyId")?.into(),
+        field(body.access_key_secret, "AccessKeySecret")?.into(),
+        field(body.security_token, "SecurityToken")?,
+        Some(expiration),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::credentials::mock_server;
+
+    const BODY: &str = r#"{
+  "AccessKeyId" : "STS.key",
+  "AccessKeySecret" : "secret",
+  "Expiration" : "2023-12-03T13:00:00Z",
+  "SecurityToken" : "token",
+  "LastUpdated" : "2023-12-03T07:00:00Z",
+  "Code" : "Success"
+}"#;
+
+    /// 模拟元数据服务，返回服务地址
+    fn server() -> String {
+        mock_server(|request| {
+            let request = request.to_lowercase();
+            let (status, body) = if request.starts_with("put /latest/api/token") {
+                ("200 OK", "imds-token")
+            } else if !request.contains("x-aliyun-ecs-metadata-token: imds-token") {
+                ("401 Unauthorized", "")
+            } else if request.starts_with("get /latest/meta-data/ram/security-credentials/ ") {
+                ("200 OK", "my-role")
+            } else if request.starts_with("get /latest/meta-data/ram/security-credentials/my-role ")
+            {
+                ("200 OK", BODY)
+            } else {
+                ("404 Not Found", "")
+            };
+            (status, body.to_owned())
+        })
+    }
+
+    #[test]
+    fn test_parse() {
+        let cred = parse(BODY).unwrap();
+        assert_eq!(cred.key().as_ref(), "STS.key");
+        assert_eq!(cred.secret(), &"secret".into());
+        assert_eq!(cred.security_token(), Some("token"));
+        assert_eq!(
+            cred.expiration(),
+            Some(&Utc.with_ymd_and_hms(2023, 12, 3, 13, 0, 0).unwrap())
+        );
+
+        let err = parse(r#"{"Code":"Failed"}"#).unwrap_err();
+        assert!(std::error::Error::source(&err)
+            .unwrap()
+            .to_string()
+            .contains("Failed"));
+
+        // 值中含有转义字符、key 出现在其他值中时都能正确解析
+        let cred = parse(
+            r#"{"Code":"Success","AccessKeyId":"STS.\"key\"","AccessKeySecret":"AccessKeyId","SecurityToken":"token","Expiration":"2023-12-03T13:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(cred.key().as_ref(), "STS.\"key\"");
+        assert_eq!(cred.secret(), &"AccessKeyId".into());
+
+        assert!(parse(r#"{"Code":"Success","AccessKeyId":"STS.key"}"#).is_err());
+        assert!(parse("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn fetch_credentials() {
+        let mut provider = EcsRamRoleProvider::new();
+        provider.metadata_url(server());
+        let cred = provider.credentials().await.unwrap();
+        assert_eq!(cred.key().as_ref(), "STS.key");
+        assert_eq!(cred.security_token(), Some("token"));
+
+        provider.role("other-role");
+        assert!(provider.credentials().await.is_err());
+    }
+}
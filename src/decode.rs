@@ -137,6 +137,25 @@ const ID: &[u8] = b"ID";
 const DISPLAY_NAME: &[u8] = b"DisplayName";
 const CONTENTS: &[u8] = b"Contents";
 
+const UPLOAD: &[u8] = b"Upload";
+const UPLOAD_ID: &[u8] = b"UploadId";
+const INITIATED: &[u8] = b"Initiated";
+const KEY_MARKER: &[u8] = b"KeyMarker";
+const UPLOAD_ID_MARKER: &[u8] = b"UploadIdMarker";
+const NEXT_KEY_MARKER: &[u8] = b"NextKeyMarker";
+const NEXT_UPLOAD_ID_MARKER: &[u8] = b"NextUploadIdMarker";
+const MAX_UPLOADS: &[u8] = b"MaxUploads";
+const PART: &[u8] = b"Part";
+const PART_NUMBER: &[u8] = b"PartNumber";
+const PART_NUMBER_MARKER: &[u8] = b"PartNumberMarker";
+const NEXT_PART_NUMBER_MARKER: &[u8] = b"NextPartNumberMarker";
+const MAX_PARTS: &[u8] = b"MaxParts";
+const TAG: &[u8] = b"Tag";
+const ERROR: &[u8] = b"Error";
+const CODE: &[u8] = b"Code";
+const VERSION_ID: &[u8] = b"VersionId";
+const VALUE: &[u8] = b"Value";
+
 const TRUE: &str = "true";
 
 /// 将一个 object 的数据写入到 rust 类型
@@ -315,7 +334,7 @@ where
                         CONTENTS => {
                             // <Contents></Contents> 标签内部的数据对应单个 object 信息
                             let mut object =
-                                init_object(self).ok_or(InnerListError::init_error(true))?;
+                                init_object(self).ok_or(InnerListError::init_error("object"))?;
                             object.decode(&reader.read_text(e.to_end().name())?)?;
                             result.push(object);
                         }
@@ -481,7 +500,7 @@ where
                     BUCKET => {
                         // <Bucket></Bucket> 标签内部的数据对应单个 bucket 信息
                         let mut bucket =
-                            init_bucket(self).ok_or(InnerListError::init_error(false))?;
+                            init_bucket(self).ok_or(InnerListError::init_error("bucket"))?;
                         bucket.decode(&reader.read_text(e.to_end().name())?)?;
                         result.push(bucket);
                     }
@@ -502,6 +521,439 @@ where
     }
 }
 
+/// 将一个分片上传任务的数据写入到 rust 类型
+pub trait RefineUpload<Error: StdError + 'static> {
+    /// 提取 key
+    fn set_key(&mut self, _key: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 upload_id
+    fn set_upload_id(&mut self, _upload_id: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取任务创建时间
+    fn set_initiated(&mut self, _initiated: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 storage_class
+    fn set_storage_class(&mut self, _storage_class: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 对单个 upload 部分的 xml 内容进行解析
+    fn decode(&mut self, xml: &str) -> Result<(), InnerItemError> {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::with_capacity(xml.len());
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    KEY => self.set_key(&reader.read_text(e.to_end().name())?)?,
+                    UPLOAD_ID => self.set_upload_id(&reader.read_text(e.to_end().name())?)?,
+                    INITIATED => self.set_initiated(&reader.read_text(e.to_end().name())?)?,
+                    STORAGE_CLASS => {
+                        self.set_storage_class(&reader.read_text(e.to_end().name())?)?
+                    }
+                    _ => (),
+                },
+                Ok(Event::Eof) => {
+                    break;
+                } // exits the loop when reaching end of file
+                Err(e) => {
+                    return Err(InnerItemError::from(e));
+                }
+                _ => (), //
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// 将分片上传任务列表写入到 rust 类型
+pub trait RefineUploadList<T, Error, ItemErr = Error>
+where
+    T: RefineUpload<ItemErr>,
+    Error: ListError,
+    ItemErr: StdError + 'static,
+{
+    /// 提取 bucket 名
+    fn set_bucket(&mut self, _bucket: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取前缀
+    fn set_prefix(&mut self, _prefix: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 key_marker
+    fn set_key_marker(&mut self, _key_marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 upload_id_marker
+    fn set_upload_id_marker(&mut self, _upload_id_marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取翻页信息 next_key_marker
+    fn set_next_key_marker(&mut self, _next_key_marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取翻页信息 next_upload_id_marker
+    fn set_next_upload_id_marker(&mut self, _next_upload_id_marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 max_uploads
+    fn set_max_uploads(&mut self, _max_uploads: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 is_truncated
+    fn set_is_truncated(&mut self, _is_truncated: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取分片上传任务列表
+    fn set_list(&mut self, _list: Vec<T>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// # 由 xml 转 struct 的底层实现
+    /// - `init_upload` 用于初始化 upload 结构体的方法
+    fn decode<F>(&mut self, xml: &str, init_upload: F) -> Result<(), InnerListError>
+    where
+        F: for<'a> Fn(&'a mut Self) -> Option<T>,
+    {
+        let mut result = Vec::new();
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut buf = Vec::with_capacity(xml.len());
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    BUCKET => self.set_bucket(&reader.read_text(e.to_end().name())?)?,
+                    PREFIX => self.set_prefix(&reader.read_text(e.to_end().name())?)?,
+                    KEY_MARKER => self.set_key_marker(&reader.read_text(e.to_end().name())?)?,
+                    UPLOAD_ID_MARKER => {
+                        self.set_upload_id_marker(&reader.read_text(e.to_end().name())?)?
+                    }
+                    NEXT_KEY_MARKER => {
+                        self.set_next_key_marker(&reader.read_text(e.to_end().name())?)?
+                    }
+                    NEXT_UPLOAD_ID_MARKER => {
+                        self.set_next_upload_id_marker(&reader.read_text(e.to_end().name())?)?
+                    }
+                    MAX_UPLOADS => self.set_max_uploads(&reader.read_text(e.to_end().name())?)?,
+                    IS_TRUNCATED => {
+                        self.set_is_truncated(reader.read_text(e.to_end().name())? == TRUE)?;
+                    }
+                    UPLOAD => {
+                        // <Upload></Upload> 标签内部的数据对应单个分片上传任务
+                        let mut upload =
+                            init_upload(self).ok_or(InnerListError::init_error("upload"))?;
+                        upload.decode(&reader.read_text(e.to_end().name())?)?;
+                        result.push(upload);
+                    }
+                    _ => (),
+                },
+                Ok(Event::Eof) => {
+                    self.set_list(result)?;
+                    break;
+                } // exits the loop when reaching end of file
+                Err(e) => {
+                    return Err(InnerListError::from(e));
+                }
+                _ => (), // There are several other `Event`s we do not consider here
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// 将一个已上传分片的数据写入到 rust 类型
+pub trait RefinePart<Error: StdError + 'static> {
+    /// 提取分片编号
+    fn set_part_number(&mut self, _part_number: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取最后修改时间
+    fn set_last_modified(&mut self, _last_modified: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 etag
+    fn set_etag(&mut self, _etag: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 size
+    fn set_size(&mut self, _size: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 对单个 part 部分的 xml 内容进行解析
+    fn decode(&mut self, xml: &str) -> Result<(), InnerItemError> {
+        let mut reader = Reader::from_str(xml);
+        let mut buf = Vec::with_capacity(xml.len());
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    PART_NUMBER => self.set_part_number(&reader.read_text(e.to_end().name())?)?,
+                    LAST_MODIFIED => {
+                        self.set_last_modified(&reader.read_text(e.to_end().name())?)?
+                    }
+                    E_TAG => {
+                        let tag = reader.read_text(e.to_end().name())?;
+                        self.set_etag(tag.trim_matches('"'))?;
+                    }
+                    SIZE => self.set_size(&reader.read_text(e.to_end().name())?)?,
+                    _ => (),
+                },
+                Ok(Event::Eof) => {
+                    break;
+                } // exits the loop when reaching end of file
+                Err(e) => {
+                    return Err(InnerItemError::from(e));
+                }
+                _ => (), //
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// 将已上传分片的列表写入到 rust 类型
+pub trait RefinePartList<T, Error, ItemErr = Error>
+where
+    T: RefinePart<ItemErr>,
+    Error: ListError,
+    ItemErr: StdError + 'static,
+{
+    /// 提取 bucket 名
+    fn set_bucket(&mut self, _bucket: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取文件路径
+    fn set_key(&mut self, _key: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 upload_id
+    fn set_upload_id(&mut self, _upload_id: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 part_number_marker
+    fn set_part_number_marker(&mut self, _marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取翻页信息 next_part_number_marker
+    fn set_next_part_number_marker(&mut self, _marker: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 max_parts
+    fn set_max_parts(&mut self, _max_parts: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取 is_truncated
+    fn set_is_truncated(&mut self, _is_truncated: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 提取分片列表
+    fn set_list(&mut self, _list: Vec<T>) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// # 由 xml 转 struct 的底层实现
+    /// - `init_part` 用于初始化 part 结构体的方法
+    fn decode<F>(&mut self, xml: &str, init_part: F) -> Result<(), InnerListError>
+    where
+        F: for<'a> Fn(&'a mut Self) -> Option<T>,
+    {
+        let mut result = Vec::new();
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut buf = Vec::with_capacity(xml.len());
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => match e.name().as_ref() {
+                    BUCKET => self.set_bucket(&reader.read_text(e.to_end().name())?)?,
+                    KEY => self.set_key(&reader.read_text(e.to_end().name())?)?,
+                    UPLOAD_ID => self.set_upload_id(&reader.read_text(e.to_end().name())?)?,
+                    PART_NUMBER_MARKER => {
+                        self.set_part_number_marker(&reader.read_text(e.to_end().name())?)?
+                    }
+                    NEXT_PART_NUMBER_MARKER => {
+                        self.set_next_part_number_marker(&reader.read_text(e.to_end().name())?)?
+                    }
+                    MAX_PARTS => self.set_max_parts(&reader.read_text(e.to_end().name())?)?,
+                    IS_TRUNCATED => {
+                        self.set_is_truncated(reader.read_text(e.to_end().name())? == TRUE)?;
+                    }
+                    PART => {
+                        // <Part></Part> 标签内部的数据对应单个分片
+                        let mut part = init_part(self).ok_or(InnerListError::init_error("part"))?;
+                        part.decode(&reader.read_text(e.to_end().name())?)?;
+                        result.push(part);
+                    }
+                    _ => (),
+                },
+                Ok(Event::Eof) => {
+                    self.set_list(result)?;
+                    break;
+                } // exits the loop when reaching end of file
+                Err(e) => {
+                    return Err(InnerListError::from(e));
+                }
+                _ => (), // There are several other `Event`s we do not consider here
+            }
+            buf.clear();
+        }
+        Ok(())
+    }
+}
+
+/// 将对象的标签集合写入到 rust 类型
+pub trait RefineTagging<Error: StdError + 'static> {
+    /// 提取单个标签
+    fn set_tag(&mut self, _key: &str, _value: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 对 `<Tagging><TagSet>` 的 xml 内容进行解析，标签值为空时 OSS 可能返回 `<Value />`
+    fn decode(&mut self, xml: &str) -> Result<(), InnerItemError> {
+        let mut reader = Reader::from_str(xml);
+        let (mut key, mut value) = (None, String::new());
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => match e.name().as_ref() {
+                    TAG => {
+                        key = None;
+                        value.clear();
+                    }
+                    KEY => key = Some(read_unescaped(&mut reader, KEY)?),
+                    VALUE => value = read_unescaped(&mut reader, VALUE)?,
+                    _ => (),
+                },
+                Event::End(e) if e.name().as_ref() == TAG => {
+                    let key = key.take().ok_or(MissingTag("Key"))?;
+                    self.set_tag(&key, &value)?;
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 将批量删除文件的结果写入到 rust 类型
+pub trait RefineDeleteResult<Error: StdError + 'static> {
+    /// 提取删除失败的文件及 OSS 返回的错误码
+    fn set_error(
+        &mut self,
+        _key: &str,
+        _version_id: Option<&str>,
+        _code: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// 对 DeleteResult 的 xml 内容进行解析，只提取 `<Error>` 中的数据
+    fn decode(&mut self, xml: &str) -> Result<(), InnerItemError> {
+        let mut reader = Reader::from_str(xml);
+        let mut in_error = false;
+        let (mut key, mut version_id, mut code) = (None, None, String::new());
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => match e.name().as_ref() {
+                    ERROR => {
+                        in_error = true;
+                        key = None;
+                        version_id = None;
+                        code.clear();
+                    }
+                    KEY if in_error => key = Some(read_unescaped(&mut reader, KEY)?),
+                    VERSION_ID if in_error => {
+                        version_id = Some(read_unescaped(&mut reader, VERSION_ID)?)
+                    }
+                    CODE if in_error => code = read_unescaped(&mut reader, CODE)?,
+                    _ => (),
+                },
+                Event::End(e) if e.name().as_ref() == ERROR => {
+                    in_error = false;
+                    let key = key.take().ok_or(MissingTag("Key"))?;
+                    self.set_error(&key, version_id.take().as_deref(), &code)?;
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 读取 xml 中首个 `name` 标签的文本，文本会被反转义，未找到该标签时返回 None
+#[cfg(feature = "core")]
+pub(crate) fn decode_text(xml: &str, name: &[u8]) -> Result<Option<String>, InnerItemError> {
+    let mut reader = Reader::from_str(xml);
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if e.name().as_ref() == name => {
+                return read_unescaped(&mut reader, name).map(Some);
+            }
+            Event::Empty(e) if e.name().as_ref() == name => return Ok(Some(String::new())),
+            Event::Eof => return Ok(None),
+            _ => (),
+        }
+    }
+}
+
+/// 读取到 `end` 结束标签为止的文本，文本会被反转义，CDATA 中的内容原样保留
+fn read_unescaped(reader: &mut Reader<&[u8]>, end: &[u8]) -> Result<String, InnerItemError> {
+    let mut text = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Text(e) => text.push_str(&e.unescape()?),
+            Event::CData(e) => text.push_str(std::str::from_utf8(&e)?),
+            Event::End(e) if e.name().as_ref() == end => return Ok(text),
+            Event::Eof => {
+                let name = String::from_utf8_lossy(end).into_owned();
+                return Err(quick_xml::Error::UnexpectedEof(name).into());
+            }
+            _ => (),
+        }
+    }
+}
+
+/// xml 中缺少必需的标签
+#[derive(Debug)]
+struct MissingTag(&'static str);
+
+impl Display for MissingTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not found tag: {}", self.0)
+    }
+}
+
+impl StdError for MissingTag {}
+
 /// # Object 的 Error 中间层
 /// 当外部实现 [`RefineObject`] 时，所使用的 Error ,可先转换为这个，
 /// 变成一个已知的 Error 类型
@@ -588,13 +1040,7 @@ impl Display for InnerListError {
             Item(item) => write!(fmt, "{}", item.0),
             Xml(xml) => write!(fmt, "{xml}"),
             Custom(out) => write!(fmt, "{out}"),
-            InitItemFailed(is_object) => {
-                if *is_object {
-                    write!(fmt, "init_object failed")
-                } else {
-                    write!(fmt, "init_bucket failed")
-                }
-            }
+            InitItemFailed(item) => write!(fmt, "init_{item} failed"),
         }
     }
 }
@@ -614,9 +1060,9 @@ impl InnerListError {
     //     }
     // }
 
-    fn init_error(is_object: bool) -> Self {
+    fn init_error(item: &'static str) -> Self {
         Self {
-            kind: ListErrorKind::InitItemFailed(is_object),
+            kind: ListErrorKind::InitItemFailed(item),
         }
     }
 
@@ -677,5 +1123,5 @@ enum ListErrorKind {
     #[non_exhaustive]
     Custom(Box<dyn StdError + 'static>),
 
-    InitItemFailed(bool),
+    InitItemFailed(&'static str),
 }
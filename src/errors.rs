@@ -276,6 +276,11 @@ impl<'a> OssService {
         }
     }
 
+    /// 返回 OSS 的错误码，如 `NoSuchKey`
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
     /// 返回报错接口的 url
     pub fn url(&self) -> &Url {
         &self.url
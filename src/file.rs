@@ -75,18 +75,34 @@
 //! [`File`]: crate::file::File
 
 use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{
+    io::{AsyncRead, AsyncReadExt},
+    Stream, TryStreamExt,
+};
 use http::{
-    header::{HeaderName, CONTENT_LENGTH, CONTENT_TYPE},
-    HeaderValue, Method,
+    header::{HeaderName, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE},
+    HeaderMap, HeaderValue, Method, StatusCode,
 };
 use reqwest::{Response, Url};
 
 use crate::{
     bucket::Bucket,
-    builder::{ArcPointer, BuilderError, RequestBuilder},
-    object::{Object, ObjectList},
+    builder::{ArcPointer, BuilderError, BuilderErrorKind, RequestBuilder},
+    config::BucketBase,
+    crc64::Crc64,
+    decode::decode_text,
+    object::{
+        content::{complete_xml, parse_upload_id, Inner},
+        delete::{
+            content_md5, delete_body, parse_delete_result, DeleteResult, ObjectIdentifier,
+            MAX_DELETE_KEYS,
+        },
+        AppendObjectResult, CopyObject, CopyObjectResult, Object, ObjectList, ObjectMeta,
+        ObjectStream, PutObject, Tagging,
+    },
     types::object::{ObjectBase, ObjectPath},
-    types::{CanonicalizedResource, ContentRange},
+    types::{CanonicalizedResource, ContentMd5, ContentRange},
 };
 #[cfg(feature = "put_file")]
 use infer::Infer;
@@ -96,6 +112,11 @@ mod test;
 
 const ETAG: &str = "ETag";
 const RANGE: &str = "Range";
+const OBJECT_META: &str = "objectMeta";
+const DELETE: &str = "delete";
+const TAGGING: &str = "tagging";
+const HASH_CRC64: &str = "x-oss-hash-crc64ecma";
+const CONTENT_MD5: HeaderName = HeaderName::from_static("content-md5");
 
 /// # 文件的相关操作
 ///
@@ -110,27 +131,38 @@ where
 
     /// 上传文件内容到 OSS 上面
     async fn put_oss(&self, content: Vec<u8>, content_type: &str) -> Result<Response, FileError> {
+        self.put_object_with(content, content_type, &PutObject::default())
+            .await
+    }
+
+    /// 上传文件内容到 OSS 上面，可指定 ACL，存储类型，加密方式等参数
+    async fn put_object_with(
+        &self,
+        content: Vec<u8>,
+        content_type: &str,
+        options: &PutObject<'_>,
+    ) -> Result<Response, FileError> {
         let (url, canonicalized) = self.get_std().ok_or(FileError {
             kind: FileErrorKind::NotFoundCanonicalizedResource,
         })?;
 
-        let content_length = content.len().to_string();
-        let headers = vec![
-            (CONTENT_LENGTH, header_from_content_length(&content_length)?),
-            (
-                CONTENT_TYPE,
-                content_type.parse().map_err(|e| FileError {
-                    kind: FileErrorKind::InvalidContentType(e),
-                })?,
-            ),
-        ];
+        let client = self.oss_client();
+        let mut headers = put_headers(content.len(), content_type, options)?;
+        if client.content_md5_enabled() {
+            headers.push(content_md5_header(&content));
+        }
+        let crc64 = client.crc64_enabled().then(|| Crc64::checksum(&content));
 
-        self.oss_client()
+        let response = client
             .builder_with_header(Method::PUT, url, canonicalized, headers)?
             .body(content)
             .send_adjust_error()
-            .await
-            .map_err(FileError::from)
+            .await?;
+
+        if let Some(crc64) = crc64 {
+            check_crc64(header_crc64(response.headers()), crc64)?;
+        }
+        Ok(response)
     }
 
     /// # 获取 OSS 上文件的部分或全部内容
@@ -154,17 +186,91 @@ where
             range.into().into(),
         )];
 
-        let content = self
-            .oss_client()
+        let client = self.oss_client();
+        let response = client
             .builder_with_header(Method::GET, url, canonicalized, list)?
             .send_adjust_error()
-            .await?
-            .bytes()
             .await?;
+        let crc64 = client
+            .crc64_enabled()
+            .then(|| full_body_crc64(response.status(), response.headers()))
+            .flatten();
+
+        let content = response.bytes().await?;
+        if crc64.is_some() {
+            check_crc64(crc64, Crc64::checksum(&content))?;
+        }
 
         Ok(content.to_vec())
     }
 
+    /// # 获取 OSS 上文件的元信息
+    async fn head_object(&self) -> Result<ObjectMeta, FileError> {
+        let (url, canonicalized) = self.get_std().ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        let response = self
+            .oss_client()
+            .builder(Method::HEAD, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(ObjectMeta::from_headers(response.headers())?)
+    }
+
+    /// # 获取 OSS 上文件的部分元信息
+    /// 包括 etag，大小，最后修改时间等，比 [`head_object`] 更轻量
+    ///
+    /// [`head_object`]: File::head_object
+    async fn get_object_meta(&self) -> Result<ObjectMeta, FileError> {
+        let (url, canonicalized) = self.get_std().ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+        let (url, canonicalized) = std_with_query(url, canonicalized, OBJECT_META);
+
+        let response = self
+            .oss_client()
+            .builder(Method::HEAD, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(ObjectMeta::from_headers(response.headers())?)
+    }
+
+    /// # 获取文件的标签
+    async fn get_object_tagging(&self) -> Result<Tagging, FileError> {
+        let (url, canonicalized) = self.get_std().ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        get_tagging(&self.oss_client(), url, canonicalized).await
+    }
+
+    /// # 设置文件的标签，会覆盖文件原有的全部标签
+    async fn put_object_tagging(&self, tagging: &Tagging) -> Result<(), FileError> {
+        let (url, canonicalized) = self.get_std().ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        put_tagging(&self.oss_client(), url, canonicalized, tagging).await
+    }
+
+    /// # 删除文件的全部标签
+    async fn delete_object_tagging(&self) -> Result<(), FileError> {
+        let (url, canonicalized) = self.get_std().ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+        self.oss_client()
+            .builder(Method::DELETE, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(())
+    }
+
     /// # 从 OSS 中删除文件
     async fn delete_oss(&self) -> Result<(), FileError> {
         let (url, canonicalized) = self.get_std().ok_or(FileError {
@@ -484,28 +590,192 @@ where
         content: Vec<u8>,
         content_type: &str,
         path: Path,
+    ) -> Result<Response, FileError> {
+        self.put_object_with(content, content_type, path, &PutObject::default())
+            .await
+    }
+
+    /// # 上传文件到 OSS，可指定 ACL，存储类型，加密方式等参数
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main(){
+    /// # use dotenv::dotenv;
+    /// # dotenv().ok();
+    /// # let client = aliyun_oss_client::Client::from_env().unwrap();
+    /// use aliyun_oss_client::{
+    ///     file::Files,
+    ///     object::{ObjectAcl, PutObject, StorageClass},
+    /// };
+    ///
+    /// let options = PutObject {
+    ///     forbid_overwrite: true,
+    ///     object_acl: ObjectAcl::Private,
+    ///     storage_class: Some(StorageClass::IA),
+    ///     user_meta: vec![("author", "tu6ge")],
+    ///     ..Default::default()
+    /// };
+    /// let res = client
+    ///     .put_object_with(b"abc".to_vec(), "text/plain", "abc.txt", &options)
+    ///     .await;
+    /// assert!(res.is_ok());
+    /// # }
+    /// ```
+    async fn put_object_with(
+        &self,
+        content: Vec<u8>,
+        content_type: &str,
+        path: Path,
+        options: &PutObject<'_>,
     ) -> Result<Response, FileError> {
         let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
             kind: FileErrorKind::NotFoundCanonicalizedResource,
         })?;
 
-        let content_length = content.len().to_string();
-        let headers = vec![
-            (CONTENT_LENGTH, header_from_content_length(&content_length)?),
-            (
-                CONTENT_TYPE,
-                content_type.parse().map_err(|e| FileError {
-                    kind: FileErrorKind::InvalidContentType(e),
-                })?,
-            ),
-        ];
-
-        self.builder_with_header(Method::PUT, url, canonicalized, headers)?
+        let mut headers = put_headers(content.len(), content_type, options)?;
+        if self.content_md5_enabled() {
+            headers.push(content_md5_header(&content));
+        }
+        let crc64 = self.crc64_enabled().then(|| Crc64::checksum(&content));
+
+        let response = self
+            .builder_with_header(Method::PUT, url, canonicalized, headers)?
             .body(content)
             //.timeout(std::time::Duration::new(3, 0))
             .send_adjust_error()
-            .await
-            .map_err(FileError::from)
+            .await?;
+
+        if let Some(crc64) = crc64 {
+            check_crc64(header_crc64(response.headers()), crc64)?;
+        }
+        Ok(response)
+    }
+
+    /// # 从 AsyncRead 流式上传文件
+    ///
+    /// 内容小于 `part_size` 时使用一次 PUT 请求上传，否则自动切换为分片上传，
+    /// 每个分片的大小为 `part_size`（100KB 到 5GB 之间），最多 10000 个分片，内存中最多只保留一个分片
+    ///
+    /// 返回文件的 etag
+    async fn put_reader<R>(
+        &self,
+        mut reader: R,
+        content_type: &str,
+        path: Path,
+        part_size: usize,
+    ) -> Result<String, FileError>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        check_part_size(part_size)?;
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        // 分片按顺序上传，直接累加计算整个文件的 CRC64
+        let mut crc64 = self.crc64_enabled().then(Crc64::new);
+
+        let first = read_part(&mut reader, part_size).await?;
+        if first.len() < part_size {
+            if let Some(crc64) = crc64.as_mut() {
+                crc64.update(&first);
+            }
+            let mut headers = put_headers(first.len(), content_type, &PutObject::default())?;
+            if self.content_md5_enabled() {
+                headers.push(content_md5_header(&first));
+            }
+            let response = self
+                .builder_with_header(Method::PUT, url, canonicalized, headers)?
+                .body(first)
+                .send_adjust_error()
+                .await?;
+            if let Some(crc64) = crc64 {
+                check_crc64(header_crc64(response.headers()), crc64.get())?;
+            }
+            return response_etag(response.headers());
+        }
+
+        let upload_id = init_multipart(self, &url, &canonicalized, content_type).await?;
+        let result = async {
+            let mut etags = Vec::new();
+            let mut part = first;
+            while !part.is_empty() {
+                check_parts_count(etags.len())?;
+                let query = format!("partNumber={}&uploadId={upload_id}", etags.len() + 1);
+                let (url, canonicalized) =
+                    std_with_query(url.clone(), canonicalized.clone(), &query);
+                let mut headers = vec![(
+                    CONTENT_LENGTH,
+                    header_from_content_length(&part.len().to_string())?,
+                )];
+                if self.content_md5_enabled() {
+                    headers.push(content_md5_header(&part));
+                }
+                if let Some(crc64) = crc64.as_mut() {
+                    crc64.update(&part);
+                }
+                let response = self
+                    .builder_with_header(Method::PUT, url, canonicalized, headers)?
+                    .body(part)
+                    .send_adjust_error()
+                    .await?;
+                etags.push(response_etag(response.headers())?);
+
+                part = read_part(&mut reader, part_size).await?;
+            }
+
+            let body = complete_body(&etags);
+            let (url, canonicalized) = std_with_query(
+                url.clone(),
+                canonicalized.clone(),
+                &format!("uploadId={upload_id}"),
+            );
+            let headers = [(
+                CONTENT_LENGTH,
+                header_from_content_length(&body.len().to_string())?,
+            )];
+            let response = self
+                .builder_with_header(Method::POST, url, canonicalized, headers)?
+                .body(body)
+                .send_adjust_error()
+                .await?;
+            if let Some(crc64) = crc64 {
+                check_crc64(header_crc64(response.headers()), crc64.get())?;
+            }
+            response_etag(response.headers())
+        }
+        .await;
+
+        if result.is_err() {
+            let (url, canonicalized) =
+                std_with_query(url, canonicalized, &format!("uploadId={upload_id}"));
+            if let Ok(builder) = self.builder(Method::DELETE, url, canonicalized) {
+                let _ = builder.send_adjust_error().await;
+            }
+        }
+        result
+    }
+
+    /// # 从 Stream 流式上传文件
+    ///
+    /// 与 [`put_reader`] 相同，内容较大时自动切换为分片上传
+    ///
+    /// [`put_reader`]: Files::put_reader
+    async fn put_stream<S, E>(
+        &self,
+        stream: S,
+        content_type: &str,
+        path: Path,
+        part_size: usize,
+    ) -> Result<String, FileError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin + Send,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let reader = stream
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            .into_async_read();
+        self.put_reader(reader, content_type, path, part_size).await
     }
 
     /// # 获取 OSS 上文件的部分或全部内容
@@ -526,99 +796,716 @@ where
             range.into().into(),
         )];
 
-        let content = self
+        let response = self
             .builder_with_header(Method::GET, url, canonicalized, list)?
             .send_adjust_error()
-            .await?
-            .bytes()
             .await?;
+        let crc64 = self
+            .crc64_enabled()
+            .then(|| full_body_crc64(response.status(), response.headers()))
+            .flatten();
+
+        let content = response.bytes().await?;
+        if crc64.is_some() {
+            check_crc64(crc64, Crc64::checksum(&content))?;
+        }
 
         Ok(content.to_vec())
     }
 
-    /// # 删除 OSS 上的文件
-    async fn delete_object(&self, path: Path) -> Result<(), FileError> {
+    /// # 以流的方式获取 OSS 上文件的部分或全部内容
+    ///
+    /// 返回的 [`ObjectStream`] 实现了 `Stream` 和 `AsyncRead`，不会将文件内容全部读入内存
+    ///
+    /// [`ObjectStream`]: crate::object::ObjectStream
+    async fn get_object_stream<Num, R>(
+        &self,
+        path: Path,
+        range: R,
+    ) -> Result<ObjectStream, FileError>
+    where
+        R: Into<ContentRange<Num>> + Send + Sync,
+        ContentRange<Num>: Into<HeaderValue>,
+    {
         let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
             kind: FileErrorKind::NotFoundCanonicalizedResource,
         })?;
 
-        self.builder(Method::DELETE, url, canonicalized)?
+        let list: Vec<(_, HeaderValue)> = vec![(
+            {
+                #[allow(clippy::unwrap_used)]
+                RANGE.parse().unwrap()
+            },
+            range.into().into(),
+        )];
+
+        let response = self
+            .builder_with_header(Method::GET, url, canonicalized, list)?
             .send_adjust_error()
             .await?;
 
-        Ok(())
+        Ok(ObjectStream::new(response))
     }
-}
-
-fn header_from_content_length(content: &str) -> Result<HeaderValue, FileError> {
-    HeaderValue::from_str(content).map_err(|e| FileError {
-        kind: FileErrorKind::InvalidContentLength(e),
-    })
-}
 
-/// # 为更多的类型实现 上传，下载，删除等功能
-///
-/// 在 [`Client`]，[`Bucket`], [`ObjectList`] 等结构体中均已实现，其中 Client 是在默认的 bucket 上操作文件，
-/// 而 Bucket, ObjectList 则是在当前的 bucket 上操作文件
-///
-/// [`Client`]: crate::client::Client
-/// [`Bucket`]: crate::bucket::Bucket
-/// [`ObjectList`]: crate::object::ObjectList
-impl<P: Send + Sync + 'static, T: AlignBuilder + GetStdWithPath<P>> Files<P> for T {}
+    /// # 获取 OSS 上文件的元信息
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main(){
+    /// # use dotenv::dotenv;
+    /// # dotenv().ok();
+    /// # let client = aliyun_oss_client::Client::from_env().unwrap();
+    /// use aliyun_oss_client::file::Files;
+    ///
+    /// let meta = client.head_object("9AB932LY.jpeg").await.unwrap();
+    /// println!("size: {}, type: {:?}", meta.content_length(), meta.content_type());
+    /// # }
+    /// ```
+    async fn head_object(&self, path: Path) -> Result<ObjectMeta, FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
 
-/// 文件模块的 Error
-#[derive(Debug)]
-pub struct FileError {
-    kind: error_impl::FileErrorKind,
-}
+        let response = self
+            .builder(Method::HEAD, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
 
-impl FileError {
-    #[cfg(test)]
-    pub(crate) fn test_new() -> Self {
-        Self {
-            kind: error_impl::FileErrorKind::EtagNotFound,
-        }
+        Ok(ObjectMeta::from_headers(response.headers())?)
     }
-}
 
-/// 文件模块的 Error 实现方法
-mod error_impl {
-    use std::{error::Error, fmt::Display, io::ErrorKind};
+    /// # 获取 OSS 上文件的部分元信息
+    /// 包括 etag，大小，最后修改时间等，比 [`head_object`] 更轻量
+    ///
+    /// [`head_object`]: Files::head_object
+    async fn get_object_meta(&self, path: Path) -> Result<ObjectMeta, FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+        let (url, canonicalized) = std_with_query(url, canonicalized, OBJECT_META);
 
-    use http::header::InvalidHeaderValue;
+        let response = self
+            .builder(Method::HEAD, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
 
-    use crate::builder::{reqwest_to_io, BuilderError};
+        Ok(ObjectMeta::from_headers(response.headers())?)
+    }
 
-    use super::FileError;
+    /// # 复制文件
+    ///
+    /// `path` 为目标文件的路径，源文件等参数在 [`CopyObject`] 中指定
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main(){
+    /// # use dotenv::dotenv;
+    /// # dotenv().ok();
+    /// # let client = aliyun_oss_client::Client::from_env().unwrap();
+    /// use aliyun_oss_client::{file::Files, object::CopyObject};
+    ///
+    /// let options = CopyObject {
+    ///     copy_source: "abc.txt",
+    ///     ..Default::default()
+    /// };
+    /// let res = client.copy_object(&options, "abc_copy.txt").await.unwrap();
+    /// println!("etag: {}", res.etag());
+    /// # }
+    /// ```
+    ///
+    /// [`CopyObject`]: crate::object::CopyObject
+    async fn copy_object(
+        &self,
+        options: &CopyObject<'_>,
+        path: Path,
+    ) -> Result<CopyObjectResult, FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+        let headers = copy_headers(options, &canonicalized)?;
 
-    impl Display for FileError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            use FileErrorKind::*;
-            match &self.kind {
-                #[cfg(feature = "put_file")]
-                FileRead(_) => write!(f, "file read failed"),
-                InvalidContentLength(_) => write!(f, "invalid content length"),
-                InvalidContentType(_) => write!(f, "invalid content type"),
-                Build(to) => write!(f, "{to}"),
-                Reqwest(_) => write!(f, "reqwest error"),
-                EtagNotFound => write!(f, "failed to get etag"),
-                InvalidEtag(_) => write!(f, "invalid etag"),
-                NotFoundCanonicalizedResource => write!(f, "not found canonicalized-resource"),
-            }
-        }
+        let xml = self
+            .builder_with_header(Method::PUT, url, canonicalized, headers)?
+            .send_adjust_error()
+            .await?
+            .text()
+            .await?;
+
+        parse_copy_result(&xml)
     }
 
-    impl Error for FileError {
-        fn source(&self) -> Option<&(dyn Error + 'static)> {
-            use FileErrorKind::*;
-            match &self.kind {
+    /// # 追加内容到 OSS 上的文件
+    ///
+    /// 文件不存在时，`position` 需为 0，会创建一个 Appendable 类型的文件，
+    /// 之后每次追加时，`position` 需等于文件当前的长度，即上次返回的 `next_position`
+    ///
+    /// 需要自动维护追加位置时，可以使用 [`Appender`]
+    ///
+    /// [`Appender`]: crate::object::Appender
+    async fn append_object(
+        &self,
+        content: Vec<u8>,
+        content_type: &str,
+        path: Path,
+        position: u64,
+    ) -> Result<AppendObjectResult, FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+        let (url, canonicalized) =
+            std_with_query(url, canonicalized, &format!("append&position={position}"));
+
+        let headers = put_headers(content.len(), content_type, &PutObject::default())?;
+        // 从头追加时，整个文件的 CRC64 即为本次内容的值
+        let crc64 = (position == 0 && self.crc64_enabled()).then(|| Crc64::checksum(&content));
+
+        let response = self
+            .builder_with_header(Method::POST, url, canonicalized, headers)?
+            .body(content)
+            .send_adjust_error()
+            .await?;
+
+        let result = parse_append_result(response.headers())?;
+        if let Some(crc64) = crc64 {
+            check_crc64(result.crc64(), crc64)?;
+        }
+        Ok(result)
+    }
+
+    /// # 获取文件的标签
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main(){
+    /// # use dotenv::dotenv;
+    /// # dotenv().ok();
+    /// # let client = aliyun_oss_client::Client::from_env().unwrap();
+    /// use aliyun_oss_client::file::Files;
+    ///
+    /// let tagging = client.get_object_tagging("abc.txt").await.unwrap();
+    /// for (key, value) in tagging.iter() {
+    ///     println!("{key}: {value}");
+    /// }
+    /// # }
+    /// ```
+    async fn get_object_tagging(&self, path: Path) -> Result<Tagging, FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        get_tagging(self, url, canonicalized).await
+    }
+
+    /// # 设置文件的标签
+    ///
+    /// 会覆盖文件原有的全部标签，上传时设置标签可以使用 [`PutObject`] 的 `tagging`
+    ///
+    /// [`PutObject`]: crate::object::PutObject
+    async fn put_object_tagging(&self, path: Path, tagging: &Tagging) -> Result<(), FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        put_tagging(self, url, canonicalized, tagging).await
+    }
+
+    /// # 删除文件的全部标签
+    async fn delete_object_tagging(&self, path: Path) -> Result<(), FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+        let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+
+        self.builder(Method::DELETE, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(())
+    }
+
+    /// # 删除 OSS 上的文件
+    async fn delete_object(&self, path: Path) -> Result<(), FileError> {
+        let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        })?;
+
+        self.builder(Method::DELETE, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// 读取一个分片，读到末尾时返回的内容会小于 `part_size`
+async fn read_part<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    part_size: usize,
+) -> Result<Vec<u8>, FileError> {
+    // 分片可能很大，而内容可能很小，由 read_to_end 按需扩容
+    let mut part = Vec::new();
+    reader
+        .take(part_size as u64)
+        .read_to_end(&mut part)
+        .await
+        .map_err(|e| FileError {
+            kind: FileErrorKind::ReadBody(e),
+        })?;
+    Ok(part)
+}
+
+/// 初始化分片上传，返回 upload id
+async fn init_multipart<B: AlignBuilder + ?Sized>(
+    builder: &B,
+    url: &Url,
+    canonicalized: &CanonicalizedResource,
+    content_type: &str,
+) -> Result<String, FileError> {
+    let (url, canonicalized) = std_with_query(url.clone(), canonicalized.clone(), "uploads");
+    let headers = [(
+        CONTENT_TYPE,
+        content_type.parse().map_err(|e| FileError {
+            kind: FileErrorKind::InvalidContentType(e),
+        })?,
+    )];
+
+    let xml = builder
+        .builder_with_header(Method::POST, url, canonicalized, headers)?
+        .send_adjust_error()
+        .await?
+        .text()
+        .await?;
+
+    upload_id(&xml)
+}
+
+/// 获取文件的标签
+async fn get_tagging<B: AlignBuilder + ?Sized>(
+    builder: &B,
+    url: Url,
+    canonicalized: CanonicalizedResource,
+) -> Result<Tagging, FileError> {
+    let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+
+    let xml = builder
+        .builder(Method::GET, url, canonicalized)?
+        .send_adjust_error()
+        .await?
+        .text()
+        .await?;
+
+    Ok(Tagging::from_xml(&xml)?)
+}
+
+/// 设置文件的标签
+async fn put_tagging<B: AlignBuilder + ?Sized>(
+    builder: &B,
+    url: Url,
+    canonicalized: CanonicalizedResource,
+    tagging: &Tagging,
+) -> Result<(), FileError> {
+    let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+    let body = tagging.to_xml();
+    let headers = tagging_headers(&body, builder.content_md5_enabled())?;
+
+    builder
+        .builder_with_header(Method::PUT, url, canonicalized, headers)?
+        .body(body)
+        .send_adjust_error()
+        .await?;
+
+    Ok(())
+}
+
+/// PutObjectTagging 接口的 headers
+fn tagging_headers(
+    body: &str,
+    content_md5: bool,
+) -> Result<Vec<(HeaderName, HeaderValue)>, FileError> {
+    let mut headers = vec![(
+        CONTENT_LENGTH,
+        header_from_content_length(&body.len().to_string())?,
+    )];
+    if content_md5 {
+        headers.push(content_md5_header(body.as_bytes()));
+    }
+    Ok(headers)
+}
+
+/// # 批量删除文件
+///
+/// 每次请求最多删除 1000 个文件，超出时拆分为多次请求
+pub(crate) async fn delete_objects<B: AlignBuilder>(
+    builder: &B,
+    bucket: &BucketBase,
+    objects: Vec<ObjectIdentifier>,
+    quiet: bool,
+) -> Result<Vec<DeleteResult>, FileError> {
+    let mut results = Vec::with_capacity(objects.len());
+    for chunk in objects.chunks(MAX_DELETE_KEYS) {
+        let body = delete_body(chunk, quiet);
+        let (url, canonicalized) = delete_std(bucket);
+
+        let response = builder
+            .builder_with_header(Method::POST, url, canonicalized, delete_headers(&body)?)?
+            .body(body)
+            .send_adjust_error()
+            .await?;
+
+        results.extend(parse_delete_result(
+            &response.text().await?,
+            chunk.to_vec(),
+        )?);
+    }
+    Ok(results)
+}
+
+/// DeleteMultipleObjects 接口的 url 和 CanonicalizedResource
+fn delete_std(bucket: &BucketBase) -> (Url, CanonicalizedResource) {
+    let mut url = bucket.to_url();
+    url.set_query(Some(DELETE));
+    let canonicalized = CanonicalizedResource::new(format!("/{}/?{DELETE}", bucket.name()));
+    (url, canonicalized)
+}
+
+/// 批量删除的 headers，需要携带请求体的 Content-MD5
+fn delete_headers(body: &str) -> Result<Vec<(HeaderName, HeaderValue)>, FileError> {
+    Ok(vec![
+        (
+            CONTENT_LENGTH,
+            header_from_content_length(&body.len().to_string())?,
+        ),
+        (
+            CONTENT_MD5,
+            content_md5(body).parse().map_err(|e| FileError {
+                kind: FileErrorKind::InvalidHeader(http::Error::from(e)),
+            })?,
+        ),
+    ])
+}
+
+/// 请求体的 Content-MD5，会参与签名
+pub(crate) fn content_md5_header(body: &[u8]) -> (HeaderName, HeaderValue) {
+    let value = ContentMd5::from_body(body)
+        .try_into()
+        .expect("base64 must be a valid header value");
+    (CONTENT_MD5, value)
+}
+
+/// 添加子资源，如 GetObjectMeta 接口的 `objectMeta`
+pub(crate) fn std_with_query(
+    mut url: Url,
+    canonicalized: CanonicalizedResource,
+    query: &str,
+) -> (Url, CanonicalizedResource) {
+    url.set_query(Some(query));
+    let canonicalized = CanonicalizedResource::new(format!("{canonicalized}?{query}"));
+    (url, canonicalized)
+}
+
+/// 复制文件的 headers，源文件未指定 bucket 时，使用目标文件的 bucket
+fn copy_headers(
+    options: &CopyObject<'_>,
+    canonicalized: &CanonicalizedResource,
+) -> Result<Vec<(HeaderName, HeaderValue)>, FileError> {
+    let resource: &str = canonicalized.as_ref();
+    let bucket = resource
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    options.headers(bucket).map_err(|e| FileError {
+        kind: FileErrorKind::InvalidHeader(e),
+    })
+}
+
+/// 解析 CopyObjectResult
+fn parse_copy_result(xml: &str) -> Result<CopyObjectResult, FileError> {
+    const E_TAG: &[u8] = b"ETag";
+    const LAST_MODIFIED: &[u8] = b"LastModified";
+
+    let etag = decode_text(xml, E_TAG)?.ok_or(FileError {
+        kind: FileErrorKind::EtagNotFound,
+    })?;
+    let last_modified = decode_text(xml, LAST_MODIFIED)?.unwrap_or_default();
+    let last_modified = last_modified.parse().map_err(|e| FileError {
+        kind: FileErrorKind::InvalidLastModified(e),
+    })?;
+
+    Ok(CopyObjectResult::new(etag, last_modified))
+}
+
+/// 解析追加上传的结果
+fn parse_append_result(headers: &HeaderMap) -> Result<AppendObjectResult, FileError> {
+    let meta = ObjectMeta::from_headers(headers)?;
+    let next_position = meta.next_append_position().ok_or(FileError {
+        kind: FileErrorKind::NextPositionNotFound,
+    })?;
+    Ok(AppendObjectResult::new(next_position, meta.crc64()))
+}
+
+/// 上传文件的 headers
+fn put_headers(
+    content_length: usize,
+    content_type: &str,
+    options: &PutObject<'_>,
+) -> Result<Vec<(HeaderName, HeaderValue)>, FileError> {
+    let mut headers = vec![
+        (
+            CONTENT_LENGTH,
+            header_from_content_length(&content_length.to_string())?,
+        ),
+        (
+            CONTENT_TYPE,
+            content_type.parse().map_err(|e| FileError {
+                kind: FileErrorKind::InvalidContentType(e),
+            })?,
+        ),
+    ];
+    headers.extend(options.headers().map_err(|e| FileError {
+        kind: FileErrorKind::InvalidHeader(e),
+    })?);
+    Ok(headers)
+}
+
+fn header_from_content_length(content: &str) -> Result<HeaderValue, FileError> {
+    HeaderValue::from_str(content).map_err(|e| FileError {
+        kind: FileErrorKind::InvalidContentLength(e),
+    })
+}
+
+/// 从响应的 headers 中获取 etag
+fn response_etag(headers: &HeaderMap) -> Result<String, FileError> {
+    let etag = headers.get(ETAG).ok_or(FileError {
+        kind: FileErrorKind::EtagNotFound,
+    })?;
+    etag.to_str().map(ToOwned::to_owned).map_err(|e| FileError {
+        kind: FileErrorKind::InvalidEtag(e),
+    })
+}
+
+/// 从 InitiateMultipartUpload 接口的响应中获取 upload id
+fn upload_id(xml: &str) -> Result<String, FileError> {
+    parse_upload_id(xml)
+        .map(ToOwned::to_owned)
+        .ok_or(FileError {
+            kind: FileErrorKind::UploadIdNotFound,
+        })
+}
+
+/// 按顺序上传的分片，编号从 1 开始
+fn complete_body(etags: &[String]) -> String {
+    complete_xml(
+        etags
+            .iter()
+            .zip(1..)
+            .map(|(etag, index)| (index, etag.as_str())),
+    )
+}
+
+/// 检查流式上传的分片尺寸
+fn check_part_size(part_size: usize) -> Result<(), FileError> {
+    if Inner::valid_part_size(part_size) {
+        Ok(())
+    } else {
+        Err(FileError {
+            kind: FileErrorKind::InvalidPartSize,
+        })
+    }
+}
+
+/// 已上传的分片达到数量上限时，不能再上传新的分片
+fn check_parts_count(uploaded: usize) -> Result<(), FileError> {
+    if uploaded < Inner::MAX_PARTS_COUNT as usize {
+        Ok(())
+    } else {
+        Err(FileError {
+            kind: FileErrorKind::OverflowMaxPartsCount,
+        })
+    }
+}
+
+/// # 为更多的类型实现 上传，下载，删除等功能
+///
+/// 在 [`Client`]，[`Bucket`], [`ObjectList`] 等结构体中均已实现，其中 Client 是在默认的 bucket 上操作文件，
+/// 而 Bucket, ObjectList 则是在当前的 bucket 上操作文件
+///
+/// [`Client`]: crate::client::Client
+/// [`Bucket`]: crate::bucket::Bucket
+/// [`ObjectList`]: crate::object::ObjectList
+impl<P: Send + Sync + 'static, T: AlignBuilder + GetStdWithPath<P>> Files<P> for T {}
+
+/// 文件模块的 Error
+#[derive(Debug)]
+pub struct FileError {
+    kind: error_impl::FileErrorKind,
+}
+
+impl FileError {
+    #[cfg(test)]
+    pub(crate) fn test_new() -> Self {
+        Self {
+            kind: error_impl::FileErrorKind::EtagNotFound,
+        }
+    }
+
+    /// 目标目录位于源目录之下
+    pub(crate) fn nested_prefix() -> Self {
+        Self {
+            kind: FileErrorKind::NestedPrefix,
+        }
+    }
+
+    /// 找不到文件对应的 Url 和 CanonicalizedResource
+    pub(crate) fn not_found_canonicalized() -> Self {
+        Self {
+            kind: FileErrorKind::NotFoundCanonicalizedResource,
+        }
+    }
+
+    /// 本地计算的 CRC64 值与 OSS 返回的不一致
+    pub(crate) fn crc64_mismatch(expected: u64, actual: u64) -> Self {
+        Self {
+            kind: FileErrorKind::Crc64Mismatch { expected, actual },
+        }
+    }
+
+    /// 是否是 CRC64 校验失败的错误
+    pub fn is_crc64_mismatch(&self) -> bool {
+        matches!(self.kind, FileErrorKind::Crc64Mismatch { .. })
+    }
+
+    /// 读取过程中文件被修改（etag 发生变化）
+    pub(crate) fn object_changed() -> Self {
+        Self {
+            kind: FileErrorKind::ObjectChanged,
+        }
+    }
+
+    /// 是否是读取过程中文件被修改的错误
+    pub fn is_object_changed(&self) -> bool {
+        matches!(self.kind, FileErrorKind::ObjectChanged)
+    }
+
+    /// OSS 服务端返回的错误码
+    pub(crate) fn oss_code(&self) -> Option<&str> {
+        match &self.kind {
+            FileErrorKind::Build(BuilderError {
+                kind: BuilderErrorKind::OssService(e),
+            }) => Some(e.code()),
+            _ => None,
+        }
+    }
+}
+
+/// 读取响应中 OSS 记录的 CRC64 值
+pub(crate) fn header_crc64(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(HASH_CRC64)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+/// 响应体为完整的文件内容时，返回 OSS 记录的 CRC64 值，范围下载时返回 None
+pub(crate) fn full_body_crc64(status: StatusCode, headers: &HeaderMap) -> Option<u64> {
+    let is_full = match status {
+        StatusCode::OK => true,
+        StatusCode::PARTIAL_CONTENT => headers
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| {
+                let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+                let (start, end) = range.split_once('-')?;
+                let end: u64 = end.parse().ok()?;
+                Some(start == "0" && end.checked_add(1) == total.parse().ok())
+            })
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    is_full.then(|| header_crc64(headers)).flatten()
+}
+
+/// 比对 OSS 返回的 CRC64 值与本地计算的值，OSS 没有返回时不做校验
+pub(crate) fn check_crc64(expected: Option<u64>, actual: u64) -> Result<(), FileError> {
+    match expected {
+        Some(expected) if expected != actual => Err(FileError::crc64_mismatch(expected, actual)),
+        _ => Ok(()),
+    }
+}
+
+/// 文件模块的 Error 实现方法
+mod error_impl {
+    use std::{error::Error, fmt::Display, io::ErrorKind};
+
+    use http::header::InvalidHeaderValue;
+
+    use crate::{
+        builder::{reqwest_to_io, BuilderError},
+        decode::{InnerItemError, InnerListError},
+        object::{meta::InvalidObjectMeta, ExtractListError, ExtractListErrorKind, InvalidTagging},
+    };
+
+    use super::FileError;
+
+    impl Display for FileError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            use FileErrorKind::*;
+            match &self.kind {
+                #[cfg(feature = "put_file")]
+                FileRead(_) => write!(f, "file read failed"),
+                InvalidContentLength(_) => write!(f, "invalid content length"),
+                InvalidContentType(_) => write!(f, "invalid content type"),
+                Build(to) => write!(f, "{to}"),
+                Reqwest(_) => write!(f, "reqwest error"),
+                EtagNotFound => write!(f, "failed to get etag"),
+                InvalidEtag(_) => write!(f, "invalid etag"),
+                Meta(_) => write!(f, "invalid object meta"),
+                InvalidHeader(_) => write!(f, "invalid header"),
+                InvalidLastModified(_) => write!(f, "invalid last modified"),
+                NextPositionNotFound => write!(f, "failed to get next append position"),
+                ReadBody(_) => write!(f, "read body failed"),
+                UploadIdNotFound => write!(f, "not found upload id"),
+                InvalidPartSize => write!(f, "part size must be between 100k and 5G"),
+                OverflowMaxPartsCount => write!(f, "overflow max parts count"),
+                List(e) => write!(f, "list failed: {e}"),
+                Decode(e) => write!(f, "decode xml failed: {e}"),
+                NestedPrefix => write!(f, "destination prefix is inside the source prefix"),
+                NotFoundCanonicalizedResource => write!(f, "not found canonicalized-resource"),
+                Crc64Mismatch { expected, actual } => {
+                    write!(f, "crc64 mismatch, expected {expected}, actual {actual}")
+                }
+                Tagging(_) => write!(f, "invalid tagging"),
+                ObjectChanged => write!(f, "object has been changed"),
+            }
+        }
+    }
+
+    impl Error for FileError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            use FileErrorKind::*;
+            match &self.kind {
                 #[cfg(feature = "put_file")]
                 FileRead(e) => Some(e),
                 InvalidContentLength(e) | InvalidContentType(e) => Some(e),
                 Build(e) => e.source(),
                 Reqwest(e) => Some(e),
                 InvalidEtag(e) => Some(e),
-                EtagNotFound | NotFoundCanonicalizedResource => None,
+                Meta(e) => Some(e),
+                InvalidHeader(e) => Some(e),
+                InvalidLastModified(e) => Some(e),
+                ReadBody(e) => Some(e),
+                Tagging(e) => Some(e),
+                List(e) | Decode(e) => Some(e),
+                EtagNotFound
+                | UploadIdNotFound
+                | InvalidPartSize
+                | OverflowMaxPartsCount
+                | NextPositionNotFound
+                | NestedPrefix
+                | NotFoundCanonicalizedResource
+                | Crc64Mismatch { .. }
+                | ObjectChanged => None,
             }
         }
     }
@@ -633,7 +1520,25 @@ mod error_impl {
         Reqwest(reqwest::Error),
         EtagNotFound,
         InvalidEtag(http::header::ToStrError),
+        Meta(InvalidObjectMeta),
+        InvalidHeader(http::Error),
+        InvalidLastModified(chrono::ParseError),
+        NextPositionNotFound,
+        ReadBody(std::io::Error),
+        UploadIdNotFound,
+        InvalidPartSize,
+        OverflowMaxPartsCount,
+        List(ListFailed),
+        Decode(ListFailed),
+        NestedPrefix,
         NotFoundCanonicalizedResource,
+        /// expected 为 OSS 返回的值，actual 为本地计算的值
+        Crc64Mismatch {
+            expected: u64,
+            actual: u64,
+        },
+        Tagging(InvalidTagging),
+        ObjectChanged,
     }
 
     impl From<BuilderError> for FileError {
@@ -644,6 +1549,85 @@ mod error_impl {
         }
     }
 
+    impl From<InvalidObjectMeta> for FileError {
+        fn from(value: InvalidObjectMeta) -> Self {
+            Self {
+                kind: FileErrorKind::Meta(value),
+            }
+        }
+    }
+
+    impl From<ExtractListError> for FileError {
+        fn from(value: ExtractListError) -> Self {
+            let kind = match value.kind {
+                ExtractListErrorKind::Builder(e) => FileErrorKind::Build(e),
+                ExtractListErrorKind::Reqwest(e) => FileErrorKind::Reqwest(e),
+                kind => FileErrorKind::List(ListFailed::new(&ExtractListError { kind })),
+            };
+            Self { kind }
+        }
+    }
+
+    impl From<InnerListError> for FileError {
+        fn from(value: InnerListError) -> Self {
+            let failed = ListFailed {
+                message: value.to_string(),
+                source: value.get_source().map(|e| Box::new(ListFailed::new(e))),
+            };
+            Self {
+                kind: FileErrorKind::List(failed),
+            }
+        }
+    }
+
+    impl From<InnerItemError> for FileError {
+        fn from(value: InnerItemError) -> Self {
+            let failed = ListFailed {
+                message: value.to_string(),
+                source: value.get_source().map(|e| Box::new(ListFailed::new(e))),
+            };
+            Self {
+                kind: FileErrorKind::Decode(failed),
+            }
+        }
+    }
+
+    /// 解析 xml 的错误不是 Send，逐层保留错误信息，source 链与原错误一致
+    #[derive(Debug)]
+    pub(super) struct ListFailed {
+        message: String,
+        source: Option<Box<ListFailed>>,
+    }
+
+    impl ListFailed {
+        fn new(err: &(dyn Error + 'static)) -> Self {
+            Self {
+                message: err.to_string(),
+                source: err.source().map(|e| Box::new(Self::new(e))),
+            }
+        }
+    }
+
+    impl Display for ListFailed {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.message.fmt(f)
+        }
+    }
+
+    impl Error for ListFailed {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+        }
+    }
+
+    impl From<InvalidTagging> for FileError {
+        fn from(value: InvalidTagging) -> Self {
+            Self {
+                kind: FileErrorKind::Tagging(value),
+            }
+        }
+    }
+
     impl From<reqwest::Error> for FileError {
         fn from(value: reqwest::Error) -> Self {
             Self {
@@ -673,9 +1657,41 @@ mod error_impl {
                 FileErrorKind::Reqwest(e) => reqwest_to_io(e),
                 FileErrorKind::EtagNotFound => Self::new(ErrorKind::Interrupted, "etag not found"),
                 FileErrorKind::InvalidEtag(_) => Self::new(ErrorKind::Interrupted, "invalid etag"),
+                FileErrorKind::Meta(_) => Self::new(ErrorKind::InvalidData, "invalid object meta"),
+                FileErrorKind::InvalidHeader(_) => {
+                    Self::new(ErrorKind::InvalidData, "invalid header")
+                }
+                FileErrorKind::InvalidLastModified(_) => {
+                    Self::new(ErrorKind::InvalidData, "invalid last modified")
+                }
+                FileErrorKind::NextPositionNotFound => {
+                    Self::new(ErrorKind::Interrupted, "next append position not found")
+                }
+                FileErrorKind::ReadBody(e) => e,
+                FileErrorKind::UploadIdNotFound => {
+                    Self::new(ErrorKind::Interrupted, "upload id not found")
+                }
+                FileErrorKind::InvalidPartSize => {
+                    Self::new(ErrorKind::InvalidInput, "invalid part size")
+                }
+                FileErrorKind::OverflowMaxPartsCount => {
+                    Self::new(ErrorKind::InvalidInput, "overflow max parts count")
+                }
+                FileErrorKind::List(e) => Self::new(ErrorKind::Other, e),
+                FileErrorKind::Decode(e) => Self::new(ErrorKind::InvalidData, e),
+                FileErrorKind::NestedPrefix => {
+                    Self::new(ErrorKind::InvalidInput, "nested destination prefix")
+                }
                 FileErrorKind::NotFoundCanonicalizedResource => {
                     Self::new(ErrorKind::InvalidData, "not found canonicalized resource")
                 }
+                FileErrorKind::Crc64Mismatch { .. } => {
+                    Self::new(ErrorKind::InvalidData, "crc64 mismatch")
+                }
+                FileErrorKind::Tagging(e) => Self::new(ErrorKind::InvalidData, e),
+                FileErrorKind::ObjectChanged => {
+                    Self::new(ErrorKind::InvalidData, "object has been changed")
+                }
             }
         }
     }
@@ -709,6 +1725,18 @@ pub trait AlignBuilder: Send + Sync {
         resource: CanonicalizedResource,
         headers: H,
     ) -> Result<RequestBuilder, BuilderError>;
+
+    /// 是否开启了 CRC64 校验
+    #[inline]
+    fn crc64_enabled(&self) -> bool {
+        false
+    }
+
+    /// 是否自动计算 Content-MD5
+    #[inline]
+    fn content_md5_enabled(&self) -> bool {
+        false
+    }
 }
 
 impl AlignBuilder for Bucket {
@@ -723,6 +1751,16 @@ impl AlignBuilder for Bucket {
         self.client()
             .builder_with_header(method, url, resource, headers)
     }
+
+    #[inline]
+    fn crc64_enabled(&self) -> bool {
+        self.client().crc64_enabled()
+    }
+
+    #[inline]
+    fn content_md5_enabled(&self) -> bool {
+        self.client().content_md5_enabled()
+    }
 }
 
 impl<Item: Send + Sync> AlignBuilder for ObjectList<ArcPointer, Item> {
@@ -737,6 +1775,16 @@ impl<Item: Send + Sync> AlignBuilder for ObjectList<ArcPointer, Item> {
         self.client()
             .builder_with_header(method, url, resource, headers)
     }
+
+    #[inline]
+    fn crc64_enabled(&self) -> bool {
+        self.client().crc64_enabled()
+    }
+
+    #[inline]
+    fn content_md5_enabled(&self) -> bool {
+        self.client().content_md5_enabled()
+    }
 }
 
 #[cfg(feature = "blocking")]
@@ -748,15 +1796,29 @@ use self::error_impl::FileErrorKind;
 #[cfg(feature = "blocking")]
 pub mod blocking {
 
+    use std::io::Read;
+
     use super::{
-        error_impl::FileErrorKind, header_from_content_length, FileError, GetStdWithPath, ETAG,
-        RANGE,
+        check_crc64, check_part_size, check_parts_count, complete_body, content_md5_header,
+        copy_headers, delete_headers, delete_std, error_impl::FileErrorKind, full_body_crc64,
+        header_crc64, header_from_content_length, parse_append_result, parse_copy_result,
+        put_headers, response_etag, std_with_query, tagging_headers, upload_id, FileError,
+        GetStdWithPath, ETAG, OBJECT_META, RANGE, TAGGING,
     };
     use crate::{
         blocking::builder::RequestBuilder,
         bucket::Bucket,
         builder::{BuilderError, RcPointer},
-        object::ObjectList,
+        config::BucketBase,
+        crc64::Crc64,
+        object::{
+            delete::{
+                delete_body, parse_delete_result, DeleteResult, ObjectIdentifier, MAX_DELETE_KEYS,
+            },
+            stream::blocking::ObjectReader,
+            AppendObjectResult, CopyObject, CopyObjectResult, ObjectList, ObjectMeta, PutObject,
+            Tagging,
+        },
         types::{CanonicalizedResource, ContentRange},
     };
     use http::{
@@ -858,26 +1920,149 @@ pub mod blocking {
             content_type: &str,
             path: Path,
         ) -> Result<Response, FileError> {
+            self.put_object_with(content, content_type, path, &PutObject::default())
+        }
+
+        /// # 上传文件到 OSS，可指定 ACL，存储类型，加密方式等参数
+        fn put_object_with(
+            &self,
+            content: Vec<u8>,
+            content_type: &str,
+            path: Path,
+            options: &PutObject<'_>,
+        ) -> Result<Response, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+
+            let mut headers = put_headers(content.len(), content_type, options)?;
+            if self.content_md5_enabled() {
+                headers.push(content_md5_header(&content));
+            }
+            let crc64 = self.crc64_enabled().then(|| Crc64::checksum(&content));
+
+            let response = self
+                .builder_with_header(Method::PUT, url, canonicalized, headers)?
+                .body(content)
+                .send_adjust_error()?;
+
+            if let Some(crc64) = crc64 {
+                check_crc64(header_crc64(response.headers()), crc64)?;
+            }
+            Ok(response)
+        }
+
+        /// # 从 Read 流式上传文件
+        ///
+        /// 内容小于 `part_size` 时使用一次 PUT 请求上传，否则自动切换为分片上传，
+        /// 每个分片的大小为 `part_size`（100KB 到 5GB 之间），最多 10000 个分片，内存中最多只保留一个分片
+        ///
+        /// 返回文件的 etag
+        fn put_reader<R: Read>(
+            &self,
+            mut reader: R,
+            content_type: &str,
+            path: Path,
+            part_size: usize,
+        ) -> Result<String, FileError> {
+            check_part_size(part_size)?;
             let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
                 kind: FileErrorKind::NotFoundCanonicalizedResource,
             })?;
 
-            let content_length = content.len().to_string();
-            let headers = vec![
-                (CONTENT_LENGTH, header_from_content_length(&content_length)?),
-                (
+            let mut crc64 = self.crc64_enabled().then(Crc64::new);
+
+            let first = read_part(&mut reader, part_size)?;
+            if first.len() < part_size {
+                if let Some(crc64) = crc64.as_mut() {
+                    crc64.update(&first);
+                }
+                let mut headers = put_headers(first.len(), content_type, &PutObject::default())?;
+                if self.content_md5_enabled() {
+                    headers.push(content_md5_header(&first));
+                }
+                let response = self
+                    .builder_with_header(Method::PUT, url, canonicalized, headers)?
+                    .body(first)
+                    .send_adjust_error()?;
+                if let Some(crc64) = crc64 {
+                    check_crc64(header_crc64(response.headers()), crc64.get())?;
+                }
+                return response_etag(response.headers());
+            }
+
+            let upload_id = {
+                let (url, canonicalized) =
+                    std_with_query(url.clone(), canonicalized.clone(), "uploads");
+                let headers = [(
                     CONTENT_TYPE,
                     content_type.parse().map_err(|e| FileError {
                         kind: FileErrorKind::InvalidContentType(e),
                     })?,
-                ),
-            ];
-
-            let response = self
-                .builder_with_header(Method::PUT, url, canonicalized, headers)?
-                .body(content);
+                )];
+                let xml = self
+                    .builder_with_header(Method::POST, url, canonicalized, headers)?
+                    .send_adjust_error()?
+                    .text()?;
+                upload_id(&xml)?
+            };
+
+            let upload = || {
+                let mut etags = Vec::new();
+                let mut part = first;
+                while !part.is_empty() {
+                    check_parts_count(etags.len())?;
+                    let query = format!("partNumber={}&uploadId={upload_id}", etags.len() + 1);
+                    let (url, canonicalized) =
+                        std_with_query(url.clone(), canonicalized.clone(), &query);
+                    let mut headers = vec![(
+                        CONTENT_LENGTH,
+                        header_from_content_length(&part.len().to_string())?,
+                    )];
+                    if self.content_md5_enabled() {
+                        headers.push(content_md5_header(&part));
+                    }
+                    if let Some(crc64) = crc64.as_mut() {
+                        crc64.update(&part);
+                    }
+                    let response = self
+                        .builder_with_header(Method::PUT, url, canonicalized, headers)?
+                        .body(part)
+                        .send_adjust_error()?;
+                    etags.push(response_etag(response.headers())?);
+
+                    part = read_part(&mut reader, part_size)?;
+                }
 
-            response.send_adjust_error().map_err(FileError::from)
+                let body = complete_body(&etags);
+                let (url, canonicalized) = std_with_query(
+                    url.clone(),
+                    canonicalized.clone(),
+                    &format!("uploadId={upload_id}"),
+                );
+                let headers = [(
+                    CONTENT_LENGTH,
+                    header_from_content_length(&body.len().to_string())?,
+                )];
+                let response = self
+                    .builder_with_header(Method::POST, url, canonicalized, headers)?
+                    .body(body)
+                    .send_adjust_error()?;
+                if let Some(crc64) = crc64 {
+                    check_crc64(header_crc64(response.headers()), crc64.get())?;
+                }
+                response_etag(response.headers())
+            };
+
+            let result = upload();
+            if result.is_err() {
+                let (url, canonicalized) =
+                    std_with_query(url, canonicalized, &format!("uploadId={upload_id}"));
+                if let Ok(builder) = self.builder(Method::DELETE, url, canonicalized) {
+                    let _ = builder.send_adjust_error();
+                }
+            }
+            result
         }
 
         /// # 获取文件内容
@@ -898,11 +2083,174 @@ pub mod blocking {
                 range.into().into(),
             )];
 
-            Ok(self
+            let response = self
+                .builder_with_header(Method::GET, url, canonicalized, headers)?
+                .send_adjust_error()?;
+            let crc64 = self
+                .crc64_enabled()
+                .then(|| full_body_crc64(response.status(), response.headers()))
+                .flatten();
+
+            let content = response.bytes()?;
+            if crc64.is_some() {
+                check_crc64(crc64, Crc64::checksum(&content))?;
+            }
+
+            Ok(content.to_vec())
+        }
+
+        /// # 获取文件内容的 Reader
+        ///
+        /// 返回的 [`ObjectReader`] 实现了 std `Read`，不会将文件内容全部读入内存
+        ///
+        /// [`ObjectReader`]: crate::object::stream::blocking::ObjectReader
+        fn get_object_reader<Num, R>(&self, path: Path, range: R) -> Result<ObjectReader, FileError>
+        where
+            R: Into<ContentRange<Num>>,
+            ContentRange<Num>: Into<HeaderValue>,
+        {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+
+            let headers: Vec<(_, HeaderValue)> = vec![(
+                {
+                    #[allow(clippy::unwrap_used)]
+                    RANGE.parse().unwrap()
+                },
+                range.into().into(),
+            )];
+
+            let response = self
                 .builder_with_header(Method::GET, url, canonicalized, headers)?
+                .send_adjust_error()?;
+
+            Ok(ObjectReader::new(response))
+        }
+
+        /// # 获取 OSS 上文件的元信息
+        fn head_object(&self, path: Path) -> Result<ObjectMeta, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+
+            let response = self
+                .builder(Method::HEAD, url, canonicalized)?
+                .send_adjust_error()?;
+
+            Ok(ObjectMeta::from_headers(response.headers())?)
+        }
+
+        /// # 获取 OSS 上文件的部分元信息
+        fn get_object_meta(&self, path: Path) -> Result<ObjectMeta, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let (url, canonicalized) = std_with_query(url, canonicalized, OBJECT_META);
+
+            let response = self
+                .builder(Method::HEAD, url, canonicalized)?
+                .send_adjust_error()?;
+
+            Ok(ObjectMeta::from_headers(response.headers())?)
+        }
+
+        /// # 复制文件
+        ///
+        /// `path` 为目标文件的路径，源文件等参数在 [`CopyObject`] 中指定
+        fn copy_object(
+            &self,
+            options: &CopyObject<'_>,
+            path: Path,
+        ) -> Result<CopyObjectResult, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let headers = copy_headers(options, &canonicalized)?;
+
+            let xml = self
+                .builder_with_header(Method::PUT, url, canonicalized, headers)?
                 .send_adjust_error()?
-                .bytes()?
-                .to_vec())
+                .text()?;
+
+            parse_copy_result(&xml)
+        }
+
+        /// # 追加内容到 OSS 上的文件
+        ///
+        /// 需要自动维护追加位置时，可以使用 [`Appender`]
+        ///
+        /// [`Appender`]: crate::object::append::blocking::Appender
+        fn append_object(
+            &self,
+            content: Vec<u8>,
+            content_type: &str,
+            path: Path,
+            position: u64,
+        ) -> Result<AppendObjectResult, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let (url, canonicalized) =
+                std_with_query(url, canonicalized, &format!("append&position={position}"));
+
+            let headers = put_headers(content.len(), content_type, &PutObject::default())?;
+            let crc64 = (position == 0 && self.crc64_enabled()).then(|| Crc64::checksum(&content));
+
+            let response = self
+                .builder_with_header(Method::POST, url, canonicalized, headers)?
+                .body(content)
+                .send_adjust_error()?;
+
+            let result = parse_append_result(response.headers())?;
+            if let Some(crc64) = crc64 {
+                check_crc64(result.crc64(), crc64)?;
+            }
+            Ok(result)
+        }
+
+        /// # 获取文件的标签
+        fn get_object_tagging(&self, path: Path) -> Result<Tagging, FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+
+            let xml = self
+                .builder(Method::GET, url, canonicalized)?
+                .send_adjust_error()?
+                .text()?;
+
+            Ok(Tagging::from_xml(&xml)?)
+        }
+
+        /// # 设置文件的标签，会覆盖文件原有的全部标签
+        fn put_object_tagging(&self, path: Path, tagging: &Tagging) -> Result<(), FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+            let body = tagging.to_xml();
+            let headers = tagging_headers(&body, self.content_md5_enabled())?;
+
+            self.builder_with_header(Method::PUT, url, canonicalized, headers)?
+                .body(body)
+                .send_adjust_error()?;
+
+            Ok(())
+        }
+
+        /// # 删除文件的全部标签
+        fn delete_object_tagging(&self, path: Path) -> Result<(), FileError> {
+            let (url, canonicalized) = self.get_std_with_path(path).ok_or(FileError {
+                kind: FileErrorKind::NotFoundCanonicalizedResource,
+            })?;
+            let (url, canonicalized) = std_with_query(url, canonicalized, TAGGING);
+
+            self.builder(Method::DELETE, url, canonicalized)?
+                .send_adjust_error()?;
+
+            Ok(())
         }
 
         /// # 删除 OSS 上的文件
@@ -920,6 +2268,42 @@ pub mod blocking {
 
     impl<P, T: AlignBuilder + GetStdWithPath<P>> Files<P> for T {}
 
+    /// 读取一个分片，读到末尾时返回的内容会小于 `part_size`
+    fn read_part<R: Read>(reader: &mut R, part_size: usize) -> Result<Vec<u8>, FileError> {
+        let mut part = Vec::new();
+        reader
+            .take(part_size as u64)
+            .read_to_end(&mut part)
+            .map_err(|e| FileError {
+                kind: FileErrorKind::ReadBody(e),
+            })?;
+        Ok(part)
+    }
+
+    /// # 批量删除文件
+    ///
+    /// 每次请求最多删除 1000 个文件，超出时拆分为多次请求
+    pub(crate) fn delete_objects<B: AlignBuilder>(
+        builder: &B,
+        bucket: &BucketBase,
+        objects: Vec<ObjectIdentifier>,
+        quiet: bool,
+    ) -> Result<Vec<DeleteResult>, FileError> {
+        let mut results = Vec::with_capacity(objects.len());
+        for chunk in objects.chunks(MAX_DELETE_KEYS) {
+            let body = delete_body(chunk, quiet);
+            let (url, canonicalized) = delete_std(bucket);
+
+            let response = builder
+                .builder_with_header(Method::POST, url, canonicalized, delete_headers(&body)?)?
+                .body(body)
+                .send_adjust_error()?;
+
+            results.extend(parse_delete_result(&response.text()?, chunk.to_vec())?);
+        }
+        Ok(results)
+    }
+
     /// 对 Client 中的请求构建器进行抽象
     pub trait AlignBuilder {
         /// 根据具体的 API 接口参数，返回请求的构建器（不带 headers）
@@ -941,6 +2325,18 @@ pub mod blocking {
             resource: CanonicalizedResource,
             headers: H,
         ) -> Result<RequestBuilder, BuilderError>;
+
+        /// 是否开启了 CRC64 校验
+        #[inline]
+        fn crc64_enabled(&self) -> bool {
+            false
+        }
+
+        /// 是否自动计算 Content-MD5
+        #[inline]
+        fn content_md5_enabled(&self) -> bool {
+            false
+        }
     }
 
     /// # 对齐 Client, Bucket, ObjectList 等结构体的 trait
@@ -957,6 +2353,14 @@ pub mod blocking {
             self.client()
                 .builder_with_header(method, url, resource, headers)
         }
+
+        fn crc64_enabled(&self) -> bool {
+            self.client().crc64_enabled()
+        }
+
+        fn content_md5_enabled(&self) -> bool {
+            self.client().content_md5_enabled()
+        }
     }
 
     impl AlignBuilder for ObjectList<RcPointer> {
@@ -970,5 +2374,13 @@ pub mod blocking {
             self.client()
                 .builder_with_header(method, url, resource, headers)
         }
+
+        fn crc64_enabled(&self) -> bool {
+            self.client().crc64_enabled()
+        }
+
+        fn content_md5_enabled(&self) -> bool {
+            self.client().content_md5_enabled()
+        }
     }
 }
@@ -1,6 +1,6 @@
 use crate::file::{error_impl::FileErrorKind, Files};
 
-use super::header_from_content_length;
+use super::{check_crc64, full_body_crc64, header_from_content_length};
 
 mod tests_get_std {
     use reqwest::Url;
@@ -217,6 +217,7 @@ mod error {
 
     use crate::{
         builder::{BuilderError, BuilderErrorKind},
+        decode::InnerListError,
         file::{error_impl::FileErrorKind, FileError},
         tests::reqwest_error,
     };
@@ -340,6 +341,18 @@ mod error {
         );
     }
 
+    #[test]
+    fn list_error() {
+        fn assert_send<T: Send + Sync>(_: &T) {}
+
+        let err = FileError::from(InnerListError::from_custom());
+        assert_send(&err);
+        assert_eq!(format!("{err}"), "list failed: custom");
+        let source = err.source().unwrap();
+        assert_eq!(format!("{source}"), "custom");
+        assert_eq!(format!("{}", source.source().unwrap()), "custom");
+    }
+
     #[test]
     #[cfg(feature = "put_file")]
     fn into_io_error_file() {
@@ -421,6 +434,605 @@ async fn test_delete_object_error() {
     ));
 }
 
+#[tokio::test]
+async fn test_head_object() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct MyMiddleware {}
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(request.method(), "HEAD");
+            let response = http::Response::builder()
+                .status(200)
+                .header("content-length", "100")
+                .header("x-oss-meta-foo", "bar");
+            let response = match request.url().query() {
+                Some("objectMeta") => response.header("x-oss-last-access-time", "foo"),
+                _ => {
+                    assert_eq!(
+                        request.url().as_str(),
+                        "https://bar.oss-cn-qingdao.aliyuncs.com/abc.txt"
+                    );
+                    response
+                }
+            };
+            Ok(Response::from(response.body("").unwrap()))
+        }
+    }
+
+    let client = ClientArc::test_init().middleware(Arc::new(MyMiddleware {}));
+    let meta = client.head_object("abc.txt").await.unwrap();
+    assert_eq!(meta.content_length(), 100);
+    assert_eq!(meta.get_user_meta("foo"), Some("bar"));
+
+    let err = client.get_object_meta("abc.txt").await.unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::Meta(_)));
+    assert_eq!(err.to_string(), "invalid object meta");
+
+    let err = client.head_object("aaa/").await.unwrap_err();
+    assert!(matches!(
+        err.kind,
+        FileErrorKind::NotFoundCanonicalizedResource
+    ));
+}
+
+#[test]
+fn test_std_with_query() {
+    use super::std_with_query;
+    use crate::types::CanonicalizedResource;
+
+    let (url, resource) = std_with_query(
+        "https://bar.oss-cn-qingdao.aliyuncs.com/abc.txt"
+            .parse()
+            .unwrap(),
+        CanonicalizedResource::new("/bar/abc.txt"),
+        "objectMeta",
+    );
+    assert_eq!(
+        url.as_str(),
+        "https://bar.oss-cn-qingdao.aliyuncs.com/abc.txt?objectMeta"
+    );
+    assert_eq!(
+        resource,
+        CanonicalizedResource::new("/bar/abc.txt?objectMeta")
+    );
+}
+
+#[test]
+fn test_parse_copy_result() {
+    use super::parse_copy_result;
+
+    let res = parse_copy_result(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<CopyObjectResult>
+  <ETag>"F2064A169EE92E9775EE5324D0B1****"</ETag>
+  <LastModified>2023-02-24T09:41:56.000Z</LastModified>
+</CopyObjectResult>"#,
+    )
+    .unwrap();
+    assert_eq!(res.etag(), "\"F2064A169EE92E9775EE5324D0B1****\"");
+    assert_eq!(
+        res.last_modified().to_rfc3339(),
+        "2023-02-24T09:41:56+00:00"
+    );
+
+    let err = parse_copy_result("<CopyObjectResult></CopyObjectResult>").unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::EtagNotFound));
+
+    let err = parse_copy_result("<ETag>a</ETag><LastModified>b</LastModified>").unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::InvalidLastModified(_)));
+
+    let res = parse_copy_result(
+        "<CopyObjectResult><ETag>&quot;abc&quot;</ETag><LastModified>2023-02-24T09:41:56.000Z</LastModified></CopyObjectResult>",
+    )
+    .unwrap();
+    assert_eq!(res.etag(), "\"abc\"");
+
+    let err = parse_copy_result("<CopyObjectResult></ETag><ETag>a</CopyObjectResult>").unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::Decode(_)));
+}
+
+#[tokio::test]
+async fn test_copy_object() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::object::CopyObject;
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct MyMiddleware {}
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(request.method(), "PUT");
+            assert_eq!(
+                request.url().as_str(),
+                "https://bar.oss-cn-qingdao.aliyuncs.com/abc_copy.txt"
+            );
+            assert_eq!(request.headers()["x-oss-copy-source"], "/other/abc.txt");
+            let response = http::Response::builder()
+                .status(200)
+                .body("<CopyObjectResult><ETag>\"abc\"</ETag><LastModified>2023-02-24T09:41:56.000Z</LastModified></CopyObjectResult>")
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let client = ClientArc::test_init().middleware(Arc::new(MyMiddleware {}));
+    let options = CopyObject {
+        copy_source: "/other/abc.txt",
+        ..Default::default()
+    };
+    let res = client.copy_object(&options, "abc_copy.txt").await.unwrap();
+    assert_eq!(res.etag(), "\"abc\"");
+
+    let options = CopyObject {
+        copy_source: "/other/abc.txt",
+        copy_source_if_match: Some("abc\n"),
+        ..Default::default()
+    };
+    let err = client
+        .copy_object(&options, "abc_copy.txt")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::InvalidHeader(_)));
+}
+
+#[tokio::test]
+async fn test_put_object_with() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::object::{Encryption, ObjectAcl, PutObject, StorageClass};
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct MyMiddleware {}
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(request.method(), "PUT");
+            let headers = request.headers();
+            assert_eq!(headers["content-length"], "3");
+            assert_eq!(headers["content-type"], "text/plain");
+            assert_eq!(headers["x-oss-forbid-overwrite"], "true");
+            assert_eq!(headers["x-oss-server-side-encryption"], "KMS");
+            assert_eq!(headers["x-oss-server-side-data-encryption"], "SM4");
+            assert_eq!(headers["x-oss-server-side-encryption-key-id"], "key1");
+            assert_eq!(headers["x-oss-object-acl"], "private");
+            assert_eq!(headers["x-oss-storage-class"], "Archive");
+            assert_eq!(headers["x-oss-tagging"], "a=1&b=2");
+            assert_eq!(headers["cache-control"], "no-cache");
+            assert_eq!(headers["content-disposition"], "attachment");
+            assert_eq!(headers["content-encoding"], "gzip");
+            assert_eq!(headers["expires"], "Fri, 28 Feb 2012 05:38:42 GMT");
+            assert_eq!(headers["x-oss-meta-author"], "tu6ge");
+            let response = http::Response::builder().status(200).body("").unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let client = ClientArc::test_init().middleware(Arc::new(MyMiddleware {}));
+    let options = PutObject {
+        forbid_overwrite: true,
+        server_side_encryption: Some(Encryption::Kms),
+        server_side_data_encryption: Some(Encryption::Sm4),
+        server_side_encryption_key_id: Some("key1"),
+        object_acl: ObjectAcl::Private,
+        storage_class: Some(StorageClass::ARCHIVE),
+        tagging: Some("a=1&b=2"),
+        cache_control: Some("no-cache"),
+        content_disposition: Some("attachment"),
+        content_encoding: Some("gzip"),
+        expires: Some("Fri, 28 Feb 2012 05:38:42 GMT"),
+        user_meta: vec![("author", "tu6ge")],
+    };
+    let res = client
+        .put_object_with(b"abc".to_vec(), "text/plain", "abc.txt", &options)
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+
+    let options = PutObject {
+        user_meta: vec![("a b", "c")],
+        ..Default::default()
+    };
+    let err = client
+        .put_object_with(b"abc".to_vec(), "text/plain", "abc.txt", &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::InvalidHeader(_)));
+}
+
+#[tokio::test]
+async fn test_put_reader() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::errors::OssService;
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::StatusCode;
+    use reqwest::{Request, Response};
+    use std::sync::{Arc, Mutex};
+
+    /// 记录请求，`fail_part` 分片上传失败
+    #[derive(Debug, Default)]
+    struct MyMiddleware {
+        requests: Mutex<Vec<String>>,
+        fail_part: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let query = request.url().query().unwrap_or_default().to_owned();
+            let len = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|body| body.len())
+                .unwrap_or_default();
+            self.requests
+                .lock()
+                .unwrap()
+                .push(format!("{} {query} {len}", request.method()));
+
+            if self.fail_part.map_or(false, |part| query.starts_with(part)) {
+                let body = "<Error><Code>InternalError</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                let url = request.url().clone();
+                return Err(OssService::new2(
+                    body.to_owned(),
+                    &StatusCode::INTERNAL_SERVER_ERROR,
+                    url,
+                )
+                .into());
+            }
+
+            let body = if query == "uploads" {
+                "<InitiateMultipartUploadResult><UploadId>id</UploadId></InitiateMultipartUploadResult>"
+            } else {
+                ""
+            };
+            let response = http::Response::builder()
+                .status(200)
+                .header("ETag", format!("\"{}\"", query.len()))
+                .body(body)
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let middleware = Arc::new(MyMiddleware::default());
+    let client = ClientArc::test_init().middleware(middleware.clone());
+    let etag = client
+        .put_reader(&b"abcde"[..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap();
+    assert_eq!(etag, "\"0\"");
+    assert_eq!(*middleware.requests.lock().unwrap(), ["PUT  5"]);
+
+    let middleware = Arc::new(MyMiddleware::default());
+    let client = ClientArc::test_init().middleware(middleware.clone());
+    let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+        Ok(Bytes::from_static(b"0123456")),
+        Ok(Bytes::from_static(b"789abcdefghijklmno")),
+    ];
+    client
+        .put_stream(futures::stream::iter(chunks), "text/plain", "abc.txt", 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        *middleware.requests.lock().unwrap(),
+        [
+            "POST uploads 0",
+            "PUT partNumber=1&uploadId=id 10",
+            "PUT partNumber=2&uploadId=id 10",
+            "PUT partNumber=3&uploadId=id 5",
+            "POST uploadId=id 219",
+        ]
+    );
+
+    // 上传失败时取消分片上传
+    let middleware = Arc::new(MyMiddleware {
+        fail_part: Some("partNumber=2"),
+        ..Default::default()
+    });
+    let client = ClientArc::test_init().middleware(middleware.clone());
+    client
+        .put_reader(&[1u8; 25][..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap_err();
+    {
+        let requests = middleware.requests.lock().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[3], "DELETE uploadId=id 0");
+    }
+
+    // 分片尺寸超出范围时，不发送请求
+    let middleware = Arc::new(MyMiddleware::default());
+    let client = ClientArc::test_init().middleware(middleware.clone());
+    for part_size in [0, 9, 21] {
+        let err = client
+            .put_reader(&b"abcde"[..], "text/plain", "abc.txt", part_size)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.kind, FileErrorKind::InvalidPartSize));
+    }
+    assert!(middleware.requests.lock().unwrap().is_empty());
+
+    // 分片数量超出上限时，取消分片上传
+    let err = client
+        .put_reader(&[1u8; 101][..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind, FileErrorKind::OverflowMaxPartsCount));
+    let requests = middleware.requests.lock().unwrap();
+    assert_eq!(requests.len(), 12);
+    assert_eq!(requests[10], "PUT partNumber=10&uploadId=id 10");
+    assert_eq!(requests[11], "DELETE uploadId=id 0");
+}
+
+#[test]
+fn test_full_body_crc64() {
+    use http::{HeaderMap, StatusCode};
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-oss-hash-crc64ecma", "123".parse().unwrap());
+    assert_eq!(full_body_crc64(StatusCode::OK, &headers), Some(123));
+    assert_eq!(full_body_crc64(StatusCode::PARTIAL_CONTENT, &headers), None);
+
+    headers.insert("content-range", "bytes 0-9/10".parse().unwrap());
+    assert_eq!(
+        full_body_crc64(StatusCode::PARTIAL_CONTENT, &headers),
+        Some(123)
+    );
+    headers.insert("content-range", "bytes 1-9/10".parse().unwrap());
+    assert_eq!(full_body_crc64(StatusCode::PARTIAL_CONTENT, &headers), None);
+    headers.insert("content-range", "bytes 0-8/10".parse().unwrap());
+    assert_eq!(full_body_crc64(StatusCode::PARTIAL_CONTENT, &headers), None);
+
+    assert!(check_crc64(None, 1).is_ok());
+    assert!(check_crc64(Some(1), 1).is_ok());
+    let err = check_crc64(Some(1), 2).unwrap_err();
+    assert!(err.is_crc64_mismatch());
+    assert_eq!(err.to_string(), "crc64 mismatch, expected 1, actual 2");
+}
+
+#[tokio::test]
+async fn test_crc64() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::crc64::Crc64;
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::{Arc, Mutex};
+
+    /// 返回请求体的 CRC64，`corrupt` 为 true 时返回错误的值
+    #[derive(Debug, Default)]
+    struct MyMiddleware {
+        corrupt: bool,
+        /// 分片上传时，累计的请求体
+        parts: Mutex<Vec<u8>>,
+    }
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let query = request.url().query().unwrap_or_default().to_owned();
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default()
+                .to_vec();
+
+            let (content, body) = if request.method() == "GET" {
+                (b"0123456789".to_vec(), "0123456789")
+            } else if query == "uploads" {
+                (
+                    Vec::new(),
+                    "<InitiateMultipartUploadResult><UploadId>id</UploadId></InitiateMultipartUploadResult>",
+                )
+            } else if query.starts_with("partNumber") {
+                self.parts.lock().unwrap().extend(&body);
+                (body, "")
+            } else if query.starts_with("uploadId") {
+                (self.parts.lock().unwrap().clone(), "")
+            } else {
+                (body, "")
+            };
+
+            let crc64 = Crc64::checksum(&content) + u64::from(self.corrupt);
+            let response = http::Response::builder()
+                .status(200)
+                .header("ETag", "\"a\"")
+                .header("x-oss-hash-crc64ecma", crc64)
+                .header("x-oss-next-append-position", content.len())
+                .body(body)
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let mut client = ClientArc::test_init().middleware(Arc::new(MyMiddleware::default()));
+    client.enable_crc64(true);
+    client
+        .put_content_base(b"abc".to_vec(), "text/plain", "abc.txt")
+        .await
+        .unwrap();
+    client
+        .put_reader(&[1u8; 25][..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap();
+    let content = client.get_object("abc.txt", ..).await.unwrap();
+    assert_eq!(content, b"0123456789");
+
+    let corrupt = Arc::new(MyMiddleware {
+        corrupt: true,
+        ..Default::default()
+    });
+    let mut client = ClientArc::test_init().middleware(corrupt.clone());
+    // 未开启时不做校验
+    client
+        .put_content_base(b"abc".to_vec(), "text/plain", "abc.txt")
+        .await
+        .unwrap();
+
+    client.enable_crc64(true);
+    let err = client
+        .put_content_base(b"abc".to_vec(), "text/plain", "abc.txt")
+        .await
+        .unwrap_err();
+    assert!(err.is_crc64_mismatch());
+    let err = client.get_object("abc.txt", ..).await.unwrap_err();
+    assert!(err.is_crc64_mismatch());
+    let err = client
+        .put_reader(&[1u8; 25][..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap_err();
+    assert!(err.is_crc64_mismatch());
+    let err = client
+        .append_object(b"abc".to_vec(), "text/plain", "abc.txt", 0)
+        .await
+        .unwrap_err();
+    assert!(err.is_crc64_mismatch());
+}
+
+#[tokio::test]
+async fn test_content_md5() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::types::ContentMd5;
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::{Arc, Mutex};
+
+    /// 记录每个请求的 Content-MD5 是否与请求体一致
+    #[derive(Debug, Default)]
+    struct MyMiddleware {
+        md5: Mutex<Vec<Option<bool>>>,
+    }
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default();
+            let md5 = request
+                .headers()
+                .get("content-md5")
+                .map(|value| value.to_str().unwrap() == ContentMd5::from_body(body).as_ref());
+            self.md5.lock().unwrap().push(md5);
+
+            let body = if request.url().query() == Some("uploads") {
+                "<InitiateMultipartUploadResult><UploadId>id</UploadId></InitiateMultipartUploadResult>"
+            } else {
+                ""
+            };
+            let response = http::Response::builder()
+                .status(200)
+                .header("ETag", "\"a\"")
+                .body(body)
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let middleware = Arc::new(MyMiddleware::default());
+    let mut client = ClientArc::test_init().middleware(middleware.clone());
+    client
+        .put_content_base(b"abc".to_vec(), "text/plain", "abc.txt")
+        .await
+        .unwrap();
+    assert_eq!(*middleware.md5.lock().unwrap(), [None]);
+
+    middleware.md5.lock().unwrap().clear();
+    client.enable_content_md5(true);
+    client
+        .put_content_base(b"abc".to_vec(), "text/plain", "abc.txt")
+        .await
+        .unwrap();
+    client
+        .put_reader(&[1u8; 15][..], "text/plain", "abc.txt", 10)
+        .await
+        .unwrap();
+    assert_eq!(
+        *middleware.md5.lock().unwrap(),
+        [Some(true), None, Some(true), Some(true), None]
+    );
+}
+
+#[tokio::test]
+async fn test_object_tagging() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::client::ClientArc;
+    use crate::object::Tagging;
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::{Arc, Mutex};
+
+    /// 模拟文件的标签
+    #[derive(Debug, Default)]
+    struct MyMiddleware {
+        tagging: Mutex<String>,
+    }
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(
+                request.url().as_str(),
+                "https://bar.oss-cn-qingdao.aliyuncs.com/abc.txt?tagging"
+            );
+            let mut tagging = self.tagging.lock().unwrap();
+            let body = match request.method().as_str() {
+                "GET" => tagging.clone(),
+                "PUT" => {
+                    let body = request.body().unwrap().as_bytes().unwrap();
+                    assert_eq!(
+                        request.headers()["content-length"],
+                        body.len().to_string().as_str()
+                    );
+                    *tagging = String::from_utf8(body.to_vec()).unwrap();
+                    String::new()
+                }
+                "DELETE" => {
+                    *tagging = "<Tagging><TagSet></TagSet></Tagging>".to_owned();
+                    String::new()
+                }
+                _ => panic!("error"),
+            };
+            let response = http::Response::builder().status(200).body(body).unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let client = ClientArc::test_init().middleware(Arc::new(MyMiddleware::default()));
+    let tagging = Tagging::try_from([("a", "1"), ("b", "x&y")]).unwrap();
+    client
+        .put_object_tagging("abc.txt", &tagging)
+        .await
+        .unwrap();
+    let res = client.get_object_tagging("abc.txt").await.unwrap();
+    assert_eq!(res, tagging);
+
+    client.delete_object_tagging("abc.txt").await.unwrap();
+    let res = client.get_object_tagging("abc.txt").await.unwrap();
+    assert!(res.is_empty());
+}
+
 #[cfg(feature = "blocking")]
 mod blocking_files_trait {
     use crate::{
@@ -455,6 +1067,88 @@ mod blocking_files_trait {
         ));
     }
 
+    #[test]
+    fn test_head_object_error() {
+        let client = ClientRc::test_init();
+        let err = client.head_object("aaa/").unwrap_err();
+        assert!(matches!(
+            err.kind,
+            FileErrorKind::NotFoundCanonicalizedResource
+        ));
+        let err = client.get_object_meta("aaa/").unwrap_err();
+        assert!(matches!(
+            err.kind,
+            FileErrorKind::NotFoundCanonicalizedResource
+        ));
+    }
+
+    #[test]
+    fn test_put_reader() {
+        use std::{cell::RefCell, rc::Rc};
+
+        use reqwest::blocking::{Request, Response};
+
+        use crate::blocking::builder::Middleware;
+        use crate::builder::BuilderError;
+
+        #[derive(Debug, Default)]
+        struct MyMiddleware {
+            requests: RefCell<Vec<String>>,
+        }
+
+        impl Middleware for MyMiddleware {
+            fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+                let query = request.url().query().unwrap_or_default().to_owned();
+                self.requests
+                    .borrow_mut()
+                    .push(format!("{} {query}", request.method()));
+
+                let body = if query == "uploads" {
+                    "<InitiateMultipartUploadResult><UploadId>id</UploadId></InitiateMultipartUploadResult>"
+                } else {
+                    ""
+                };
+                let response = http::Response::builder()
+                    .status(200)
+                    .header("ETag", "\"abc\"")
+                    .body(body)
+                    .unwrap();
+                Ok(Response::from(response))
+            }
+        }
+
+        let middleware = Rc::new(MyMiddleware::default());
+        let client = ClientRc::test_init().middleware(middleware.clone());
+        let etag = client
+            .put_reader(&[1u8; 15][..], "text/plain", "abc.txt", 10)
+            .unwrap();
+        assert_eq!(etag, "\"abc\"");
+        assert_eq!(
+            *middleware.requests.borrow(),
+            [
+                "POST uploads",
+                "PUT partNumber=1&uploadId=id",
+                "PUT partNumber=2&uploadId=id",
+                "POST uploadId=id",
+            ]
+        );
+
+        let err = client
+            .put_reader(&[1u8; 15][..], "text/plain", "abc.txt", 21)
+            .unwrap_err();
+        assert!(matches!(err.kind, FileErrorKind::InvalidPartSize));
+
+        middleware.requests.borrow_mut().clear();
+        let err = client
+            .put_reader(&[1u8; 101][..], "text/plain", "abc.txt", 10)
+            .unwrap_err();
+        assert!(matches!(err.kind, FileErrorKind::OverflowMaxPartsCount));
+        assert_eq!(
+            middleware.requests.borrow().last().unwrap(),
+            "DELETE uploadId=id"
+        );
+    }
+
     #[test]
     fn test_delete_object_error() {
         let client = ClientRc::test_init();
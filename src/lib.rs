@@ -258,6 +258,12 @@ pub mod config;
 #[allow(dead_code)]
 mod consts;
 
+#[cfg(feature = "core")]
+pub mod crc64;
+
+#[cfg(feature = "core")]
+pub mod credentials;
+
 #[cfg(feature = "decode")]
 pub mod decode;
 
@@ -56,6 +56,7 @@
 //! ```
 //! [issue 12]: https://github.com/tu6ge/oss-rs/issues/12
 
+use crate::auth::{v4, SignUrlOptions};
 use crate::bucket::Bucket;
 #[cfg(feature = "blocking")]
 use crate::builder::RcPointer;
@@ -77,10 +78,9 @@ use crate::types::{
     CanonicalizedResource, Query, QueryKey, QueryValue, CONTINUATION_TOKEN,
 };
 use crate::{BucketName, Client, EndPoint, KeyId, KeySecret};
-use async_stream::try_stream;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use futures_core::stream::Stream;
-use http::Method;
+use http::{header::CONTENT_TYPE, HeaderName, HeaderValue, Method};
 use oss_derive::oss_gen_rc;
 use url::Url;
 
@@ -96,6 +96,29 @@ use std::{
 
 pub mod content;
 pub use content::Content;
+pub mod append;
+pub use append::{AppendObjectResult, Appender};
+mod checkpoint;
+pub mod delete;
+pub use delete::{DeleteResult, ObjectIdentifier};
+pub mod prefix;
+pub use prefix::{PrefixFailure, PrefixOptions, PrefixProgress, PrefixSummary};
+pub mod download;
+pub use download::{DownloadError, DownloadOptions};
+pub mod resumable;
+pub use resumable::{ResumableError, ResumableUpload};
+pub mod meta;
+pub use meta::ObjectMeta;
+pub mod multipart;
+pub use multipart::{
+    InvalidMultipart, ListUploadsQuery, MultipartUpload, MultipartUploadList, PartInfo, PartList,
+};
+pub mod seekable;
+pub use seekable::SeekableReader;
+pub mod stream;
+pub use stream::ObjectStream;
+pub mod tagging;
+pub use tagging::{InvalidTagging, Tagging};
 
 #[cfg(test)]
 mod test;
@@ -356,35 +379,41 @@ impl ObjectList<ArcPointer> {
             None => Err(ExtractListError {
                 kind: ExtractListErrorKind::NoMoreFile,
             }),
-            Some(query) => {
-                let mut url = self.bucket.to_url();
-                url.set_oss_query(&query);
+            Some(query) => self.list_with_query(query).await,
+        }
+    }
 
-                let canonicalized = CanonicalizedResource::from_bucket_query(&self.bucket, &query);
+    /// 使用当前的 bucket 和 client，按照查询条件获取文件列表
+    async fn list_with_query(
+        &self,
+        query: Query,
+    ) -> Result<ObjectList<ArcPointer>, ExtractListError> {
+        let mut url = self.bucket.to_url();
+        url.set_oss_query(&query);
 
-                let response = self
-                    .builder(Method::GET, url, canonicalized)?
-                    .send_adjust_error()
-                    .await?;
+        let canonicalized = CanonicalizedResource::from_bucket_query(&self.bucket, &query);
 
-                let mut list = ObjectList::<ArcPointer> {
-                    client: self.client(),
-                    bucket: self.bucket.clone(),
-                    object_list: Vec::with_capacity(query.get_max_keys()),
-                    ..Default::default()
-                };
+        let response = self
+            .builder(Method::GET, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+
+        let mut list = ObjectList::<ArcPointer> {
+            client: self.client(),
+            bucket: self.bucket.clone(),
+            object_list: Vec::with_capacity(query.get_max_keys()),
+            ..Default::default()
+        };
 
-                list.decode(&response.text().await?, Self::init_object)?;
+        list.decode(&response.text().await?, Self::init_object)?;
 
-                list.set_search_query(query);
-                Ok(list)
-            }
-        }
+        list.set_search_query(query);
+        Ok(list)
     }
 
     /// # 将 object_list 转化为 stream, 返回第二页，第三页... 的内容
     ///
-    /// *不够完善，最后一次迭代返回的是 `Some(Err(OssError::WithoutMore))`，而不是 `None`*
+    /// 没有下一页时，stream 结束
     ///
     /// ## 用法
     ///
@@ -414,10 +443,14 @@ impl ObjectList<ArcPointer> {
     /// # }
     /// ```
     pub fn into_stream(self) -> impl Stream<Item = Result<Self, ExtractListError>> {
-        try_stream! {
-            let result = self.get_next_list().await?;
-            yield result;
-        }
+        // ExtractListError 不是 Send，不能在 await 之间保存，
+        // 用 unfold 代替 try_stream，返回的 stream 才能在多线程中使用
+        let query = self.next_query();
+        futures::stream::unfold((self, query), |(list, query)| async move {
+            let res = list.list_with_query(query?).await;
+            let query = res.as_ref().ok().and_then(ObjectList::next_query);
+            Some((res, (list, query)))
+        })
     }
 }
 
@@ -654,6 +687,23 @@ impl<T: PointerFamily> Object<T> {
         )
     }
 
+    /// 使用 HeadObject 等接口返回的元信息，更新当前的 Object
+    pub fn set_meta(&mut self, meta: &ObjectMeta) {
+        if let Some(last_modified) = meta.last_modified() {
+            self.last_modified = *last_modified;
+        }
+        if let Some(etag) = meta.etag() {
+            self.etag = etag.to_owned();
+        }
+        if let Some(object_type) = meta.object_type() {
+            self._type = object_type.to_owned();
+        }
+        if let Some(storage_class) = meta.storage_class() {
+            self.storage_class = *storage_class;
+        }
+        self.size = meta.content_length();
+    }
+
     /// 读取 文件路径
     pub fn path(&self) -> ObjectPath {
         self.base.path()
@@ -718,6 +768,17 @@ impl Object<ArcPointer> {
     pub fn to_sign_url(&self, key: &KeyId, secret: &KeySecret, expires: i64) -> Url {
         self.base.to_sign_url(key, secret, expires)
     }
+
+    /// 带签名的 Url 链接，可自定义请求方式，header 及子资源
+    pub fn to_sign_url_with(
+        &self,
+        key: &KeyId,
+        secret: &KeySecret,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> Url {
+        self.base.to_sign_url_with(key, secret, options, expires)
+    }
 }
 
 /// Object 结构体的构建器
@@ -1488,40 +1549,177 @@ impl PartialEq<ObjectBase<ArcPointer>> for Object<ArcPointer> {
     }
 }
 
-/// 未来计划支持的功能
-#[derive(Default)]
-#[doc(hidden)]
+/// # 上传文件的参数
+///
+/// ```
+/// use aliyun_oss_client::object::{Encryption, ObjectAcl, PutObject};
+///
+/// let options = PutObject {
+///     forbid_overwrite: true,
+///     server_side_encryption: Some(Encryption::Aes256),
+///     object_acl: ObjectAcl::Private,
+///     cache_control: Some("no-cache"),
+///     user_meta: vec![("author", "tu6ge")],
+///     ..Default::default()
+/// };
+/// ```
+/// [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/putobject)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct PutObject<'a> {
+    /// 文件已存在时，禁止覆盖
     pub forbid_overwrite: bool,
+    /// 服务端加密方式
     pub server_side_encryption: Option<Encryption>,
+    /// KMS 加密时使用的数据加密算法，仅支持 SM4
     pub server_side_data_encryption: Option<Encryption>,
+    /// KMS 加密时使用的密钥 id
     pub server_side_encryption_key_id: Option<&'a str>,
+    /// 文件的访问权限
     pub object_acl: ObjectAcl,
-    pub storage_class: StorageClass,
+    /// 文件的存储类型，不设置时为 Standard
+    pub storage_class: Option<StorageClass>,
+    /// 文件的标签，如 `a=1&b=2`
     pub tagging: Option<&'a str>,
+    /// 下载时的缓存行为，如 `no-cache`
+    pub cache_control: Option<&'a str>,
+    /// 下载时的文件名，如 `attachment; filename="abc.txt"`
+    pub content_disposition: Option<&'a str>,
+    /// 文件的编码方式，如 `gzip`
+    pub content_encoding: Option<&'a str>,
+    /// 缓存的过期时间，格式为 GMT 时间
+    pub expires: Option<&'a str>,
+    /// 自定义元信息，key 不包含 `x-oss-meta-` 前缀
+    pub user_meta: Vec<(&'a str, &'a str)>,
+}
+
+impl PutObject<'_> {
+    /// 转化为请求的 headers
+    pub(crate) fn headers(&self) -> Result<Vec<(HeaderName, HeaderValue)>, http::Error> {
+        let mut list = Vec::new();
+        push_headers(
+            &mut list,
+            [
+                (
+                    "x-oss-forbid-overwrite",
+                    self.forbid_overwrite.then_some("true"),
+                ),
+                (
+                    "x-oss-server-side-encryption",
+                    self.server_side_encryption.as_ref().map(Encryption::as_str),
+                ),
+                (
+                    "x-oss-server-side-data-encryption",
+                    self.server_side_data_encryption
+                        .as_ref()
+                        .map(Encryption::as_str),
+                ),
+                (
+                    "x-oss-server-side-encryption-key-id",
+                    self.server_side_encryption_key_id,
+                ),
+                ("x-oss-object-acl", self.object_acl.header_value()),
+                (
+                    "x-oss-storage-class",
+                    self.storage_class.as_ref().map(StorageClass::as_str),
+                ),
+                ("x-oss-tagging", self.tagging),
+                ("cache-control", self.cache_control),
+                ("content-disposition", self.content_disposition),
+                ("content-encoding", self.content_encoding),
+                ("expires", self.expires),
+            ],
+        )?;
+        push_user_meta(&mut list, &self.user_meta)?;
+
+        Ok(list)
+    }
+}
+
+/// 添加有值的 headers
+fn push_headers<const N: usize>(
+    list: &mut Vec<(HeaderName, HeaderValue)>,
+    items: [(&'static str, Option<&str>); N],
+) -> Result<(), http::Error> {
+    for (name, value) in items {
+        if let Some(value) = value {
+            list.push((HeaderName::from_static(name), HeaderValue::from_str(value)?));
+        }
+    }
+    Ok(())
+}
+
+/// 添加 `x-oss-meta-*` 自定义元信息
+fn push_user_meta(
+    list: &mut Vec<(HeaderName, HeaderValue)>,
+    meta: &[(&str, &str)],
+) -> Result<(), http::Error> {
+    for (key, value) in meta {
+        let name = format!("x-oss-meta-{key}");
+        list.push((
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        ));
+    }
+    Ok(())
 }
 
-/// 未来计划支持的功能
-#[derive(Default)]
-#[doc(hidden)]
+/// 服务端加密方式
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Encryption {
+    /// AES256
     #[default]
     Aes256,
+    /// KMS
     Kms,
+    /// SM4
     Sm4,
 }
 
-/// 未来计划支持的功能
-#[derive(Default)]
-#[doc(hidden)]
+impl Encryption {
+    /// 转化为 header 中使用的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Aes256 => "AES256",
+            Self::Kms => "KMS",
+            Self::Sm4 => "SM4",
+        }
+    }
+}
+
+/// 文件的访问权限
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ObjectAcl {
+    /// 继承 bucket 的访问权限
     #[default]
     Default,
+    /// 私有
     Private,
+    /// 公共读
     PublicRead,
+    /// 公共读写
     PublicReadWrite,
 }
 
+impl ObjectAcl {
+    /// 转化为 header 中使用的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Private => "private",
+            Self::PublicRead => "public-read",
+            Self::PublicReadWrite => "public-read-write",
+        }
+    }
+
+    /// 为 `Default` 时不需要发送 header
+    fn header_value(&self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            _ => Some(self.as_str()),
+        }
+    }
+}
+
 /// 存储类型
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -1541,6 +1739,8 @@ enum StorageClassKind {
     Archive,
     /// ColdArchive
     ColdArchive,
+    /// DeepColdArchive
+    DeepColdArchive,
 }
 
 impl StorageClass {
@@ -1560,6 +1760,10 @@ impl StorageClass {
     pub const COLD_ARCHIVE: Self = Self {
         kind: StorageClassKind::ColdArchive,
     };
+    /// DeepColdArchive
+    pub const DEEP_COLD_ARCHIVE: Self = Self {
+        kind: StorageClassKind::DeepColdArchive,
+    };
 
     /// init StorageClass
     pub fn new(s: &str) -> Option<StorageClass> {
@@ -1570,36 +1774,309 @@ impl StorageClass {
             'i' | 'I' => StorageClassKind::IA,
             's' | 'S' => StorageClassKind::Standard,
             'c' | 'C' => StorageClassKind::ColdArchive,
+            'd' | 'D' => StorageClassKind::DeepColdArchive,
             _ => return None,
         };
         Some(Self { kind })
     }
+
+    /// 转化为 header 中使用的值
+    pub fn as_str(&self) -> &'static str {
+        match self.kind {
+            StorageClassKind::Standard => "Standard",
+            StorageClassKind::IA => "IA",
+            StorageClassKind::Archive => "Archive",
+            StorageClassKind::ColdArchive => "ColdArchive",
+            StorageClassKind::DeepColdArchive => "DeepColdArchive",
+        }
+    }
 }
 
-/// 未来计划支持的功能
-#[derive(Default)]
-#[doc(hidden)]
+/// # 复制文件的参数
+///
+/// 源文件 `copy_source` 可以是同一 bucket 下的文件路径，如 `dir/file.txt`，
+/// 也可以是 `/bucket/dir/file.txt` 的形式，用于跨 bucket 复制（需在同一地域）
+///
+/// ```
+/// use aliyun_oss_client::object::{CopyDirective, CopyObject, StorageClass};
+///
+/// let options = CopyObject {
+///     copy_source: "/other-bucket/abc.txt",
+///     metadata_directive: CopyDirective::Replace,
+///     user_meta: vec![("author", "tu6ge")],
+///     storage_class: Some(StorageClass::IA),
+///     ..Default::default()
+/// };
+/// ```
+/// [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/copyobject)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct CopyObject<'a> {
+    /// 目标文件已存在时，禁止覆盖
     pub forbid_overwrite: bool,
+    /// 源文件
     pub copy_source: &'a str,
+    /// 源文件的 etag 与之相等时，才会复制
     pub copy_source_if_match: Option<&'a str>,
+    /// 源文件的 etag 与之不相等时，才会复制
     pub copy_source_if_none_match: Option<&'a str>,
+    /// 源文件在该时间之后未被修改时，才会复制，格式为 GMT 时间
     pub copy_source_if_unmodified_since: Option<&'a str>,
+    /// 源文件在该时间之后被修改过时，才会复制，格式为 GMT 时间
     pub copy_source_if_modified_since: Option<&'a str>,
+    /// 元信息的复制方式
     pub metadata_directive: CopyDirective,
+    /// `metadata_directive` 为 `Replace` 时，目标文件的自定义元信息，key 不包含 `x-oss-meta-` 前缀
+    pub user_meta: Vec<(&'a str, &'a str)>,
+    /// 服务端加密方式
     pub server_side_encryption: Option<Encryption>,
+    /// KMS 加密时使用的密钥 id
     pub server_side_encryption_key_id: Option<&'a str>,
+    /// 目标文件的访问权限
     pub object_acl: ObjectAcl,
-    pub storage_class: StorageClass,
+    /// 目标文件的存储类型，不设置时为 Standard
+    pub storage_class: Option<StorageClass>,
+    /// 目标文件的标签，如 `a=1&b=2`
     pub tagging: Option<&'a str>,
+    /// 标签的复制方式
     pub tagging_directive: CopyDirective,
 }
 
-/// 未来计划支持的功能
-#[derive(Default)]
-#[doc(hidden)]
+/// 复制文件时，元信息和标签的处理方式
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum CopyDirective {
+    /// 复制源文件的
     #[default]
     Copy,
+    /// 使用请求中指定的
     Replace,
 }
+
+impl CopyDirective {
+    /// 转化为 header 中使用的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Copy => "COPY",
+            Self::Replace => "REPLACE",
+        }
+    }
+}
+
+impl CopyObject<'_> {
+    /// 转化为请求的 headers，`bucket` 为目标文件所在的 bucket
+    pub(crate) fn headers(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<(HeaderName, HeaderValue)>, http::Error> {
+        let mut list = vec![
+            (
+                HeaderName::from_static("x-oss-copy-source"),
+                HeaderValue::from_str(&self.source(bucket))?,
+            ),
+            (
+                HeaderName::from_static("x-oss-metadata-directive"),
+                HeaderValue::from_static(self.metadata_directive.as_str()),
+            ),
+            (
+                HeaderName::from_static("x-oss-tagging-directive"),
+                HeaderValue::from_static(self.tagging_directive.as_str()),
+            ),
+        ];
+
+        push_headers(
+            &mut list,
+            [
+                (
+                    "x-oss-forbid-overwrite",
+                    self.forbid_overwrite.then_some("true"),
+                ),
+                ("x-oss-copy-source-if-match", self.copy_source_if_match),
+                (
+                    "x-oss-copy-source-if-none-match",
+                    self.copy_source_if_none_match,
+                ),
+                (
+                    "x-oss-copy-source-if-unmodified-since",
+                    self.copy_source_if_unmodified_since,
+                ),
+                (
+                    "x-oss-copy-source-if-modified-since",
+                    self.copy_source_if_modified_since,
+                ),
+                (
+                    "x-oss-server-side-encryption",
+                    self.server_side_encryption.as_ref().map(Encryption::as_str),
+                ),
+                (
+                    "x-oss-server-side-encryption-key-id",
+                    self.server_side_encryption_key_id,
+                ),
+                (
+                    "x-oss-storage-class",
+                    self.storage_class.as_ref().map(StorageClass::as_str),
+                ),
+                ("x-oss-tagging", self.tagging),
+                ("x-oss-object-acl", self.object_acl.header_value()),
+            ],
+        )?;
+        push_user_meta(&mut list, &self.user_meta)?;
+
+        Ok(list)
+    }
+
+    /// 分片拷贝时，初始化分片上传的 headers
+    ///
+    /// `metadata_directive` 为 `Copy` 时，沿用源文件 `meta` 中的 content-type 和自定义元信息，
+    /// 否则使用 `content_type` 和 `user_meta`
+    pub(crate) fn multipart_headers(
+        &self,
+        meta: &ObjectMeta,
+        content_type: &str,
+    ) -> Result<Vec<(HeaderName, HeaderValue)>, http::Error> {
+        let mut list = Vec::new();
+        match self.metadata_directive {
+            CopyDirective::Copy => {
+                let content_type = meta.content_type().unwrap_or(content_type);
+                list.push((CONTENT_TYPE, HeaderValue::from_str(content_type)?));
+                let user_meta: Vec<_> = meta
+                    .user_meta()
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect();
+                push_user_meta(&mut list, &user_meta)?;
+            }
+            CopyDirective::Replace => {
+                list.push((CONTENT_TYPE, HeaderValue::from_str(content_type)?));
+                push_user_meta(&mut list, &self.user_meta)?;
+            }
+        }
+
+        push_headers(
+            &mut list,
+            [
+                (
+                    "x-oss-forbid-overwrite",
+                    self.forbid_overwrite.then_some("true"),
+                ),
+                (
+                    "x-oss-server-side-encryption",
+                    self.server_side_encryption.as_ref().map(Encryption::as_str),
+                ),
+                (
+                    "x-oss-server-side-encryption-key-id",
+                    self.server_side_encryption_key_id,
+                ),
+                (
+                    "x-oss-storage-class",
+                    self.storage_class.as_ref().map(StorageClass::as_str),
+                ),
+                ("x-oss-tagging", self.tagging),
+                ("x-oss-object-acl", self.object_acl.header_value()),
+            ],
+        )?;
+
+        Ok(list)
+    }
+
+    /// UploadPartCopy 接口的 headers，`etag` 为源文件的 etag，用于确保复制过程中源文件没有变化
+    pub(crate) fn part_headers(
+        &self,
+        bucket: &str,
+        etag: Option<&str>,
+        range: Option<(u64, u64)>,
+    ) -> Result<Vec<(HeaderName, HeaderValue)>, http::Error> {
+        let mut list = vec![(
+            HeaderName::from_static("x-oss-copy-source"),
+            HeaderValue::from_str(&self.source(bucket))?,
+        )];
+        // 不指定区间时复制整个源文件
+        if let Some((start, end)) = range {
+            list.push((
+                HeaderName::from_static("x-oss-copy-source-range"),
+                HeaderValue::from_str(&format!("bytes={start}-{end}"))?,
+            ));
+        }
+
+        push_headers(
+            &mut list,
+            [
+                (
+                    "x-oss-copy-source-if-match",
+                    self.copy_source_if_match.or(etag),
+                ),
+                (
+                    "x-oss-copy-source-if-none-match",
+                    self.copy_source_if_none_match,
+                ),
+                (
+                    "x-oss-copy-source-if-unmodified-since",
+                    self.copy_source_if_unmodified_since,
+                ),
+                (
+                    "x-oss-copy-source-if-modified-since",
+                    self.copy_source_if_modified_since,
+                ),
+            ],
+        )?;
+
+        Ok(list)
+    }
+
+    /// 拆分源文件，返回 bucket，文件路径和版本
+    pub(crate) fn source_parts<'b>(
+        &'b self,
+        bucket: &'b str,
+    ) -> (&'b str, &'b str, Option<&'b str>) {
+        let (path, version) = match self.copy_source.split_once("?versionId=") {
+            Some((path, version)) => (path, Some(version)),
+            None => (self.copy_source, None),
+        };
+        match path.strip_prefix('/').and_then(|path| path.split_once('/')) {
+            Some((bucket, path)) => (bucket, path, version),
+            None => (bucket, path, version),
+        }
+    }
+
+    /// 编码后的源文件，形如 `/bucket/object`
+    fn source(&self, bucket: &str) -> String {
+        let (path, version) = match self.copy_source.split_once("?versionId=") {
+            Some((path, version)) => (path, Some(version)),
+            None => (self.copy_source, None),
+        };
+        let path = match path.strip_prefix('/') {
+            Some(path) => path.to_owned(),
+            None => format!("{bucket}/{path}"),
+        };
+        let mut source = format!("/{}", v4::path_encode(&path));
+        if let Some(version) = version {
+            source.push_str("?versionId=");
+            source.push_str(version);
+        }
+        source
+    }
+}
+
+/// 复制文件的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyObjectResult {
+    etag: String,
+    last_modified: DateTime<Utc>,
+}
+
+impl CopyObjectResult {
+    pub(crate) fn new(etag: String, last_modified: DateTime<Utc>) -> Self {
+        Self {
+            etag,
+            last_modified,
+        }
+    }
+
+    /// 目标文件的 etag
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// 目标文件的最后修改时间
+    pub fn last_modified(&self) -> &DateTime<Utc> {
+        &self.last_modified
+    }
+}
@@ -0,0 +1,459 @@
+//! # 追加上传
+//!
+//! [`Appender`] 会记录下次追加的位置，可以持续地向同一个文件追加内容，适用于日志等场景，
+//! 同步的 `blocking::Appender` 还实现了 [`std::io::Write`]
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, object::Appender};
+//! # #[tokio::main]
+//! # async fn main() -> std::io::Result<()> {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let mut appender = Appender::new(client, "logs/app.log".parse().unwrap());
+//! appender.set_content_type("text/plain");
+//! appender.append(b"line 1\n").await?;
+//! appender.append(b"line 2\n").await?;
+//! # Ok(())
+//! # }
+//! ```
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/appendobject)
+
+use crate::{
+    crc64::{combine, Crc64},
+    file::{check_crc64, FileError, Files, DEFAULT_CONTENT_TYPE},
+    ObjectPath,
+};
+
+/// 追加位置与文件长度不一致时，OSS 返回的错误码
+const POSITION_NOT_EQUAL: &str = "PositionNotEqualToLength";
+
+/// 追加上传的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppendObjectResult {
+    next_position: u64,
+    crc64: Option<u64>,
+}
+
+impl AppendObjectResult {
+    pub(crate) fn new(next_position: u64, crc64: Option<u64>) -> Self {
+        Self {
+            next_position,
+            crc64,
+        }
+    }
+
+    /// 下次追加的位置，即当前文件的长度
+    pub fn next_position(&self) -> u64 {
+        self.next_position
+    }
+
+    /// 当前整个文件的 CRC64 值
+    pub fn crc64(&self) -> Option<u64> {
+        self.crc64
+    }
+}
+
+/// # 追加上传的句柄
+///
+/// 会自动维护追加的位置，当位置与文件长度不一致时（如其他进程追加了内容），
+/// 会通过 HEAD 请求获取文件长度后重新追加
+#[derive(Debug, Clone)]
+pub struct Appender<C> {
+    client: C,
+    path: ObjectPath,
+    position: u64,
+    content_type: String,
+    crc64: Option<u64>,
+}
+
+impl<C: Files<ObjectPath>> Appender<C> {
+    /// 初始化，从位置 0 开始追加
+    pub fn new(client: C, path: ObjectPath) -> Self {
+        Self {
+            client,
+            path,
+            position: 0,
+            content_type: DEFAULT_CONTENT_TYPE.to_owned(),
+            crc64: None,
+        }
+    }
+
+    /// 文件路径
+    pub fn path(&self) -> &ObjectPath {
+        &self.path
+    }
+
+    /// 下次追加的位置
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 设置下次追加的位置
+    ///
+    /// 之前记录的 CRC64 值会被清除，下次追加后不再校验整个文件的 CRC64
+    pub fn set_position(&mut self, position: u64) -> &mut Self {
+        self.position = position;
+        self.crc64 = None;
+        self
+    }
+
+    /// 设置文件类型，只在创建文件时（即第一次追加时）生效
+    pub fn set_content_type(&mut self, content_type: &str) -> &mut Self {
+        self.content_type = content_type.to_owned();
+        self
+    }
+
+    /// 最近一次追加后，整个文件的 CRC64 值
+    pub fn crc64(&self) -> Option<u64> {
+        self.crc64
+    }
+
+    /// 通过 HEAD 请求，将追加位置同步为文件当前的长度
+    pub async fn sync_position(&mut self) -> Result<u64, FileError> {
+        let meta = self.client.head_object(self.path.clone()).await?;
+        self.position = meta
+            .next_append_position()
+            .unwrap_or_else(|| meta.content_length());
+        Ok(self.position)
+    }
+
+    /// 追加内容
+    ///
+    /// 客户端开启 CRC64 校验时，会用上次追加后的值与本次内容的值合并，
+    /// 并与 OSS 返回的整个文件的值比对
+    pub async fn append(&mut self, content: &[u8]) -> Result<AppendObjectResult, FileError> {
+        let mut previous = self.crc64;
+        let result = match self.append_once(content).await {
+            Err(e) if e.oss_code() == Some(POSITION_NOT_EQUAL) => {
+                // 文件被其他地方修改过，之前的值已失效
+                previous = None;
+                self.sync_position().await?;
+                self.append_once(content).await?
+            }
+            res => res?,
+        };
+
+        self.position = result.next_position();
+        self.crc64 = result.crc64();
+
+        if self.client.crc64_enabled() {
+            verify_crc64(previous, content, &result)?;
+        }
+        Ok(result)
+    }
+
+    async fn append_once(&self, content: &[u8]) -> Result<AppendObjectResult, FileError> {
+        self.client
+            .append_object(
+                content.to_vec(),
+                &self.content_type,
+                self.path.clone(),
+                self.position,
+            )
+            .await
+    }
+}
+
+/// 合并上次追加后的 CRC64 与本次内容的值，与 OSS 返回的值比对
+///
+/// 没有上次的值时（从头追加的情况已在 `append_object` 中校验）跳过
+fn verify_crc64(
+    previous: Option<u64>,
+    content: &[u8],
+    result: &AppendObjectResult,
+) -> Result<(), FileError> {
+    match previous {
+        Some(previous) => {
+            let local = combine(previous, Crc64::checksum(content), content.len() as u64);
+            check_crc64(result.crc64(), local)
+        }
+        None => Ok(()),
+    }
+}
+
+/// 同步的追加上传
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use std::io::{Result as IoResult, Write};
+
+    use super::{verify_crc64, AppendObjectResult, POSITION_NOT_EQUAL};
+    use crate::{
+        file::{BlockingFiles, FileError, DEFAULT_CONTENT_TYPE},
+        ObjectPath,
+    };
+
+    /// # 追加上传的句柄
+    ///
+    /// 会自动维护追加的位置，当位置与文件长度不一致时，
+    /// 会通过 HEAD 请求获取文件长度后重新追加
+    #[derive(Debug, Clone)]
+    pub struct Appender<C> {
+        client: C,
+        path: ObjectPath,
+        position: u64,
+        content_type: String,
+        crc64: Option<u64>,
+    }
+
+    impl<C: BlockingFiles<ObjectPath>> Appender<C> {
+        /// 初始化，从位置 0 开始追加
+        pub fn new(client: C, path: ObjectPath) -> Self {
+            Self {
+                client,
+                path,
+                position: 0,
+                content_type: DEFAULT_CONTENT_TYPE.to_owned(),
+                crc64: None,
+            }
+        }
+
+        /// 文件路径
+        pub fn path(&self) -> &ObjectPath {
+            &self.path
+        }
+
+        /// 下次追加的位置
+        pub fn position(&self) -> u64 {
+            self.position
+        }
+
+        /// 设置下次追加的位置
+        ///
+        /// 之前记录的 CRC64 值会被清除，下次追加后不再校验整个文件的 CRC64
+        pub fn set_position(&mut self, position: u64) -> &mut Self {
+            self.position = position;
+            self.crc64 = None;
+            self
+        }
+
+        /// 设置文件类型，只在创建文件时（即第一次追加时）生效
+        pub fn set_content_type(&mut self, content_type: &str) -> &mut Self {
+            self.content_type = content_type.to_owned();
+            self
+        }
+
+        /// 最近一次追加后，整个文件的 CRC64 值
+        pub fn crc64(&self) -> Option<u64> {
+            self.crc64
+        }
+
+        /// 通过 HEAD 请求，将追加位置同步为文件当前的长度
+        pub fn sync_position(&mut self) -> Result<u64, FileError> {
+            let meta = self.client.head_object(self.path.clone())?;
+            self.position = meta
+                .next_append_position()
+                .unwrap_or_else(|| meta.content_length());
+            Ok(self.position)
+        }
+
+        /// 追加内容
+        ///
+        /// 客户端开启 CRC64 校验时，会与 OSS 返回的整个文件的值比对
+        pub fn append(&mut self, content: &[u8]) -> Result<AppendObjectResult, FileError> {
+            let mut previous = self.crc64;
+            let result = match self.append_once(content) {
+                Err(e) if e.oss_code() == Some(POSITION_NOT_EQUAL) => {
+                    previous = None;
+                    self.sync_position()?;
+                    self.append_once(content)?
+                }
+                res => res?,
+            };
+
+            self.position = result.next_position();
+            self.crc64 = result.crc64();
+
+            if self.client.crc64_enabled() {
+                verify_crc64(previous, content, &result)?;
+            }
+            Ok(result)
+        }
+
+        fn append_once(&self, content: &[u8]) -> Result<AppendObjectResult, FileError> {
+            self.client.append_object(
+                content.to_vec(),
+                &self.content_type,
+                self.path.clone(),
+                self.position,
+            )
+        }
+    }
+
+    impl<C: BlockingFiles<ObjectPath>> Write for Appender<C> {
+        fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+            self.append(buf)?;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> IoResult<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    };
+
+    use async_trait::async_trait;
+    use http::{Method, StatusCode};
+    use reqwest::{Request, Response};
+
+    use super::Appender;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        crc64::Crc64,
+        errors::OssService,
+        Client,
+    };
+
+    /// 模拟 OSS 上的 Appendable 文件，`length` 为文件的实际长度
+    #[derive(Debug, Default)]
+    struct MockAppend {
+        length: Mutex<u64>,
+    }
+
+    #[async_trait]
+    impl Middleware for MockAppend {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let mut length = self.length.lock().unwrap();
+            let response = http::Response::builder()
+                .header("x-oss-next-append-position", length.to_string())
+                .header("x-oss-hash-crc64ecma", "123");
+
+            if request.method() == Method::HEAD {
+                let response = response
+                    .status(200)
+                    .header("content-length", length.to_string());
+                return Ok(Response::from(response.body("").unwrap()));
+            }
+
+            assert_eq!(request.method(), Method::POST);
+            let query = request.url().query().unwrap().to_owned();
+            if query != format!("append&position={length}") {
+                let body = "<Error><Code>PositionNotEqualToLength</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                let url = request.url().clone();
+                return Err(OssService::new2(body.to_owned(), &StatusCode::CONFLICT, url).into());
+            }
+
+            let size = request.body().unwrap().as_bytes().unwrap().len() as u64;
+            *length += size;
+            let response = http::Response::builder()
+                .status(200)
+                .header("x-oss-next-append-position", length.to_string())
+                .header("x-oss-hash-crc64ecma", "456")
+                .body("")
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn append() {
+        let middleware = Arc::new(MockAppend::default());
+        let client = Client::test_init().middleware(middleware.clone());
+
+        let mut appender = Appender::new(client, "abc.log".parse().unwrap());
+        let res = appender.append(b"abc").await.unwrap();
+        assert_eq!(res.next_position(), 3);
+        assert_eq!(res.crc64(), Some(456));
+        assert_eq!(appender.position(), 3);
+
+        // 其他进程追加了内容
+        *middleware.length.lock().unwrap() += 10;
+        let res = appender.append(b"de").await.unwrap();
+        assert_eq!(res.next_position(), 15);
+        assert_eq!(appender.position(), 15);
+        assert_eq!(appender.crc64(), Some(456));
+
+        appender.set_position(1);
+        assert_eq!(appender.sync_position().await.unwrap(), 15);
+    }
+
+    /// 记录文件的全部内容，返回对应的 CRC64，`corrupt` 为 true 时返回错误的值
+    #[derive(Debug, Default)]
+    struct MockCrc64 {
+        content: Mutex<Vec<u8>>,
+        corrupt: AtomicBool,
+    }
+
+    #[async_trait]
+    impl Middleware for MockCrc64 {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let mut content = self.content.lock().unwrap();
+            content.extend(request.body().unwrap().as_bytes().unwrap());
+
+            let crc64 = Crc64::checksum(&content) + u64::from(self.corrupt.load(Ordering::SeqCst));
+            let response = http::Response::builder()
+                .status(200)
+                .header("x-oss-next-append-position", content.len())
+                .header("x-oss-hash-crc64ecma", crc64)
+                .body("")
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn append_crc64() {
+        let middleware = Arc::new(MockCrc64::default());
+        let mut client = Client::test_init().middleware(middleware.clone());
+        client.enable_crc64(true);
+
+        let mut appender = Appender::new(client, "abc.log".parse().unwrap());
+        appender.append(b"abc").await.unwrap();
+        let res = appender.append(b"de").await.unwrap();
+        assert_eq!(res.crc64(), Some(Crc64::checksum(b"abcde")));
+
+        middleware.corrupt.store(true, Ordering::SeqCst);
+        let err = appender.append(b"f").await.unwrap_err();
+        assert!(err.is_crc64_mismatch());
+        // 内容已经追加，位置仍然更新
+        assert_eq!(appender.position(), 6);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_append() {
+        use super::blocking::Appender;
+        use crate::blocking::builder::Middleware;
+        use crate::ClientRc;
+        use reqwest::blocking::{Request, Response};
+        use std::io::Write;
+        use std::rc::Rc;
+
+        /// 文件已有 4 个字节
+        #[derive(Debug)]
+        struct MockAppend {}
+
+        impl Middleware for MockAppend {
+            fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+                let response = http::Response::builder().status(200);
+                let response = match (request.method(), request.url().query()) {
+                    (&Method::HEAD, _) => response.header("content-length", "4"),
+                    (_, Some("append&position=4")) => response
+                        .header("x-oss-next-append-position", "7")
+                        .header("x-oss-hash-crc64ecma", "789"),
+                    _ => {
+                        let body = "<Error><Code>PositionNotEqualToLength</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                        let url = request.url().clone();
+                        return Err(
+                            OssService::new2(body.to_owned(), &StatusCode::CONFLICT, url).into(),
+                        );
+                    }
+                };
+                Ok(Response::from(response.body("").unwrap()))
+            }
+        }
+
+        let client = ClientRc::test_init().middleware(Rc::new(MockAppend {}));
+        let mut appender = Appender::new(client, "abc.log".parse().unwrap());
+        appender.write_all(b"abc").unwrap();
+        assert_eq!(appender.position(), 7);
+        assert_eq!(appender.crc64(), Some(789));
+    }
+}
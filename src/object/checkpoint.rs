@@ -0,0 +1,36 @@
+//! 断点续传和断点下载共用的 checkpoint 文件
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// 默认的分片大小 10MB
+pub(super) const DEFAULT_PART_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 默认同时传输的分片数量
+pub(super) const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 在本地文件名后添加后缀，如 `{file}.checkpoint`
+pub(super) fn with_suffix(file: &Path, suffix: &str) -> PathBuf {
+    let mut name = file.to_owned().into_os_string();
+    name.push(suffix);
+    name.into()
+}
+
+/// 保存在本地的传输进度，通过 Display 和 FromStr 读写
+pub(super) trait CheckpointFile: Display + FromStr {
+    /// 读取 checkpoint 文件，文件不存在或内容无效时返回 None
+    fn load(path: &Path) -> Option<Self> {
+        fs::read_to_string(path).ok()?.parse().ok()
+    }
+
+    /// 先写入临时文件再重命名，避免进程中断时留下不完整的 checkpoint
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp = with_suffix(path, ".tmp");
+        fs::write(&tmp, self.to_string())?;
+        fs::rename(&tmp, path)
+    }
+}
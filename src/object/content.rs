@@ -50,22 +50,30 @@ use std::{
     sync::Arc,
 };
 
-use futures::executor::block_on;
-use http::{header::CONTENT_LENGTH, HeaderValue, Method};
+use bytes::Bytes;
+use futures::{executor::block_on, stream, StreamExt, TryStreamExt};
+use http::{header::CONTENT_LENGTH, HeaderName, HeaderValue, Method};
 use url::Url;
 
 use crate::{
     builder::BuilderError,
-    decode::RefineObject,
-    file::{AlignBuilder, DEFAULT_CONTENT_TYPE},
+    config::BucketBase,
+    crc64::{combine, Crc64},
+    decode::{decode_text, RefineObject},
+    file::{
+        check_crc64, content_md5_header, header_crc64, AlignBuilder, FileError,
+        DEFAULT_CONTENT_TYPE,
+    },
     types::{
         object::{InvalidObjectPath, SetObjectPath},
         CanonicalizedResource,
     },
-    Client, ObjectPath,
+    BucketName, Client, ObjectPath,
 };
 
-use super::{BuildInItemError, InitObject, Objects};
+use super::{
+    meta::InvalidObjectMeta, BuildInItemError, CopyObject, InitObject, ObjectMeta, Objects,
+};
 
 #[cfg(feature = "blocking")]
 pub mod blocking;
@@ -101,12 +109,23 @@ pub struct Inner {
     upload_id: String,
     /// 分片上传返回的 etag
     etag_list: Vec<(u16, HeaderValue)>,
+    /// 开启 CRC64 校验时，已上传分片的编号，CRC64 值和长度
+    crc64_list: Vec<(u16, u64, u64)>,
     part_size: usize,
+    /// 同时上传的分片数量
+    concurrency: usize,
 }
 
 impl Write for Content {
-    // 写入缓冲区
+    // 写入缓冲区，已写满的分片超过并发数时，先将其上传，上传失败时不写入 buf
     fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        if self.content_part.len() > self.concurrency {
+            let last = self.content_part.pop();
+            let parts = std::mem::take(&mut self.content_part);
+            self.content_part.extend(last);
+            block_on(self.upload_parts(parts))?;
+        }
+
         self.inner.write(buf)
     }
 
@@ -116,11 +135,13 @@ impl Write for Content {
 
         //println!("len: {}", len);
 
-        if len == 0 {
-            return Ok(());
-        }
-        if len == 1 {
-            return block_on(self.upload());
+        if self.upload_id.is_empty() {
+            if len == 0 {
+                return Ok(());
+            }
+            if len == 1 {
+                return block_on(self.upload());
+            }
         }
 
         block_on(self.upload_multi())
@@ -219,30 +240,95 @@ impl Content {
     }
 
     async fn upload_multi(&mut self) -> IoResult<()> {
-        self.init_multi().await?;
-
-        let mut i = 1;
-        let mut size: u64 = 0;
-        self.content_part.reverse();
-        while let Some(item) = self.content_part.pop() {
-            size += item.len() as u64;
-            self.upload_part(i, item).await?;
-            i += 1;
-        }
+        let parts = std::mem::take(&mut self.content_part);
+        self.upload_parts(parts).await?;
 
         self.complete_multi().await?;
-        self.content_size = size;
         Ok(())
     }
 
+    /// 并发上传多个分块，分块依次使用还未上传成功的编号
+    ///
+    /// 还未初始化分块上传时，会先进行初始化；
+    /// 记录所有上传成功的分块，失败的分块按原顺序放回缓冲区头部，可以再次调用 write 或 flush 重试
+    async fn upload_parts(&mut self, parts: Vec<Vec<u8>>) -> Result<(), ContentError> {
+        if self.upload_id.is_empty() {
+            if let Err(e) = self.init_multi().await {
+                self.restore_parts(parts);
+                return Err(e);
+            }
+            self.content_size = 0;
+        }
+
+        if self.etag_list.len() + parts.len() > Inner::MAX_PARTS_COUNT as usize {
+            self.restore_parts(parts);
+            return Err(ContentError::new(ContentErrorKind::OverflowMaxPartsCount));
+        }
+
+        let numbers = self.free_part_numbers(parts.len());
+
+        let this = &*self;
+        let crc64 = self.client.crc64_enabled();
+        let mut results: Vec<_> = stream::iter(parts.into_iter().zip(numbers))
+            .map(|(part, index)| async move {
+                let part = Bytes::from(part);
+                let res = this.upload_part(index, part.clone()).await;
+                (index, part, res)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+        results.sort_by_key(|(index, ..)| *index);
+
+        let mut failed = Vec::new();
+        let mut error = None;
+        for (index, part, res) in results {
+            match res {
+                Ok(etag) => {
+                    if crc64 {
+                        self.crc64_list
+                            .push((index, Crc64::checksum(&part), part.len() as u64));
+                    }
+                    self.content_size += part.len() as u64;
+                    self.etag_list.push((index, etag));
+                }
+                Err(e) => {
+                    failed.push(Vec::from(part));
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        self.etag_list.sort_by_key(|(index, _)| *index);
+        self.restore_parts(failed);
+
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// 将未上传的分块放回缓冲区头部
+    fn restore_parts(&mut self, mut parts: Vec<Vec<u8>>) {
+        parts.append(&mut self.content_part);
+        self.content_part = parts;
+    }
+
     /// 初始化批量上传
     async fn init_multi(&mut self) -> Result<(), ContentError> {
+        self.init_multi_with_header([]).await
+    }
+
+    /// 携带 headers 初始化批量上传，如文件的 content-type 和自定义元信息
+    async fn init_multi_with_header<H>(&mut self, headers: H) -> Result<(), ContentError>
+    where
+        H: IntoIterator<Item = (HeaderName, HeaderValue)>,
+    {
         const UPLOADS: &str = "uploads";
 
         let (url, resource) = self.part_canonicalized(UPLOADS);
         let xml = self
             .client
-            .builder(Method::POST, url, resource)?
+            .builder_with_header(Method::POST, url, resource, headers)?
             .send_adjust_error()
             .await?
             .text()
@@ -250,8 +336,8 @@ impl Content {
 
         self.parse_upload_id(&xml)
     }
-    /// 上传分块
-    async fn upload_part(&mut self, index: u16, buf: Vec<u8>) -> Result<(), ContentError> {
+    /// 上传分块，返回分块的 etag
+    async fn upload_part(&self, index: u16, buf: Bytes) -> Result<HeaderValue, ContentError> {
         const ETAG: &str = "ETag";
 
         if self.upload_id.is_empty() {
@@ -269,11 +355,14 @@ impl Content {
             self.part_canonicalized(&format!("partNumber={}&uploadId={}", index, self.upload_id));
 
         let content_length = buf.len().to_string();
-        let headers = vec![(
+        let mut headers = vec![(
             CONTENT_LENGTH,
             HeaderValue::from_str(&content_length)
                 .expect("content length must be a valid header value"),
         )];
+        if self.client.content_md5_enabled() {
+            headers.push(content_md5_header(&buf));
+        }
 
         let resp = self
             .client
@@ -292,12 +381,20 @@ impl Content {
         // 59A2A10DD1686F679EE885FC1EBA5183
         //let etag = &(etag.to_str().unwrap())[1..33];
 
-        self.etag_list.push((index, etag.to_owned()));
-
-        Ok(())
+        Ok(etag.to_owned())
     }
     /// 完成分块上传
     async fn complete_multi(&mut self) -> Result<(), ContentError> {
+        let crc64 = self
+            .client
+            .crc64_enabled()
+            .then(|| self.parts_crc64())
+            .flatten();
+        self.complete_multi_with_crc64(crc64).await
+    }
+
+    /// 完成分块上传，`crc64` 不为空时，与 OSS 返回的整个文件的 CRC64 比对
+    async fn complete_multi_with_crc64(&mut self, crc64: Option<u64>) -> Result<(), ContentError> {
         if self.upload_id.is_empty() {
             return Err(ContentError::new(ContentErrorKind::NoFoundUploadId));
         }
@@ -313,7 +410,7 @@ impl Content {
                 .expect("content length must be a valid header value"),
         )];
 
-        let _resp = self
+        let resp = self
             .client
             .builder_with_header(Method::POST, url, resource, headers)?
             .body(xml)
@@ -321,10 +418,160 @@ impl Content {
             .await?;
 
         self.etag_list.clear();
+        self.crc64_list.clear();
         self.upload_id = String::default();
 
+        if let Some(crc64) = crc64 {
+            check_crc64(header_crc64(resp.headers()), crc64)
+                .map_err(|e| ContentError::new(ContentErrorKind::Crc64Mismatch(e)))?;
+        }
+
         Ok(())
     }
+    /// # 分片拷贝
+    ///
+    /// 将 `options` 中的源文件复制到当前路径，适用于超过 1GB，无法使用 CopyObject 复制的文件
+    ///
+    /// 按照 `part_size` 将源文件拆分为多个区间，并发调用 UploadPartCopy 接口，
+    /// 任意一个分片失败时，会取消本次分片上传
+    ///
+    /// ```rust,no_run
+    /// # use aliyun_oss_client::{Client, object::{Content, CopyObject}};
+    /// # use std::sync::Arc;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// dotenv::dotenv().ok();
+    /// let client = Client::from_env().unwrap();
+    ///
+    /// let mut content = Content::from_client(Arc::new(client))
+    ///     .path("backup/archive.tar")
+    ///     .unwrap();
+    /// content.part_size(512 * 1024 * 1024).unwrap();
+    ///
+    /// let options = CopyObject {
+    ///     copy_source: "/other-bucket/archive.tar",
+    ///     ..Default::default()
+    /// };
+    /// content.copy_multi(&options).await.unwrap();
+    /// # }
+    /// ```
+    /// [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/uploadpartcopy)
+    pub async fn copy_multi(&mut self, options: &CopyObject<'_>) -> Result<(), ContentError> {
+        let bucket = self.client.get_bucket_name().to_string();
+        let meta = self.head_source(options, &bucket).await?;
+
+        let part_size = self.part_size as u64;
+        let count = (meta.content_length() + part_size - 1) / part_size;
+        if count > Inner::MAX_PARTS_COUNT as u64 {
+            return Err(ContentError::new(ContentErrorKind::OverflowMaxPartsCount));
+        }
+
+        let headers = options
+            .multipart_headers(&meta, self.content_type)
+            .map_err(|e| ContentError::new(ContentErrorKind::InvalidHeader(e)))?;
+        self.init_multi_with_header(headers).await?;
+
+        let etag = meta.etag();
+        let ranges: Vec<_> = if count == 0 {
+            // 空文件无法用区间表示，不指定区间，复制整个源文件作为唯一的分片
+            vec![(1, None)]
+        } else {
+            (0..count)
+                .map(|index| {
+                    let start = index * part_size;
+                    let end = (start + part_size).min(meta.content_length()) - 1;
+                    (index as u16 + 1, Some((start, end)))
+                })
+                .collect()
+        };
+
+        let (this, bucket) = (&*self, &bucket);
+        let res = stream::iter(ranges)
+            .map(|(index, range)| async move {
+                let headers = options
+                    .part_headers(bucket, etag, range)
+                    .map_err(|e| ContentError::new(ContentErrorKind::InvalidHeader(e)))?;
+                this.copy_part(index, headers)
+                    .await
+                    .map(|etag| (index, etag))
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect::<Vec<_>>()
+            .await;
+
+        let res = match res {
+            Ok(etags) => {
+                self.etag_list = etags;
+                self.etag_list.sort_by_key(|(index, _)| *index);
+                // 复制后的文件与源文件的 CRC64 相同
+                let crc64 = self.client.crc64_enabled().then(|| meta.crc64()).flatten();
+                self.complete_multi_with_crc64(crc64).await
+            }
+            Err(e) => Err(e),
+        };
+
+        if res.is_err() {
+            let _ = self.abort_multi().await;
+        } else {
+            self.content_size = meta.content_length();
+        }
+        res
+    }
+
+    /// 获取源文件的元信息
+    async fn head_source(
+        &self,
+        options: &CopyObject<'_>,
+        bucket: &str,
+    ) -> Result<ObjectMeta, ContentError> {
+        let invalid = || ContentError::new(ContentErrorKind::InvalidCopySource);
+
+        let (bucket, path, version) = options.source_parts(bucket);
+        let name: BucketName = bucket.parse().map_err(|_| invalid())?;
+        let path: ObjectPath = path.parse().map_err(|_| invalid())?;
+
+        let mut url = BucketBase::new(name, self.client.endpoint.clone()).to_url();
+        url.set_object_path(&path);
+        let mut resource = format!("/{}/{}", bucket, path.as_ref());
+        if let Some(version) = version {
+            url.set_query(Some(&format!("versionId={version}")));
+            resource.push_str(&format!("?versionId={version}"));
+        }
+
+        let response = self
+            .client
+            .builder(Method::HEAD, url, CanonicalizedResource::new(resource))?
+            .send_adjust_error()
+            .await?;
+
+        ObjectMeta::from_headers(response.headers())
+            .map_err(|e| ContentError::new(ContentErrorKind::SourceMeta(e)))
+    }
+
+    /// 复制一个分片，返回分片的 etag
+    async fn copy_part(
+        &self,
+        index: u16,
+        headers: Vec<(HeaderName, HeaderValue)>,
+    ) -> Result<HeaderValue, ContentError> {
+        let (url, resource) =
+            self.part_canonicalized(&format!("partNumber={}&uploadId={}", index, self.upload_id));
+
+        let xml = self
+            .client
+            .builder_with_header(Method::PUT, url, resource, headers)?
+            .send_adjust_error()
+            .await?
+            .text()
+            .await?;
+
+        decode_text(&xml, b"ETag")
+            .ok()
+            .flatten()
+            .and_then(|etag| HeaderValue::from_str(&etag).ok())
+            .ok_or(ContentError::new(ContentErrorKind::NoFoundEtag))
+    }
+
     /// 取消分块上传
     pub async fn abort_multi(&mut self) -> Result<(), ContentError> {
         if self.upload_id.is_empty() {
@@ -341,6 +588,7 @@ impl Content {
 
         //println!("resp: {:?}", resp);
         self.etag_list.clear();
+        self.crc64_list.clear();
         self.upload_id = String::default();
 
         Ok(())
@@ -416,7 +664,9 @@ impl Default for Inner {
             content_type: Self::DEFAULT_CONTENT_TYPE,
             upload_id: String::default(),
             etag_list: Vec::default(),
+            crc64_list: Vec::default(),
             part_size: 200 * 1024 * 1024, // 200M
+            concurrency: Self::DEFAULT_CONCURRENCY,
         }
     }
 }
@@ -451,13 +701,15 @@ fn get_content_type(filename: &str) -> &'static str {
 
 impl Inner {
     const DEFAULT_CONTENT_TYPE: &str = DEFAULT_CONTENT_TYPE;
+    /// 默认同时上传的分片数量
+    const DEFAULT_CONCURRENCY: usize = 4;
 
     /// 最大存储容量 48.8 TB, 49664 = 1024 * 48.5
     #[cfg(not(test))]
     const MAX_SIZE: u64 = 1024 * 1024 * 1024 * 49_664;
     /// 最大 part 数量
     #[cfg(not(test))]
-    const MAX_PARTS_COUNT: u16 = 10000;
+    pub(crate) const MAX_PARTS_COUNT: u16 = 10000;
     /// 单个 part 的最小尺寸 100K
     #[cfg(not(test))]
     const PART_SIZE_MIN: usize = 102400;
@@ -470,7 +722,7 @@ impl Inner {
     const MAX_SIZE: u64 = 200;
     /// 最大 part 数量
     #[cfg(test)]
-    const MAX_PARTS_COUNT: u16 = 10;
+    pub(crate) const MAX_PARTS_COUNT: u16 = 10;
     /// 单个 part 的最小尺寸 100K
     #[cfg(test)]
     const PART_SIZE_MIN: usize = 10;
@@ -500,7 +752,7 @@ impl Inner {
 
     // 写入缓冲区
     fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
-        let part_total = self.content_part.len();
+        let part_total = self.content_part.len() + self.etag_list.len();
         if part_total >= Inner::MAX_PARTS_COUNT as usize {
             return Err(ContentError::new(ContentErrorKind::OverflowMaxPartsCount).into());
         }
@@ -530,38 +782,64 @@ impl Inner {
 
     /// 设置分块的尺寸
     pub fn part_size(&mut self, size: usize) -> Result<(), ContentError> {
-        if (Self::PART_SIZE_MIN..=Self::PART_SIZE_MAX).contains(&size) {
+        if Self::valid_part_size(size) {
             self.part_size = size;
             Ok(())
         } else {
             Err(ContentError::new(ContentErrorKind::OverflowPartSize))
         }
     }
+    /// 分块的尺寸是否在 100K 到 5G 之间
+    pub(crate) fn valid_part_size(size: usize) -> bool {
+        (Self::PART_SIZE_MIN..=Self::PART_SIZE_MAX).contains(&size)
+    }
+    /// 按从小到大的顺序，取 `count` 个还没有上传成功的分块编号
+    ///
+    /// 缓冲区中的分块与这些编号一一对应，上传失败的分块会重新使用原来的编号
+    fn free_part_numbers(&self, count: usize) -> Vec<u16> {
+        (1..=Self::MAX_PARTS_COUNT)
+            .filter(|number| {
+                self.etag_list
+                    .binary_search_by_key(number, |(index, _)| *index)
+                    .is_err()
+            })
+            .take(count)
+            .collect()
+    }
     fn parse_upload_id(&mut self, xml: &str) -> Result<(), ContentError> {
-        if let (Some(start), Some(end)) = (xml.find("<UploadId>"), xml.find("</UploadId>")) {
-            self.upload_id = xml[start + 10..end].to_owned();
-            Ok(())
-        } else {
-            Err(ContentError::new(ContentErrorKind::NoFoundUploadId))
+        self.upload_id = parse_upload_id(xml)
+            .ok_or(ContentError::new(ContentErrorKind::NoFoundUploadId))?
+            .to_owned();
+        Ok(())
+    }
+    /// 按分片编号合并各分片的 CRC64，得到整个文件的值，有分片没有记录时返回 None
+    fn parts_crc64(&self) -> Option<u64> {
+        if self.crc64_list.len() != self.etag_list.len() {
+            return None;
         }
+
+        let mut list = self.crc64_list.clone();
+        list.sort_by_key(|(index, ..)| *index);
+        Some(
+            list.into_iter()
+                .fold(0, |crc, (_, part, len)| combine(crc, part, len)),
+        )
     }
+
     fn etag_list_xml(&self) -> Result<String, ContentError> {
         if self.etag_list.is_empty() {
             return Err(ContentError::new(ContentErrorKind::EtagListEmpty));
         }
-        let mut list = String::new();
-        for (index, etag) in self.etag_list.iter() {
-            list.push_str(&format!(
-                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
-                index,
-                etag.to_str().expect("etag covert str failed")
-            ));
-        }
+        Ok(complete_xml(self.etag_list.iter().map(|(index, etag)| {
+            (*index, etag.to_str().expect("etag covert str failed"))
+        })))
+    }
 
-        Ok(format!(
-            "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
-            list
-        ))
+    /// # 设置同时上传的分片数量
+    ///
+    /// 缓冲区中写满的分片超过该数量时，会并发上传这些分片，缓冲区最多占用 `(concurrency + 1) * part_size` 的内存
+    pub fn concurrency(&mut self, concurrency: usize) {
+        self.concurrency = concurrency.max(1);
     }
 
     /// 清空缓冲区
@@ -570,6 +848,29 @@ impl Inner {
     }
 }
 
+/// 从 InitiateMultipartUpload 接口的响应中获取 upload id
+pub(crate) fn parse_upload_id(xml: &str) -> Option<&str> {
+    match (xml.find("<UploadId>"), xml.find("</UploadId>")) {
+        (Some(start), Some(end)) if start + 10 <= end => Some(&xml[start + 10..end]),
+        _ => None,
+    }
+}
+
+/// CompleteMultipartUpload 接口的请求体，`parts` 为分片编号及其 etag
+pub(crate) fn complete_xml<'a, I>(parts: I) -> String
+where
+    I: IntoIterator<Item = (u16, &'a str)>,
+{
+    let mut xml = String::from("<CompleteMultipartUpload>");
+    for (index, etag) in parts {
+        xml.push_str(&format!(
+            "<Part><PartNumber>{index}</PartNumber><ETag>{etag}</ETag></Part>"
+        ));
+    }
+    xml.push_str("</CompleteMultipartUpload>");
+    xml
+}
+
 impl From<Client> for Content {
     fn from(value: Client) -> Self {
         Content {
@@ -610,6 +911,18 @@ enum ContentErrorKind {
 
     /// max size must be lt 48.8TB
     OverflowMaxSize,
+
+    /// invalid copy source
+    InvalidCopySource,
+
+    /// invalid header
+    InvalidHeader(http::Error),
+
+    /// invalid source object meta
+    SourceMeta(InvalidObjectMeta),
+
+    /// crc64 mismatch
+    Crc64Mismatch(FileError),
 }
 
 impl ContentError {
@@ -656,6 +969,8 @@ impl From<ContentError> for std::io::Error {
             EtagListEmpty => Self::new(NotFound, kind),
             OverflowPartSize => Self::new(Unsupported, kind),
             OverflowMaxSize => Self::new(Unsupported, kind),
+            InvalidCopySource | InvalidHeader(_) | SourceMeta(_) => Self::new(InvalidInput, kind),
+            Crc64Mismatch(e) => e.into(),
         }
     }
 }
@@ -670,6 +985,10 @@ impl Display for ContentErrorKind {
             Self::EtagListEmpty => "etag list is empty".fmt(f),
             Self::OverflowPartSize => "part size must be between 100k and 5G".fmt(f),
             Self::OverflowMaxSize => "max size must be lt 48.8TB".fmt(f),
+            Self::InvalidCopySource => "invalid copy source".fmt(f),
+            Self::InvalidHeader(_) => "invalid header".fmt(f),
+            Self::SourceMeta(_) => "invalid source object meta".fmt(f),
+            Self::Crc64Mismatch(_) => "crc64 mismatch".fmt(f),
         }
     }
 }
@@ -678,12 +997,16 @@ impl Error for ContentErrorKind {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             Self::Builder(e) => Some(e),
+            Self::InvalidHeader(e) => Some(e),
+            Self::SourceMeta(e) => Some(e),
+            Self::Crc64Mismatch(e) => Some(e),
             Self::NoFoundUploadId
             | Self::NoFoundEtag
             | Self::OverflowMaxPartsCount
             | Self::EtagListEmpty
             | Self::OverflowPartSize
-            | Self::OverflowMaxSize => None,
+            | Self::OverflowMaxSize
+            | Self::InvalidCopySource => None,
         }
     }
 }
@@ -693,16 +1016,24 @@ mod tests {
     use std::{
         io::{Read, Seek, Write},
         ops::Deref,
-        sync::Arc,
+        sync::{Arc, Mutex},
     };
 
     use super::{
         get_content_type,
-        test_suite::{AbortMulti, CompleteMulti, InitMulti, UploadMulti, UploadPart},
+        test_suite::{
+            AbortMulti, CompleteMulti, ConcurrentMulti, CopyMulti, InitMulti, UploadMulti,
+            UploadPart,
+        },
         Content, Inner, List,
     };
 
-    use crate::{decode::RefineObject, object::InitObject, Client, ObjectPath};
+    use crate::{
+        crc64::Crc64,
+        decode::RefineObject,
+        object::{CopyDirective, CopyObject, InitObject},
+        Client, ObjectPath,
+    };
 
     #[test]
     fn default() {
@@ -716,6 +1047,7 @@ mod tests {
         assert_eq!(inner.upload_id, "");
         assert_eq!(inner.etag_list.len(), 0);
         assert_eq!(inner.part_size, 200 * 1024 * 1024);
+        assert_eq!(inner.concurrency, 4);
     }
 
     #[test]
@@ -830,26 +1162,30 @@ mod tests {
             .path("aaa.txt")
             .unwrap();
 
-        let err = con.upload_part(1, b"bbb".to_vec()).await.unwrap_err();
+        let err = con
+            .upload_part(1, b"bbb".to_vec().into())
+            .await
+            .unwrap_err();
         assert_eq!(err.to_string(), "not found upload id");
 
         con.upload_id = "foo_upload_id".to_string();
         for _i in 0..10 {
             con.etag_list.push((1, "a".parse().unwrap()));
         }
-        let err = con.upload_part(1, b"bbb".to_vec()).await.unwrap_err();
+        let err = con
+            .upload_part(1, b"bbb".to_vec().into())
+            .await
+            .unwrap_err();
         assert_eq!(err.to_string(), "overflow max parts count");
         con.etag_list.clear();
 
         let err = con
-            .upload_part(1, b"012345678901234567890".to_vec())
+            .upload_part(1, b"012345678901234567890".to_vec().into())
             .await
             .unwrap_err();
         assert_eq!(err.to_string(), "part size must be between 100k and 5G");
 
-        con.upload_part(2, b"bbb".to_vec()).await.unwrap();
-        let (index, value) = con.etag_list.pop().unwrap();
-        assert_eq!(index, 2);
+        let value = con.upload_part(2, b"bbb".to_vec().into()).await.unwrap();
         assert_eq!(value.to_str().unwrap(), "foo_etag");
     }
 
@@ -885,6 +1221,109 @@ mod tests {
         assert_eq!(con.content_size, 6);
     }
 
+    #[test]
+    fn concurrent_write() {
+        let middleware = Arc::new(ConcurrentMulti::default());
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+        con.concurrency(2);
+
+        con.write_all(&[1u8; 35]).unwrap();
+        // 写满的前两个分片已上传，缓冲区只保留后两个分片
+        assert_eq!(middleware.parts.lock().unwrap().len(), 2);
+        assert_eq!(con.content_part.len(), 2);
+
+        con.flush().unwrap();
+        let mut parts = middleware.parts.lock().unwrap().clone();
+        parts.sort();
+        assert_eq!(parts, ["1", "2", "3", "4"]);
+        assert_eq!(
+            *middleware.complete.lock().unwrap(),
+            "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>etag1</ETag></Part><Part><PartNumber>2</PartNumber><ETag>etag2</ETag></Part><Part><PartNumber>3</PartNumber><ETag>etag3</ETag></Part><Part><PartNumber>4</PartNumber><ETag>etag4</ETag></Part></CompleteMultipartUpload>"
+        );
+        assert_eq!(con.content_size, 35);
+        assert!(con.upload_id.is_empty());
+    }
+
+    #[test]
+    fn concurrent_write_retry() {
+        let middleware = Arc::new(ConcurrentMulti {
+            fail_part: Mutex::new(Some("1")),
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+        con.concurrency(2);
+
+        con.write_all(&[1u8; 30]).unwrap();
+        // 分片 1 上传失败，分片 2 的 etag 被保留，本次写入的数据没有进入缓冲区
+        con.write(&[2u8; 5]).unwrap_err();
+        assert_eq!(*middleware.parts.lock().unwrap(), ["2"]);
+        assert_eq!(con.etag_list.len(), 1);
+        assert_eq!(con.content_part.len(), 2);
+        assert_eq!(con.content_part[0], [1u8; 10]);
+        assert_eq!(con.content_size, 10);
+
+        con.write_all(&[2u8; 5]).unwrap();
+        con.flush().unwrap();
+        let mut parts = middleware.parts.lock().unwrap().clone();
+        parts.sort();
+        assert_eq!(parts, ["1", "2", "3", "4"]);
+        assert!(middleware
+            .complete
+            .lock()
+            .unwrap()
+            .contains("<Part><PartNumber>1</PartNumber><ETag>etag1</ETag></Part><Part><PartNumber>2</PartNumber>"));
+        assert_eq!(con.content_size, 35);
+    }
+
+    #[test]
+    fn concurrent_write_crc64() {
+        let data: Vec<u8> = (0..35).collect();
+        let middleware = Arc::new(ConcurrentMulti {
+            crc64: Some(Crc64::checksum(&data)),
+            ..Default::default()
+        });
+        let mut client = Client::test_init().middleware(middleware);
+        client.enable_crc64(true);
+        let client = Arc::new(client);
+
+        let mut con = Content::from_client(client.clone())
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+        con.concurrency(2);
+        con.write_all(&data).unwrap();
+        con.flush().unwrap();
+        assert!(con.crc64_list.is_empty());
+
+        let mut con = Content::from_client(client).path("aaa.txt").unwrap();
+        con.part_size(10).unwrap();
+        con.write_all(&data[1..]).unwrap();
+        let err = con.flush().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn parts_crc64() {
+        let mut inner = Inner::default();
+        assert_eq!(inner.parts_crc64(), Some(0));
+
+        inner.etag_list.push((1, "a".parse().unwrap()));
+        assert_eq!(inner.parts_crc64(), None);
+
+        inner.etag_list.push((2, "b".parse().unwrap()));
+        inner.crc64_list.push((2, Crc64::checksum(b"56789"), 5));
+        inner.crc64_list.push((1, Crc64::checksum(b"1234"), 4));
+        assert_eq!(inner.parts_crc64(), Some(Crc64::checksum(b"123456789")));
+    }
+
     #[tokio::test]
     async fn abort_multi() {
         let client = Client::test_init().middleware(Arc::new(AbortMulti {}));
@@ -901,6 +1340,155 @@ mod tests {
         assert!(con.upload_id.is_empty());
     }
 
+    #[tokio::test]
+    async fn copy_multi() {
+        let middleware = Arc::new(CopyMulti::default());
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+
+        let options = CopyObject {
+            copy_source: "/other/src.zip",
+            ..Default::default()
+        };
+        con.copy_multi(&options).await.unwrap();
+
+        let mut requests = middleware.requests.lock().unwrap().clone();
+        requests[2..5].sort();
+        assert_eq!(
+            requests,
+            [
+                "HEAD",
+                "INIT application/zip author=tu6ge",
+                "PUT 1 bytes=0-9",
+                "PUT 2 bytes=10-19",
+                "PUT 3 bytes=20-24",
+                "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>\"etag1\"</ETag></Part><Part><PartNumber>2</PartNumber><ETag>\"etag2\"</ETag></Part><Part><PartNumber>3</PartNumber><ETag>\"etag3\"</ETag></Part></CompleteMultipartUpload>",
+            ]
+        );
+        assert_eq!(con.content_size, 25);
+        assert!(con.upload_id.is_empty());
+
+        // 替换元信息
+        let middleware = Arc::new(CopyMulti::default());
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(20).unwrap();
+        let options = CopyObject {
+            copy_source: "/other/src.zip",
+            metadata_directive: CopyDirective::Replace,
+            user_meta: vec![("author", "foo")],
+            ..Default::default()
+        };
+        con.copy_multi(&options).await.unwrap();
+        assert_eq!(
+            middleware.requests.lock().unwrap()[1],
+            "INIT text/plain author=foo"
+        );
+    }
+
+    #[tokio::test]
+    async fn copy_multi_empty() {
+        let middleware = Arc::new(CopyMulti {
+            empty: true,
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+
+        let options = CopyObject {
+            copy_source: "/other/src.zip",
+            ..Default::default()
+        };
+        con.copy_multi(&options).await.unwrap();
+
+        assert_eq!(
+            *middleware.requests.lock().unwrap(),
+            [
+                "HEAD",
+                "INIT application/zip author=tu6ge",
+                "PUT 1 ",
+                "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>\"etag1\"</ETag></Part></CompleteMultipartUpload>",
+            ]
+        );
+        assert_eq!(con.content_size, 0);
+    }
+
+    #[tokio::test]
+    async fn copy_multi_no_etag() {
+        let middleware = Arc::new(CopyMulti {
+            no_etag: true,
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(20).unwrap();
+
+        let options = CopyObject {
+            copy_source: "/other/src.zip",
+            ..Default::default()
+        };
+        con.copy_multi(&options).await.unwrap();
+        assert_eq!(middleware.requests.lock().unwrap().len(), 5);
+
+        let middleware = Arc::new(CopyMulti {
+            malformed: true,
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(20).unwrap();
+        let err = con.copy_multi(&options).await.unwrap_err();
+        assert!(matches!(err.kind, super::ContentErrorKind::NoFoundEtag));
+        assert_eq!(
+            middleware
+                .requests
+                .lock()
+                .unwrap()
+                .last()
+                .map(String::as_str),
+            Some("DELETE uploadId=foo_upload_id")
+        );
+    }
+
+    #[tokio::test]
+    async fn copy_multi_abort() {
+        let middleware = Arc::new(CopyMulti {
+            fail_part: Some("2"),
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+        let mut con = Content::from_client(Arc::new(client))
+            .path("aaa.txt")
+            .unwrap();
+        con.part_size(10).unwrap();
+        con.concurrency(1);
+
+        let options = CopyObject {
+            copy_source: "/other/src.zip",
+            ..Default::default()
+        };
+        con.copy_multi(&options).await.unwrap_err();
+
+        let requests = middleware.requests.lock().unwrap().clone();
+        assert_eq!(
+            requests.last().map(String::as_str),
+            Some("DELETE uploadId=foo_upload_id")
+        );
+        assert!(con.upload_id.is_empty());
+    }
+
     #[test]
     fn seek() {
         let mut inner = Inner::default();
@@ -14,7 +14,8 @@ use super::mock::blocking::Files;
 #[cfg(not(test))]
 use crate::file::BlockingFiles;
 use crate::{
-    file::blocking::AlignBuilder,
+    crc64::Crc64,
+    file::{blocking::AlignBuilder, check_crc64, content_md5_header, header_crc64},
     object::InitObject,
     types::{
         object::{InvalidObjectPath, SetObjectPath},
@@ -207,10 +208,17 @@ impl Content {
         let (url, resource) = self.part_canonicalized(&query);
 
         let content_length = buf.len().to_string();
-        let headers = vec![(
+        let mut headers = vec![(
             CONTENT_LENGTH,
             HeaderValue::from_str(&content_length).unwrap(),
         )];
+        if self.client.content_md5_enabled() {
+            headers.push(content_md5_header(&buf));
+        }
+        let crc64 = self
+            .client
+            .crc64_enabled()
+            .then(|| (index, Crc64::checksum(&buf), buf.len() as u64));
 
         let resp = self
             .client
@@ -229,6 +237,7 @@ impl Content {
         //let etag = &(etag.to_str().unwrap())[1..33];
 
         self.etag_list.push((index, etag.to_owned()));
+        self.crc64_list.extend(crc64);
 
         Ok(())
     }
@@ -251,7 +260,13 @@ impl Content {
             HeaderValue::from_str(&content_length).unwrap(),
         )];
 
-        let _resp = self
+        let crc64 = self
+            .client
+            .crc64_enabled()
+            .then(|| self.parts_crc64())
+            .flatten();
+
+        let resp = self
             .client
             .builder_with_header(Method::POST, url, resource, headers)?
             .body(xml)
@@ -259,8 +274,14 @@ impl Content {
 
         //println!("resp: {}", resp);
         self.etag_list.clear();
+        self.crc64_list.clear();
         self.upload_id = String::default();
 
+        if let Some(crc64) = crc64 {
+            check_crc64(header_crc64(resp.headers()), crc64)
+                .map_err(|e| ContentError::new(ContentErrorKind::Crc64Mismatch(e)))?;
+        }
+
         Ok(())
     }
 
@@ -279,6 +300,7 @@ impl Content {
 
         //println!("resp: {:?}", resp);
         self.etag_list.clear();
+        self.crc64_list.clear();
         self.upload_id = String::default();
 
         Ok(())
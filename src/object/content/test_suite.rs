@@ -1,8 +1,11 @@
+use std::sync::Mutex;
+
 use async_trait::async_trait;
 use http::HeaderValue;
 use reqwest::{Body, Request, Response};
 
 use crate::builder::{BuilderError, Middleware};
+use crate::errors::OssService;
 
 #[derive(Debug)]
 pub(super) struct InitMulti {}
@@ -225,3 +228,169 @@ impl Middleware for UploadMulti {
         panic!("error");
     }
 }
+
+/// 记录分块的上传顺序及完成分块上传时的请求体，完成时返回 `crc64`
+#[derive(Debug, Default)]
+pub(super) struct ConcurrentMulti {
+    pub(super) parts: Mutex<Vec<String>>,
+    pub(super) complete: Mutex<String>,
+    pub(super) crc64: Option<u64>,
+    /// 该编号的分片第一次上传时返回错误
+    pub(super) fail_part: Mutex<Option<&'static str>>,
+}
+
+#[async_trait]
+impl Middleware for ConcurrentMulti {
+    async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+        use http::response::Builder;
+        let query = request.url().query().unwrap().to_owned();
+        let response = match request.method().as_str() {
+            "POST" if query == "uploads" => Builder::new()
+                .status(200)
+                .body("<InitiateMultipartUploadResult><UploadId>foo_upload_id</UploadId></InitiateMultipartUploadResult>".to_owned()),
+            "PUT" => {
+                let number = query
+                    .strip_prefix("partNumber=")
+                    .and_then(|q| q.split('&').next())
+                    .unwrap()
+                    .to_owned();
+                let mut fail_part = self.fail_part.lock().unwrap();
+                if fail_part.as_deref() == Some(number.as_str()) {
+                    *fail_part = None;
+                    return Err(OssService::new2(
+                        "<Error><Code>InternalError</Code><Message>foo</Message><RequestId>r</RequestId></Error>".to_owned(),
+                        &reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                        request.url().clone(),
+                    )
+                    .into());
+                }
+                self.parts.lock().unwrap().push(number.clone());
+                Builder::new()
+                    .status(200)
+                    .header("ETag", format!("etag{number}"))
+                    .body(String::new())
+            }
+            "POST" => {
+                assert_eq!(query, "uploadId=foo_upload_id");
+                let body = request.body().unwrap().as_bytes().unwrap();
+                *self.complete.lock().unwrap() = String::from_utf8(body.to_vec()).unwrap();
+                let mut builder = Builder::new().status(200);
+                if let Some(crc64) = self.crc64 {
+                    builder = builder.header("x-oss-hash-crc64ecma", crc64);
+                }
+                builder.body(String::new())
+            }
+            _ => panic!("error"),
+        };
+        Ok(response.unwrap().into())
+    }
+}
+
+/// 分片拷贝，源文件大小为 25（`empty` 时为 0），`fail_part` 对应的分片返回错误
+#[derive(Debug, Default)]
+pub(super) struct CopyMulti {
+    pub(super) fail_part: Option<&'static str>,
+    pub(super) empty: bool,
+    /// HEAD 不返回 ETag
+    pub(super) no_etag: bool,
+    /// UploadPartCopy 返回无法解析的响应
+    pub(super) malformed: bool,
+    pub(super) requests: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl Middleware for CopyMulti {
+    async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+        use http::response::Builder;
+
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .map(|v| v.to_str().unwrap().to_owned())
+                .unwrap_or_default()
+        };
+        let query = request.url().query().unwrap_or_default().to_owned();
+        let response = match request.method().as_str() {
+            "HEAD" => {
+                assert_eq!(
+                    request.url().as_str(),
+                    "https://other.oss-cn-qingdao.aliyuncs.com/src.zip"
+                );
+                assert_eq!(header("canonicalizedresource"), "/other/src.zip");
+                self.requests.lock().unwrap().push("HEAD".to_owned());
+                let mut builder = Builder::new()
+                    .status(200)
+                    .header("Content-Length", if self.empty { "0" } else { "25" })
+                    .header("Content-Type", "application/zip")
+                    .header("x-oss-meta-author", "tu6ge");
+                if !self.no_etag {
+                    builder = builder.header("ETag", "\"src_etag\"");
+                }
+                builder.body(String::new())
+            }
+            "POST" if query == "uploads" => {
+                self.requests.lock().unwrap().push(format!(
+                    "INIT {} author={}",
+                    header("content-type"),
+                    header("x-oss-meta-author")
+                ));
+                Builder::new()
+                    .status(200)
+                    .body("<InitiateMultipartUploadResult><UploadId>foo_upload_id</UploadId></InitiateMultipartUploadResult>".to_owned())
+            }
+            "PUT" => {
+                assert_eq!(header("x-oss-copy-source"), "/other/src.zip");
+                assert_eq!(
+                    request.headers().get("x-oss-copy-source-if-match"),
+                    (!self.no_etag)
+                        .then(|| http::HeaderValue::from_static("\"src_etag\""))
+                        .as_ref()
+                );
+                let number = query
+                    .strip_prefix("partNumber=")
+                    .and_then(|q| q.split('&').next())
+                    .unwrap()
+                    .to_owned();
+                self.requests.lock().unwrap().push(format!(
+                    "PUT {number} {}",
+                    header("x-oss-copy-source-range")
+                ));
+                if self.fail_part == Some(number.as_str()) {
+                    let body = "<Error><Code>InternalError</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                    return Err(OssService::new2(
+                        body.to_owned(),
+                        &http::StatusCode::INTERNAL_SERVER_ERROR,
+                        request.url().clone(),
+                    )
+                    .into());
+                } else if self.malformed {
+                    Builder::new()
+                        .status(200)
+                        .body("<CopyPartResult></ETag><ETag></CopyPartResult>".to_owned())
+                } else {
+                    Builder::new().status(200).body(format!(
+                        "<CopyPartResult><LastModified>2023-01-01T00:00:00.000Z</LastModified><ETag>&quot;etag{number}&quot;</ETag></CopyPartResult>"
+                    ))
+                }
+            }
+            "POST" => {
+                let body = request.body().unwrap().as_bytes().unwrap();
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(body.to_vec()).unwrap());
+                Builder::new().status(200).body(String::new())
+            }
+            "DELETE" => {
+                self.requests
+                    .lock()
+                    .unwrap()
+                    .push(format!("DELETE {query}"));
+                Builder::new().status(204).body(String::new())
+            }
+            _ => panic!("error"),
+        };
+        Ok(response.unwrap().into())
+    }
+}
@@ -0,0 +1,478 @@
+//! # 批量删除文件
+//!
+//! 一次请求最多删除 1000 个文件，超出时会自动拆分为多次请求
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::Client;
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let res = client
+//!     .delete_objects(["foo.txt", "bar.txt"], false)
+//!     .await
+//!     .unwrap();
+//! for item in res {
+//!     if let Some(code) = item.error_code() {
+//!         println!("delete {} failed: {}", item.key(), code);
+//!     }
+//! }
+//! # }
+//! ```
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/deletemultipleobjects)
+
+use std::convert::Infallible;
+
+use quick_xml::escape::escape;
+
+#[cfg(feature = "blocking")]
+use crate::builder::RcPointer;
+#[cfg(feature = "blocking")]
+use crate::client::ClientRc;
+use crate::{
+    bucket::Bucket,
+    builder::ArcPointer,
+    client::ClientArc,
+    decode::{InnerItemError, RefineDeleteResult},
+    file::{self, FileError},
+    object::{Object, ObjectList},
+    types::{object::ObjectPath, ContentMd5},
+};
+
+/// 单次请求最多删除的文件数量
+pub(crate) const MAX_DELETE_KEYS: usize = 1000;
+
+/// 待删除的文件，可以指定版本
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectIdentifier {
+    key: String,
+    version_id: Option<String>,
+}
+
+impl ObjectIdentifier {
+    /// 初始化，删除文件的当前版本
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            version_id: None,
+        }
+    }
+
+    /// 初始化，删除文件的指定版本
+    pub fn with_version(key: impl Into<String>, version_id: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            version_id: Some(version_id.into()),
+        }
+    }
+
+    /// 文件路径
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 文件版本
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+}
+
+impl From<&str> for ObjectIdentifier {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for ObjectIdentifier {
+    fn from(key: String) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<ObjectPath> for ObjectIdentifier {
+    fn from(path: ObjectPath) -> Self {
+        Self::new(path.to_string())
+    }
+}
+
+impl From<&ObjectPath> for ObjectIdentifier {
+    fn from(path: &ObjectPath) -> Self {
+        Self::new(path.to_string())
+    }
+}
+
+impl From<(&str, &str)> for ObjectIdentifier {
+    fn from((key, version_id): (&str, &str)) -> Self {
+        Self::with_version(key, version_id)
+    }
+}
+
+impl From<(String, String)> for ObjectIdentifier {
+    fn from((key, version_id): (String, String)) -> Self {
+        Self::with_version(key, version_id)
+    }
+}
+
+/// 单个文件的删除结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteResult {
+    object: ObjectIdentifier,
+    error: Option<String>,
+}
+
+impl DeleteResult {
+    /// 文件路径
+    pub fn key(&self) -> &str {
+        self.object.key()
+    }
+
+    /// 文件版本
+    pub fn version_id(&self) -> Option<&str> {
+        self.object.version_id()
+    }
+
+    /// 是否删除成功
+    pub fn is_deleted(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// 删除失败时，OSS 返回的错误码
+    pub fn error_code(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// 生成 DeleteMultipleObjects 接口的请求体
+pub(crate) fn delete_body(objects: &[ObjectIdentifier], quiet: bool) -> String {
+    let mut body =
+        format!(r#"<?xml version="1.0" encoding="UTF-8"?><Delete><Quiet>{quiet}</Quiet>"#);
+    for object in objects {
+        body.push_str("<Object><Key>");
+        body.push_str(&escape(&object.key));
+        body.push_str("</Key>");
+        if let Some(version_id) = &object.version_id {
+            body.push_str("<VersionId>");
+            body.push_str(&escape(version_id));
+            body.push_str("</VersionId>");
+        }
+        body.push_str("</Object>");
+    }
+    body.push_str("</Delete>");
+    body
+}
+
+/// 请求体的 Content-MD5
+pub(crate) fn content_md5(body: &str) -> String {
+    ContentMd5::from_body(body.as_bytes()).to_string()
+}
+
+/// 解析删除结果，响应中没有出现在 `<Error>` 里的文件均视为删除成功
+pub(crate) fn parse_delete_result(
+    xml: &str,
+    objects: Vec<ObjectIdentifier>,
+) -> Result<Vec<DeleteResult>, InnerItemError> {
+    let mut errors = DeleteErrors::default();
+    errors.decode(xml)?;
+
+    Ok(objects
+        .into_iter()
+        .map(|object| {
+            let error = errors
+                .0
+                .iter()
+                .find(|(key, version_id, _)| {
+                    *key == object.key && (version_id.is_none() || *version_id == object.version_id)
+                })
+                .map(|(_, _, code)| code.clone());
+            DeleteResult { object, error }
+        })
+        .collect())
+}
+
+/// 响应中删除失败的文件，依次为 key，version_id 和错误码
+#[derive(Default)]
+struct DeleteErrors(Vec<(String, Option<String>, String)>);
+
+impl RefineDeleteResult<Infallible> for DeleteErrors {
+    fn set_error(
+        &mut self,
+        key: &str,
+        version_id: Option<&str>,
+        code: &str,
+    ) -> Result<(), Infallible> {
+        self.0.push((
+            key.to_owned(),
+            version_id.map(ToOwned::to_owned),
+            code.to_owned(),
+        ));
+        Ok(())
+    }
+}
+
+impl ClientArc {
+    /// # 批量删除默认 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    ///
+    /// `quiet` 为 true 时，OSS 只返回删除失败的文件
+    pub async fn delete_objects<I>(
+        &self,
+        objects: I,
+        quiet: bool,
+    ) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::delete_objects(self, &self.get_bucket_base(), objects, quiet).await
+    }
+}
+
+impl Bucket {
+    /// # 批量删除当前 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    pub async fn delete_objects<I>(
+        &self,
+        objects: I,
+        quiet: bool,
+    ) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::delete_objects(self, &self.base, objects, quiet).await
+    }
+}
+
+impl<Item: Send + Sync> ObjectList<ArcPointer, Item> {
+    /// # 批量删除当前 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    pub async fn delete_objects<I>(
+        &self,
+        objects: I,
+        quiet: bool,
+    ) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::delete_objects(self, &self.bucket, objects, quiet).await
+    }
+}
+
+impl ObjectList<ArcPointer> {
+    /// # 删除列表中的全部文件
+    ///
+    /// 删除当前页的文件后，继续获取下一页并删除，直到没有更多的文件
+    pub async fn delete_all(self, quiet: bool) -> Result<Vec<DeleteResult>, FileError> {
+        let mut results = Vec::new();
+        let mut list = self;
+        loop {
+            let objects = list.object_list.iter().map(Object::path);
+            results.extend(list.delete_objects(objects, quiet).await?);
+
+            list = match list.get_next_list().await {
+                Ok(next) => next,
+                Err(e) if e.is_no_more() => return Ok(results),
+                Err(e) => return Err(e.into()),
+            };
+        }
+    }
+}
+
+impl From<&Object<ArcPointer>> for ObjectIdentifier {
+    fn from(object: &Object<ArcPointer>) -> Self {
+        Self::from(object.path())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl ClientRc {
+    /// # 批量删除默认 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    pub fn delete_objects<I>(&self, objects: I, quiet: bool) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::blocking::delete_objects(self, &self.get_bucket_base(), objects, quiet)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl Bucket<RcPointer> {
+    /// # 批量删除当前 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    pub fn delete_objects<I>(&self, objects: I, quiet: bool) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::blocking::delete_objects(self, &self.base, objects, quiet)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl ObjectList<RcPointer> {
+    /// # 批量删除当前 bucket 上的文件
+    ///
+    /// 超过 1000 个文件时，会拆分为多次请求，返回每个文件的删除结果
+    pub fn delete_objects<I>(&self, objects: I, quiet: bool) -> Result<Vec<DeleteResult>, FileError>
+    where
+        I: IntoIterator,
+        I::Item: Into<ObjectIdentifier>,
+    {
+        let objects = objects.into_iter().map(Into::into).collect();
+        file::blocking::delete_objects(self, &self.bucket, objects, quiet)
+    }
+
+    /// # 删除列表中的全部文件
+    ///
+    /// 删除当前页的文件后，继续获取下一页并删除，直到没有更多的文件
+    pub fn delete_all(mut self, quiet: bool) -> Result<Vec<DeleteResult>, FileError> {
+        let mut results = Vec::new();
+        loop {
+            let objects = self.object_list.iter().map(Object::path);
+            results.extend(self.delete_objects(objects, quiet)?);
+
+            match self.next_query() {
+                Some(query) => {
+                    self.search_query = query;
+                    self = self.get_object_list()?;
+                }
+                None => return Ok(results),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl From<&Object<RcPointer>> for ObjectIdentifier {
+    fn from(object: &Object<RcPointer>) -> Self {
+        Self::from(object.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use http::Method;
+    use reqwest::{Request, Response};
+
+    use super::*;
+    use crate::builder::{BuilderError, Middleware};
+
+    #[test]
+    fn body() {
+        let objects = [
+            ObjectIdentifier::from("a&b.txt"),
+            ObjectIdentifier::from(("c.txt", "v1")),
+        ];
+        assert_eq!(
+            delete_body(&objects, true),
+            r#"<?xml version="1.0" encoding="UTF-8"?><Delete><Quiet>true</Quiet><Object><Key>a&amp;b.txt</Key></Object><Object><Key>c.txt</Key><VersionId>v1</VersionId></Object></Delete>"#
+        );
+        assert_eq!(content_md5(""), "1B2M2Y8AsgTpgAmY7PhCfg==");
+    }
+
+    #[test]
+    fn parse_result() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<DeleteResult>
+  <Deleted><Key>a.txt</Key></Deleted>
+  <Error><Key>b&amp;c.txt</Key><Code>AccessDenied</Code><Message>m</Message></Error>
+</DeleteResult>"#;
+        let res = parse_delete_result(xml, vec!["a.txt".into(), "b&c.txt".into(), "d.txt".into()])
+            .unwrap();
+        assert_eq!(res.len(), 3);
+        assert!(res[0].is_deleted());
+        assert_eq!(res[1].key(), "b&c.txt");
+        assert!(!res[1].is_deleted());
+        assert_eq!(res[1].error_code(), Some("AccessDenied"));
+        assert!(res[2].is_deleted());
+        assert_eq!(res[2].version_id(), None);
+
+        // 结束标签在开始标签之前，不能当作删除成功
+        let xml =
+            "<DeleteResult><Error></Key><Key>a.txt<Code>AccessDenied</Code></Error></DeleteResult>";
+        parse_delete_result(xml, vec!["a.txt".into()]).unwrap_err();
+
+        let xml = "<DeleteResult><Error><Code>AccessDenied</Code></Error></DeleteResult>";
+        let err = parse_delete_result(xml, vec!["a.txt".into()]).unwrap_err();
+        assert_eq!(err.to_string(), "not found tag: Key");
+
+        let xml = "<DeleteResult><Error><Key><![CDATA[a<b.txt]]></Key><VersionId>v1</VersionId><Code>AccessDenied</Code></Error></DeleteResult>";
+        let res = parse_delete_result(
+            xml,
+            vec![("a<b.txt", "v1").into(), ("a<b.txt", "v2").into()],
+        )
+        .unwrap();
+        assert!(!res[0].is_deleted());
+        assert!(res[1].is_deleted());
+    }
+
+    /// 记录每次请求删除的文件数量
+    #[derive(Debug, Default)]
+    struct MockDelete {
+        sizes: Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl Middleware for MockDelete {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(request.method(), Method::POST);
+            assert_eq!(
+                request.url().as_str(),
+                "https://bar.oss-cn-qingdao.aliyuncs.com/?delete"
+            );
+            let body = std::str::from_utf8(request.body().unwrap().as_bytes().unwrap()).unwrap();
+            assert!(body.contains("<Quiet>true</Quiet>"));
+            assert_eq!(
+                request.headers().get("content-md5").unwrap(),
+                &content_md5(body)
+            );
+            self.sizes
+                .lock()
+                .unwrap()
+                .push(body.matches("<Object>").count());
+
+            let xml = if body.contains("<Key>key-1</Key>") {
+                "<DeleteResult><Error><Key>key-1</Key><Code>AccessDenied</Code></Error></DeleteResult>"
+            } else {
+                "<DeleteResult></DeleteResult>"
+            };
+            let response = http::Response::builder().status(200).body(xml).unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_objects() {
+        let middleware = Arc::new(MockDelete::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        let keys: Vec<_> = (0..2500).map(|i| format!("key-{i}")).collect();
+        let res = client.delete_objects(keys, true).await.unwrap();
+
+        assert_eq!(*middleware.sizes.lock().unwrap(), vec![1000, 1000, 500]);
+        assert_eq!(res.len(), 2500);
+        assert_eq!(res[1].error_code(), Some("AccessDenied"));
+        assert_eq!(res.iter().filter(|r| r.is_deleted()).count(), 2499);
+
+        let res = client.delete_objects(Vec::<String>::new(), true).await;
+        assert!(res.unwrap().is_empty());
+        assert_eq!(middleware.sizes.lock().unwrap().len(), 3);
+    }
+}
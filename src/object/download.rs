@@ -0,0 +1,571 @@
+//! # 断点续传下载
+//!
+//! [`download_to_file`] 先通过 HEAD 请求获取文件大小，再将文件拆分为多个区间并发下载到临时文件中，
+//! 下载进度保存在本地的 checkpoint 文件中，中断后再次下载时只下载缺少的区间。
+//!
+//! 下载完成后会校验 CRC64，然后将临时文件重命名为目标文件
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, object::DownloadOptions};
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let options = DownloadOptions {
+//!     part_size: 5 * 1024 * 1024,
+//!     concurrency: 8,
+//!     ..Default::default()
+//! };
+//! client
+//!     .download_to_file("videos/video.mp4".parse().unwrap(), "video.mp4", &options)
+//!     .await
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! [`download_to_file`]: crate::client::ClientArc::download_to_file
+
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{self, Display},
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use futures::{pin_mut, stream, StreamExt};
+use http::{
+    header::{IF_MATCH, RANGE},
+    HeaderValue, Method,
+};
+
+use super::checkpoint::{with_suffix, CheckpointFile, DEFAULT_CONCURRENCY, DEFAULT_PART_SIZE};
+use crate::{
+    client::ClientArc,
+    crc64::Crc64,
+    file::{FileError, Files},
+    object::ObjectMeta,
+    types::ContentRange,
+    ObjectPath,
+};
+
+/// 下载过程中文件被修改时，OSS 返回的错误码
+pub(super) const PRECONDITION_FAILED: &str = "PreconditionFailed";
+
+/// 下载的参数
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// 每个区间的大小
+    pub part_size: u64,
+    /// 同时下载的区间数量
+    pub concurrency: usize,
+    /// checkpoint 文件的路径，默认为 `{file}.checkpoint`
+    pub checkpoint: Option<PathBuf>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            checkpoint: None,
+        }
+    }
+}
+
+impl ClientArc {
+    /// # 下载文件到本地
+    ///
+    /// 中途失败时，已下载的区间会保留在临时文件 `{file}.download` 中，再次调用时从断点继续下载，
+    /// 成功后返回文件的元信息
+    pub async fn download_to_file<P: AsRef<Path>>(
+        &self,
+        path: ObjectPath,
+        file: P,
+        options: &DownloadOptions,
+    ) -> Result<ObjectMeta, DownloadError> {
+        Download::new(self, path, file.as_ref(), options)
+            .run()
+            .await
+    }
+}
+
+struct Download<'a, C> {
+    client: &'a C,
+    path: ObjectPath,
+    file: &'a Path,
+    temp: PathBuf,
+    checkpoint: PathBuf,
+    part_size: u64,
+    concurrency: usize,
+}
+
+impl<'a, C: Files<ObjectPath>> Download<'a, C> {
+    fn new(client: &'a C, path: ObjectPath, file: &'a Path, options: &DownloadOptions) -> Self {
+        Self {
+            client,
+            path,
+            file,
+            temp: with_suffix(file, ".download"),
+            checkpoint: options
+                .checkpoint
+                .clone()
+                .unwrap_or_else(|| with_suffix(file, ".checkpoint")),
+            part_size: options.part_size.max(1),
+            concurrency: options.concurrency.max(1),
+        }
+    }
+
+    async fn run(&self) -> Result<ObjectMeta, DownloadError> {
+        let meta = self.client.head_object(self.path.clone()).await?;
+
+        let mut checkpoint = Checkpoint {
+            path: self.path.as_ref().to_owned(),
+            etag: meta.etag().unwrap_or_default().to_owned(),
+            size: meta.content_length(),
+            part_size: self.part_size,
+            ..Default::default()
+        };
+
+        match Checkpoint::load(&self.checkpoint) {
+            Some(saved)
+                if saved.same_object(&checkpoint) && self.temp_len() == Some(saved.size) =>
+            {
+                checkpoint.parts = saved.parts;
+            }
+            _ => {
+                // 文件已变化或临时文件不完整，重新下载
+                File::create(&self.temp)?.set_len(checkpoint.size)?;
+                checkpoint.save(&self.checkpoint)?;
+            }
+        }
+
+        let etag = checkpoint.etag.clone();
+        let missing: Vec<_> = (0..checkpoint.part_count())
+            .filter(|index| !checkpoint.parts.contains(index))
+            .map(|index| (index, checkpoint.part_range(index)))
+            .collect();
+
+        let downloads = stream::iter(missing)
+            .map(|(index, (start, end))| {
+                let etag = &etag;
+                async move {
+                    let content = self.get_range(start, end, etag).await?;
+                    self.write_part(start, &content)?;
+                    Ok::<_, DownloadError>(index)
+                }
+            })
+            .buffer_unordered(self.concurrency);
+        pin_mut!(downloads);
+
+        while let Some(res) = downloads.next().await {
+            match res {
+                Ok(index) => {
+                    checkpoint.parts.insert(index);
+                    checkpoint.save(&self.checkpoint)?;
+                }
+                Err(e) => {
+                    if e.is_object_changed() {
+                        self.clean();
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Some(crc64) = meta.crc64() {
+            let local = self.temp_crc64()?;
+            if local != crc64 {
+                self.clean();
+                return Err(DownloadError::new(DownloadErrorKind::Crc64Mismatch {
+                    expected: crc64,
+                    actual: local,
+                }));
+            }
+        }
+
+        fs::rename(&self.temp, self.file)?;
+        fs::remove_file(&self.checkpoint)?;
+
+        Ok(meta)
+    }
+
+    /// 下载 `start..=end` 区间的内容，文件被修改时返回 PreconditionFailed
+    async fn get_range(&self, start: u64, end: u64, etag: &str) -> Result<Vec<u8>, DownloadError> {
+        let (url, resource) = self
+            .client
+            .get_std_with_path(self.path.clone())
+            .ok_or(FileError::not_found_canonicalized())?;
+
+        let mut headers = vec![(RANGE, HeaderValue::from(ContentRange::from(start..end)))];
+        if !etag.is_empty() {
+            let etag = HeaderValue::from_str(etag)
+                .map_err(|_| DownloadError::new(DownloadErrorKind::ObjectChanged))?;
+            headers.push((IF_MATCH, etag));
+        }
+
+        let content = self
+            .client
+            .builder_with_header(Method::GET, url, resource, headers)
+            .map_err(FileError::from)?
+            .send_adjust_error()
+            .await
+            .map_err(|e| match FileError::from(e) {
+                e if e.oss_code() == Some(PRECONDITION_FAILED) => {
+                    DownloadError::new(DownloadErrorKind::ObjectChanged)
+                }
+                e => e.into(),
+            })?
+            .bytes()
+            .await
+            .map_err(FileError::from)?;
+
+        if content.len() as u64 != end - start + 1 {
+            return Err(DownloadError::new(DownloadErrorKind::ObjectChanged));
+        }
+        Ok(content.to_vec())
+    }
+
+    fn write_part(&self, offset: u64, content: &[u8]) -> io::Result<()> {
+        let mut file = OpenOptions::new().write(true).open(&self.temp)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(content)
+    }
+
+    fn temp_len(&self) -> Option<u64> {
+        fs::metadata(&self.temp).ok().map(|meta| meta.len())
+    }
+
+    fn temp_crc64(&self) -> io::Result<u64> {
+        let mut file = File::open(&self.temp)?;
+        let mut crc = Crc64::new();
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            match file.read(&mut buf)? {
+                0 => break,
+                len => crc.update(&buf[..len]),
+            }
+        }
+        Ok(crc.get())
+    }
+
+    /// 删除临时文件和 checkpoint，下次重新下载
+    fn clean(&self) {
+        let _ = fs::remove_file(&self.temp);
+        let _ = fs::remove_file(&self.checkpoint);
+    }
+}
+
+/// checkpoint 文件中保存的下载进度
+///
+/// 每行一个字段，如 `etag=xxx`，已完成的区间为 `part={index}`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Checkpoint {
+    path: String,
+    etag: String,
+    size: u64,
+    part_size: u64,
+    parts: BTreeSet<u64>,
+}
+
+impl CheckpointFile for Checkpoint {}
+
+impl Checkpoint {
+    /// 是否为同一个文件的下载进度
+    fn same_object(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.etag == other.etag
+            && self.size == other.size
+            && self.part_size == other.part_size
+    }
+
+    fn part_count(&self) -> u64 {
+        (self.size + self.part_size - 1) / self.part_size
+    }
+
+    /// 第 index 个区间的起止位置（包含 end）
+    fn part_range(&self, index: u64) -> (u64, u64) {
+        let start = index * self.part_size;
+        let end = (start + self.part_size).min(self.size) - 1;
+        (start, end)
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "path={}", self.path)?;
+        writeln!(f, "etag={}", self.etag)?;
+        writeln!(f, "size={}", self.size)?;
+        writeln!(f, "part_size={}", self.part_size)?;
+        for index in self.parts.iter() {
+            writeln!(f, "part={index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Checkpoint {
+    type Err = DownloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DownloadError::new(DownloadErrorKind::InvalidCheckpoint);
+
+        let mut checkpoint = Self::default();
+        for line in s.lines() {
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            match key {
+                "path" => checkpoint.path = value.to_owned(),
+                "etag" => checkpoint.etag = value.to_owned(),
+                "size" => checkpoint.size = value.parse().map_err(|_| invalid())?,
+                "part_size" => checkpoint.part_size = value.parse().map_err(|_| invalid())?,
+                "part" => {
+                    checkpoint
+                        .parts
+                        .insert(value.parse().map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// 下载文件的错误
+#[derive(Debug)]
+pub struct DownloadError {
+    kind: DownloadErrorKind,
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum DownloadErrorKind {
+    Io(io::Error),
+    File(FileError),
+    ObjectChanged,
+    Crc64Mismatch { expected: u64, actual: u64 },
+    InvalidCheckpoint,
+}
+
+impl DownloadError {
+    fn new(kind: DownloadErrorKind) -> Self {
+        Self { kind }
+    }
+
+    fn is_object_changed(&self) -> bool {
+        matches!(self.kind, DownloadErrorKind::ObjectChanged)
+    }
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DownloadErrorKind::*;
+        match &self.kind {
+            Io(_) => "io error".fmt(f),
+            File(e) => e.fmt(f),
+            ObjectChanged => "object changed during download".fmt(f),
+            Crc64Mismatch { expected, actual } => {
+                write!(f, "crc64 mismatch, expected {expected}, actual {actual}")
+            }
+            InvalidCheckpoint => "invalid checkpoint file".fmt(f),
+        }
+    }
+}
+
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use DownloadErrorKind::*;
+        match &self.kind {
+            Io(e) => Some(e),
+            File(e) => Some(e),
+            ObjectChanged | Crc64Mismatch { .. } | InvalidCheckpoint => None,
+        }
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(value: io::Error) -> Self {
+        Self::new(DownloadErrorKind::Io(value))
+    }
+}
+
+impl From<FileError> for DownloadError {
+    fn from(value: FileError) -> Self {
+        Self::new(DownloadErrorKind::File(value))
+    }
+}
+
+impl From<DownloadError> for io::Error {
+    fn from(DownloadError { kind }: DownloadError) -> Self {
+        use DownloadErrorKind::*;
+        match kind {
+            Io(e) => e,
+            File(e) => e.into(),
+            ObjectChanged | Crc64Mismatch { .. } | InvalidCheckpoint => {
+                Self::new(io::ErrorKind::InvalidData, DownloadError { kind })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use http::{Method, StatusCode};
+    use reqwest::{Request, Response};
+
+    use super::*;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        errors::OssService,
+        Client,
+    };
+
+    /// 模拟 OSS 上 25 个字节的文件，`fail_part` 为下载失败的区间起始位置
+    #[derive(Debug, Default)]
+    struct MockObject {
+        ranges: Mutex<Vec<String>>,
+        fail_part: Option<u64>,
+        crc64: Option<u64>,
+    }
+
+    const CONTENT: &[u8] = b"0123456789abcdefghijklmno";
+
+    #[async_trait]
+    impl Middleware for MockObject {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let mut crc = Crc64::new();
+            crc.update(CONTENT);
+            let response = http::Response::builder()
+                .status(200)
+                .header("ETag", "\"etag\"")
+                .header(
+                    "x-oss-hash-crc64ecma",
+                    self.crc64.unwrap_or(crc.get()).to_string(),
+                );
+
+            if request.method() == Method::HEAD {
+                let response = response.header("content-length", CONTENT.len().to_string());
+                return Ok(Response::from(response.body(Vec::new()).unwrap()));
+            }
+
+            assert_eq!(request.headers().get("If-Match").unwrap(), "\"etag\"");
+            let range = request.headers().get("Range").unwrap().to_str().unwrap();
+            self.ranges.lock().unwrap().push(range.to_owned());
+
+            let (start, end) = range.trim_start_matches("bytes=").split_once('-').unwrap();
+            let (start, end): (usize, usize) = (start.parse().unwrap(), end.parse().unwrap());
+            if self.fail_part == Some(start as u64) {
+                let body = "<Error><Code>InternalError</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                let url = request.url().clone();
+                return Err(OssService::new2(
+                    body.to_owned(),
+                    &StatusCode::INTERNAL_SERVER_ERROR,
+                    url,
+                )
+                .into());
+            }
+
+            Ok(Response::from(
+                response.body(CONTENT[start..=end].to_vec()).unwrap(),
+            ))
+        }
+    }
+
+    /// 临时文件名带上进程 id，避免同时运行的测试互相覆盖
+    fn temp_file(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("{name}-{}", std::process::id()))
+    }
+
+    fn options() -> DownloadOptions {
+        DownloadOptions {
+            part_size: 10,
+            concurrency: 1,
+            checkpoint: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn download() {
+        let file = temp_file("aliyun-oss-client-download");
+        let middleware = Arc::new(MockObject {
+            fail_part: Some(10),
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+
+        let err = client
+            .download_to_file("abc.bin".parse().unwrap(), &file, &options())
+            .await
+            .unwrap_err();
+        let source = err.source().unwrap().downcast_ref::<FileError>().unwrap();
+        assert_eq!(source.oss_code(), Some("InternalError"));
+        assert!(!file.exists());
+
+        let checkpoint = Checkpoint::load(&with_suffix(&file, ".checkpoint")).unwrap();
+        assert_eq!(checkpoint.parts, BTreeSet::from([0]));
+
+        // 从断点继续下载
+        let middleware = Arc::new(MockObject::default());
+        let client = Client::test_init().middleware(middleware.clone());
+        let meta = client
+            .download_to_file("abc.bin".parse().unwrap(), &file, &options())
+            .await
+            .unwrap();
+        assert_eq!(meta.content_length(), 25);
+        assert_eq!(
+            *middleware.ranges.lock().unwrap(),
+            ["bytes=10-19", "bytes=20-24"]
+        );
+        assert_eq!(fs::read(&file).unwrap(), CONTENT);
+        assert!(!with_suffix(&file, ".checkpoint").exists());
+        assert!(!with_suffix(&file, ".download").exists());
+        fs::remove_file(file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn crc64_mismatch() {
+        let file = temp_file("aliyun-oss-client-download-crc");
+        let middleware = Arc::new(MockObject {
+            crc64: Some(1),
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware);
+
+        let err = client
+            .download_to_file("abc.bin".parse().unwrap(), &file, &options())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("crc64 mismatch"));
+        assert!(!file.exists());
+        assert!(!with_suffix(&file, ".download").exists());
+    }
+
+    #[test]
+    fn checkpoint() {
+        let checkpoint = Checkpoint {
+            path: "abc".to_owned(),
+            etag: "\"etag\"".to_owned(),
+            size: 25,
+            part_size: 10,
+            parts: BTreeSet::from([0, 2]),
+        };
+        assert_eq!(checkpoint.part_count(), 3);
+        assert_eq!(checkpoint.part_range(0), (0, 9));
+        assert_eq!(checkpoint.part_range(2), (20, 24));
+
+        let file = temp_file("aliyun-oss-client-download-checkpoint");
+        checkpoint.save(&file).unwrap();
+        assert_eq!(Checkpoint::load(&file).unwrap(), checkpoint);
+        fs::remove_file(file).unwrap();
+
+        let err = "size=abc".parse::<Checkpoint>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid checkpoint file");
+    }
+}
@@ -0,0 +1,406 @@
+//! # Object 元信息
+//!
+//! 解析 HeadObject 和 GetObjectMeta 接口返回的 headers
+//!
+//! [HeadObject 文档](https://help.aliyun.com/zh/oss/developer-reference/headobject)
+//! [GetObjectMeta 文档](https://help.aliyun.com/zh/oss/developer-reference/getobjectmeta)
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+};
+
+use chrono::{DateTime, Utc};
+use http::{
+    header::{ToStrError, CONTENT_LENGTH, CONTENT_TYPE, ETAG, LAST_MODIFIED},
+    HeaderMap,
+};
+
+use super::StorageClass;
+
+const USER_META_PREFIX: &str = "x-oss-meta-";
+const STORAGE_CLASS: &str = "x-oss-storage-class";
+const HASH_CRC64: &str = "x-oss-hash-crc64ecma";
+const VERSION_ID: &str = "x-oss-version-id";
+const RESTORE: &str = "x-oss-restore";
+const SERVER_SIDE_ENCRYPTION: &str = "x-oss-server-side-encryption";
+const SERVER_SIDE_ENCRYPTION_KEY_ID: &str = "x-oss-server-side-encryption-key-id";
+const SERVER_SIDE_DATA_ENCRYPTION: &str = "x-oss-server-side-data-encryption";
+const OBJECT_TYPE: &str = "x-oss-object-type";
+const NEXT_APPEND_POSITION: &str = "x-oss-next-append-position";
+const LAST_ACCESS_TIME: &str = "x-oss-last-access-time";
+
+/// # Object 的元信息
+///
+/// 由 [`head_object`] 或 [`get_object_meta`] 返回，
+/// `get_object_meta` 只返回部分信息，其余字段为 `None`
+///
+/// [`head_object`]: crate::file::Files::head_object
+/// [`get_object_meta`]: crate::file::Files::get_object_meta
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObjectMeta {
+    content_length: u64,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<DateTime<Utc>>,
+    last_access_time: Option<DateTime<Utc>>,
+    storage_class: Option<StorageClass>,
+    user_meta: BTreeMap<String, String>,
+    crc64: Option<u64>,
+    version_id: Option<String>,
+    restore: Option<RestoreState>,
+    server_side_encryption: Option<String>,
+    server_side_encryption_key_id: Option<String>,
+    server_side_data_encryption: Option<String>,
+    object_type: Option<String>,
+    next_append_position: Option<u64>,
+}
+
+/// 归档类型文件的解冻状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RestoreState {
+    /// 正在解冻
+    Ongoing,
+    /// 解冻完成，包含解冻状态的过期时间
+    Restored(Option<DateTime<Utc>>),
+}
+
+impl RestoreState {
+    /// 解析 `x-oss-restore` 的值，如 `ongoing-request="false", expiry-date="Sun, 16 Apr 2017 08:12:33 GMT"`
+    fn new(value: &str) -> Option<Self> {
+        let field = |key: &str| {
+            let (_, rest) = value.split_once(key)?;
+            let rest = rest.strip_prefix("=\"")?;
+            rest.split_once('"').map(|(val, _)| val)
+        };
+
+        match field("ongoing-request")? {
+            "true" => Some(Self::Ongoing),
+            _ => Some(Self::Restored(field("expiry-date").and_then(|val| {
+                DateTime::parse_from_rfc2822(val)
+                    .ok()
+                    .map(|d| d.with_timezone(&Utc))
+            }))),
+        }
+    }
+}
+
+impl ObjectMeta {
+    /// 从响应的 headers 中解析元信息
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, InvalidObjectMeta> {
+        let get = |name: &str| -> Result<Option<&str>, InvalidObjectMeta> {
+            headers
+                .get(name)
+                .map(|value| value.to_str())
+                .transpose()
+                .map_err(|e| InvalidObjectMeta::new(name, "", e))
+        };
+        let string = |name: &str| get(name).map(|v| v.map(ToOwned::to_owned));
+        let number = |name: &str| -> Result<Option<u64>, InvalidObjectMeta> {
+            get(name)?
+                .map(|v| v.parse().map_err(|e| InvalidObjectMeta::new(name, v, e)))
+                .transpose()
+        };
+        let date = |name: &str| -> Result<Option<DateTime<Utc>>, InvalidObjectMeta> {
+            get(name)?
+                .map(|v| {
+                    DateTime::parse_from_rfc2822(v)
+                        .map(|d| d.with_timezone(&Utc))
+                        .map_err(|e| InvalidObjectMeta::new(name, v, e))
+                })
+                .transpose()
+        };
+
+        // OSS 新增的存储类型无法识别时忽略，不影响其他元信息的解析
+        let storage_class = get(STORAGE_CLASS)?.and_then(StorageClass::new);
+
+        let mut user_meta = BTreeMap::new();
+        for (name, value) in headers {
+            if let Some(key) = name.as_str().strip_prefix(USER_META_PREFIX) {
+                let value = value
+                    .to_str()
+                    .map_err(|e| InvalidObjectMeta::new(name.as_str(), "", e))?;
+                user_meta.insert(key.to_owned(), value.to_owned());
+            }
+        }
+
+        Ok(Self {
+            content_length: number(CONTENT_LENGTH.as_str())?.unwrap_or_default(),
+            content_type: string(CONTENT_TYPE.as_str())?,
+            etag: string(ETAG.as_str())?,
+            last_modified: date(LAST_MODIFIED.as_str())?,
+            last_access_time: date(LAST_ACCESS_TIME)?,
+            storage_class,
+            user_meta,
+            crc64: number(HASH_CRC64)?,
+            version_id: string(VERSION_ID)?,
+            restore: get(RESTORE)?.and_then(RestoreState::new),
+            server_side_encryption: string(SERVER_SIDE_ENCRYPTION)?,
+            server_side_encryption_key_id: string(SERVER_SIDE_ENCRYPTION_KEY_ID)?,
+            server_side_data_encryption: string(SERVER_SIDE_DATA_ENCRYPTION)?,
+            object_type: string(OBJECT_TYPE)?,
+            next_append_position: number(NEXT_APPEND_POSITION)?,
+        })
+    }
+
+    /// 文件大小，单位字节
+    pub fn content_length(&self) -> u64 {
+        self.content_length
+    }
+
+    /// 文件的 mime 类型
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// 文件的 etag
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// 最后修改时间
+    pub fn last_modified(&self) -> Option<&DateTime<Utc>> {
+        self.last_modified.as_ref()
+    }
+
+    /// 最后访问时间，需要 bucket 开启访问跟踪
+    pub fn last_access_time(&self) -> Option<&DateTime<Utc>> {
+        self.last_access_time.as_ref()
+    }
+
+    /// 存储类型
+    pub fn storage_class(&self) -> Option<&StorageClass> {
+        self.storage_class.as_ref()
+    }
+
+    /// 所有的自定义元信息，key 不包含 `x-oss-meta-` 前缀
+    pub fn user_meta(&self) -> &BTreeMap<String, String> {
+        &self.user_meta
+    }
+
+    /// 读取一条自定义元信息，key 不包含 `x-oss-meta-` 前缀
+    pub fn get_user_meta(&self, key: &str) -> Option<&str> {
+        self.user_meta.get(&key.to_lowercase()).map(String::as_str)
+    }
+
+    /// 文件的 CRC64 值
+    pub fn crc64(&self) -> Option<u64> {
+        self.crc64
+    }
+
+    /// 版本 id，bucket 开启版本控制时才有
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// 归档类型文件的解冻状态
+    pub fn restore(&self) -> Option<&RestoreState> {
+        self.restore.as_ref()
+    }
+
+    /// 服务端加密方式，如 `AES256`，`KMS`
+    pub fn server_side_encryption(&self) -> Option<&str> {
+        self.server_side_encryption.as_deref()
+    }
+
+    /// KMS 加密时使用的密钥 id
+    pub fn server_side_encryption_key_id(&self) -> Option<&str> {
+        self.server_side_encryption_key_id.as_deref()
+    }
+
+    /// KMS 加密时使用的数据加密算法
+    pub fn server_side_data_encryption(&self) -> Option<&str> {
+        self.server_side_data_encryption.as_deref()
+    }
+
+    /// 文件类型，如 `Normal`，`Appendable`，`Multipart`，`Symlink`
+    pub fn object_type(&self) -> Option<&str> {
+        self.object_type.as_deref()
+    }
+
+    /// 追加上传时，下次追加的位置
+    pub fn next_append_position(&self) -> Option<u64> {
+        self.next_append_position
+    }
+}
+
+impl TryFrom<&HeaderMap> for ObjectMeta {
+    type Error = InvalidObjectMeta;
+    fn try_from(headers: &HeaderMap) -> Result<Self, Self::Error> {
+        Self::from_headers(headers)
+    }
+}
+
+/// 解析 Object 元信息时的错误
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidObjectMeta {
+    name: String,
+    source: String,
+    kind: InvalidObjectMetaKind,
+}
+
+impl InvalidObjectMeta {
+    fn new<K: Into<InvalidObjectMetaKind>>(name: &str, source: &str, kind: K) -> Self {
+        Self {
+            name: name.to_owned(),
+            source: source.to_owned(),
+            kind: kind.into(),
+        }
+    }
+}
+
+impl Display for InvalidObjectMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse {} failed, gived str: {}", self.name, self.source)
+    }
+}
+
+impl Error for InvalidObjectMeta {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use InvalidObjectMetaKind::*;
+        match &self.kind {
+            ToStr(e) => Some(e),
+            Number(e) => Some(e),
+            Date(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum InvalidObjectMetaKind {
+    ToStr(ToStrError),
+    Number(ParseIntError),
+    Date(chrono::ParseError),
+}
+
+impl From<ToStrError> for InvalidObjectMetaKind {
+    fn from(value: ToStrError) -> Self {
+        Self::ToStr(value)
+    }
+}
+
+impl From<ParseIntError> for InvalidObjectMetaKind {
+    fn from(value: ParseIntError) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<chrono::ParseError> for InvalidObjectMetaKind {
+    fn from(value: chrono::ParseError) -> Self {
+        Self::Date(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use http::{HeaderMap, HeaderValue};
+
+    use super::*;
+
+    fn headers(list: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in list {
+            map.insert(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn from_headers() {
+        let map = headers(&[
+            ("content-length", "344606"),
+            ("content-type", "image/jpeg"),
+            ("etag", "\"fba9dede5f27731c9771645a3986****\""),
+            ("last-modified", "Fri, 24 Feb 2012 06:07:48 GMT"),
+            ("x-oss-storage-class", "Archive"),
+            ("x-oss-meta-Author", "tu6ge"),
+            ("x-oss-hash-crc64ecma", "3161812495027033016"),
+            (
+                "x-oss-version-id",
+                "CAEQNhiBgMDJgZCA0BYiIDc4MGZjZGI2OTBjOTRmNTE5NmU5NmFhZjhjYmY0****",
+            ),
+            (
+                "x-oss-restore",
+                "ongoing-request=\"false\", expiry-date=\"Sun, 16 Apr 2017 08:12:33 GMT\"",
+            ),
+            ("x-oss-server-side-encryption", "KMS"),
+            ("x-oss-server-side-encryption-key-id", "key-id"),
+            ("x-oss-object-type", "Appendable"),
+            ("x-oss-next-append-position", "344606"),
+        ]);
+
+        let meta = ObjectMeta::from_headers(&map).unwrap();
+        assert_eq!(meta.content_length(), 344606);
+        assert_eq!(meta.content_type(), Some("image/jpeg"));
+        assert_eq!(meta.etag(), Some("\"fba9dede5f27731c9771645a3986****\""));
+        assert_eq!(
+            meta.last_modified(),
+            Some(&Utc.with_ymd_and_hms(2012, 2, 24, 6, 7, 48).unwrap())
+        );
+        assert_eq!(meta.last_access_time(), None);
+        assert_eq!(meta.storage_class(), Some(&StorageClass::ARCHIVE));
+        assert_eq!(meta.get_user_meta("author"), Some("tu6ge"));
+        assert_eq!(meta.get_user_meta("Author"), Some("tu6ge"));
+        assert_eq!(meta.user_meta().len(), 1);
+        assert_eq!(meta.crc64(), Some(3161812495027033016));
+        assert!(meta.version_id().unwrap().starts_with("CAEQ"));
+        assert_eq!(
+            meta.restore(),
+            Some(&RestoreState::Restored(Some(
+                Utc.with_ymd_and_hms(2017, 4, 16, 8, 12, 33).unwrap()
+            )))
+        );
+        assert_eq!(meta.server_side_encryption(), Some("KMS"));
+        assert_eq!(meta.server_side_encryption_key_id(), Some("key-id"));
+        assert_eq!(meta.server_side_data_encryption(), None);
+        assert_eq!(meta.object_type(), Some("Appendable"));
+        assert_eq!(meta.next_append_position(), Some(344606));
+
+        let meta = ObjectMeta::from_headers(&HeaderMap::new()).unwrap();
+        assert_eq!(meta, ObjectMeta::default());
+    }
+
+    #[test]
+    fn restore_state() {
+        assert_eq!(
+            RestoreState::new("ongoing-request=\"true\""),
+            Some(RestoreState::Ongoing)
+        );
+        assert_eq!(
+            RestoreState::new("ongoing-request=\"false\""),
+            Some(RestoreState::Restored(None))
+        );
+        assert_eq!(RestoreState::new("foo"), None);
+    }
+
+    #[test]
+    fn invalid_headers() {
+        let err = ObjectMeta::from_headers(&headers(&[("content-length", "abc")])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse content-length failed, gived str: abc"
+        );
+        assert!(err.source().is_some());
+
+        let err =
+            ObjectMeta::from_headers(&headers(&[("last-modified", "2012-02-24")])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parse last-modified failed, gived str: 2012-02-24"
+        );
+
+        let meta = ObjectMeta::from_headers(&headers(&[("x-oss-storage-class", "foo")])).unwrap();
+        assert_eq!(meta.storage_class(), None);
+
+        let meta =
+            ObjectMeta::from_headers(&headers(&[("x-oss-storage-class", "DeepColdArchive")]))
+                .unwrap();
+        assert_eq!(meta.storage_class(), Some(&StorageClass::DEEP_COLD_ARCHIVE));
+    }
+}
@@ -0,0 +1,726 @@
+//! # 管理未完成的分片上传任务
+//!
+//! 分片上传中断后，已上传的分片会一直占用存储空间，可以通过 [`list_multipart_uploads`] 找到这些任务，
+//! 通过 [`list_parts`] 查看已上传的分片，或者使用 [`abort_stale_uploads`] 清理长时间未完成的任务
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::Client;
+//! use chrono::Duration;
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let aborted = client.abort_stale_uploads(Duration::days(7)).await.unwrap();
+//! for upload in aborted {
+//!     println!("aborted {} {}", upload.key(), upload.upload_id());
+//! }
+//! # }
+//! ```
+//!
+//! [`list_multipart_uploads`]: crate::client::ClientArc::list_multipart_uploads
+//! [`list_parts`]: crate::client::ClientArc::list_parts
+//! [`abort_stale_uploads`]: crate::client::ClientArc::abort_stale_uploads
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/listmultipartuploads)
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+};
+
+use async_stream::try_stream;
+use chrono::{DateTime, Duration, Utc};
+use futures::{pin_mut, Stream, TryStreamExt};
+use http::Method;
+use quick_xml::escape::unescape;
+
+use crate::{
+    client::ClientArc,
+    decode::{ListError, RefinePart, RefinePartList, RefineUpload, RefineUploadList},
+    file::{AlignBuilder, FileError, GetStdWithPath},
+    types::CanonicalizedResource,
+};
+
+/// 分片上传任务已完成或已取消时，OSS 返回的错误码
+const NO_SUCH_UPLOAD: &str = "NoSuchUpload";
+
+/// 列出分片上传任务的查询条件
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListUploadsQuery {
+    /// 只列出以此为前缀的文件的任务
+    pub prefix: Option<String>,
+    /// 从此文件之后开始列出
+    pub key_marker: Option<String>,
+    /// 与 `key_marker` 一起使用，从此任务之后开始列出
+    pub upload_id_marker: Option<String>,
+    /// 每页的最大数量，OSS 默认为 1000
+    pub max_uploads: Option<u16>,
+}
+
+impl ListUploadsQuery {
+    fn to_query(&self) -> String {
+        let mut query = url::form_urlencoded::Serializer::new(String::from("uploads"));
+        let params = [
+            ("prefix", self.prefix.clone()),
+            ("key-marker", self.key_marker.clone()),
+            ("upload-id-marker", self.upload_id_marker.clone()),
+            ("max-uploads", self.max_uploads.map(|n| n.to_string())),
+        ];
+        for (key, value) in params {
+            if let Some(value) = value {
+                query.append_pair(key, &value);
+            }
+        }
+        query.finish()
+    }
+}
+
+/// 单个未完成的分片上传任务
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultipartUpload {
+    key: String,
+    upload_id: String,
+    initiated: DateTime<Utc>,
+}
+
+impl MultipartUpload {
+    /// 文件路径
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 任务 id
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// 任务创建时间
+    pub fn initiated(&self) -> &DateTime<Utc> {
+        &self.initiated
+    }
+}
+
+impl RefineUpload<InvalidMultipart> for MultipartUpload {
+    fn set_key(&mut self, key: &str) -> Result<(), InvalidMultipart> {
+        self.key = key.to_owned();
+        Ok(())
+    }
+
+    fn set_upload_id(&mut self, upload_id: &str) -> Result<(), InvalidMultipart> {
+        self.upload_id = upload_id.to_owned();
+        Ok(())
+    }
+
+    fn set_initiated(&mut self, initiated: &str) -> Result<(), InvalidMultipart> {
+        self.initiated = initiated.parse().map_err(|e| InvalidMultipart {
+            source: initiated.to_owned(),
+            kind: InvalidMultipartKind::Initiated(e),
+        })?;
+        Ok(())
+    }
+}
+
+/// 一页分片上传任务
+#[derive(Clone, Default)]
+pub struct MultipartUploadList {
+    client: ClientArc,
+    query: ListUploadsQuery,
+    bucket: String,
+    next_key_marker: String,
+    next_upload_id_marker: String,
+    is_truncated: bool,
+    uploads: Vec<MultipartUpload>,
+}
+
+impl fmt::Debug for MultipartUploadList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartUploadList")
+            .field("bucket", &self.bucket)
+            .field("query", &self.query)
+            .field("next_key_marker", &self.next_key_marker)
+            .field("next_upload_id_marker", &self.next_upload_id_marker)
+            .field("is_truncated", &self.is_truncated)
+            .field("uploads", &self.uploads)
+            .finish()
+    }
+}
+
+impl RefineUploadList<MultipartUpload, InvalidMultipart> for MultipartUploadList {
+    fn set_bucket(&mut self, bucket: &str) -> Result<(), InvalidMultipart> {
+        self.bucket = bucket.to_owned();
+        Ok(())
+    }
+
+    fn set_next_key_marker(&mut self, marker: &str) -> Result<(), InvalidMultipart> {
+        self.next_key_marker = marker.to_owned();
+        Ok(())
+    }
+
+    fn set_next_upload_id_marker(&mut self, marker: &str) -> Result<(), InvalidMultipart> {
+        self.next_upload_id_marker = marker.to_owned();
+        Ok(())
+    }
+
+    fn set_is_truncated(&mut self, is_truncated: bool) -> Result<(), InvalidMultipart> {
+        self.is_truncated = is_truncated;
+        Ok(())
+    }
+
+    fn set_list(&mut self, list: Vec<MultipartUpload>) -> Result<(), InvalidMultipart> {
+        self.uploads = list;
+        Ok(())
+    }
+}
+
+impl MultipartUploadList {
+    /// bucket 名称
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// 当前页的任务
+    pub fn uploads(&self) -> &[MultipartUpload] {
+        &self.uploads
+    }
+
+    /// 转化为任务列表
+    pub fn into_uploads(self) -> Vec<MultipartUpload> {
+        self.uploads
+    }
+
+    /// 是否还有下一页
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+
+    /// 下一页的查询条件，没有下一页时返回 `None`
+    pub fn next_query(&self) -> Option<ListUploadsQuery> {
+        if !self.is_truncated {
+            return None;
+        }
+        Some(ListUploadsQuery {
+            key_marker: Some(self.next_key_marker.clone()),
+            upload_id_marker: Some(self.next_upload_id_marker.clone()),
+            ..self.query.clone()
+        })
+    }
+
+    /// # 将列表转化为 stream, 返回第二页，第三页... 的内容
+    ///
+    /// 没有下一页时，stream 结束
+    pub fn into_stream(self) -> impl Stream<Item = Result<Self, FileError>> {
+        try_stream! {
+            let mut query = self.next_query();
+            while let Some(q) = query {
+                let list = self.client.list_multipart_uploads(q).await?;
+                query = list.next_query();
+                yield list;
+            }
+        }
+    }
+}
+
+/// 单个已上传的分片
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartInfo {
+    part_number: u32,
+    etag: String,
+    size: u64,
+    last_modified: DateTime<Utc>,
+}
+
+impl PartInfo {
+    /// 分片编号
+    pub fn part_number(&self) -> u32 {
+        self.part_number
+    }
+
+    /// 分片的 etag
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// 分片大小
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// 分片上传时间
+    pub fn last_modified(&self) -> &DateTime<Utc> {
+        &self.last_modified
+    }
+}
+
+impl RefinePart<InvalidMultipart> for PartInfo {
+    fn set_part_number(&mut self, part_number: &str) -> Result<(), InvalidMultipart> {
+        self.part_number = part_number.parse().map_err(|e| InvalidMultipart {
+            source: part_number.to_owned(),
+            kind: InvalidMultipartKind::PartNumber(e),
+        })?;
+        Ok(())
+    }
+
+    fn set_last_modified(&mut self, last_modified: &str) -> Result<(), InvalidMultipart> {
+        self.last_modified = last_modified.parse().map_err(|e| InvalidMultipart {
+            source: last_modified.to_owned(),
+            kind: InvalidMultipartKind::LastModified(e),
+        })?;
+        Ok(())
+    }
+
+    fn set_etag(&mut self, etag: &str) -> Result<(), InvalidMultipart> {
+        // etag 中的引号可能被转义为 `&quot;`
+        let etag = unescape(etag).map_err(|e| InvalidMultipart {
+            source: etag.to_owned(),
+            kind: InvalidMultipartKind::Etag(e),
+        })?;
+        self.etag = etag.trim_matches('"').to_owned();
+        Ok(())
+    }
+
+    fn set_size(&mut self, size: &str) -> Result<(), InvalidMultipart> {
+        self.size = size.parse().map_err(|e| InvalidMultipart {
+            source: size.to_owned(),
+            kind: InvalidMultipartKind::Size(e),
+        })?;
+        Ok(())
+    }
+}
+
+/// 一页已上传的分片
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartList {
+    key: String,
+    upload_id: String,
+    next_part_number_marker: u32,
+    is_truncated: bool,
+    parts: Vec<PartInfo>,
+}
+
+impl RefinePartList<PartInfo, InvalidMultipart> for PartList {
+    fn set_key(&mut self, key: &str) -> Result<(), InvalidMultipart> {
+        self.key = key.to_owned();
+        Ok(())
+    }
+
+    fn set_upload_id(&mut self, upload_id: &str) -> Result<(), InvalidMultipart> {
+        self.upload_id = upload_id.to_owned();
+        Ok(())
+    }
+
+    fn set_next_part_number_marker(&mut self, marker: &str) -> Result<(), InvalidMultipart> {
+        self.next_part_number_marker = marker.parse().map_err(|e| InvalidMultipart {
+            source: marker.to_owned(),
+            kind: InvalidMultipartKind::PartNumber(e),
+        })?;
+        Ok(())
+    }
+
+    fn set_is_truncated(&mut self, is_truncated: bool) -> Result<(), InvalidMultipart> {
+        self.is_truncated = is_truncated;
+        Ok(())
+    }
+
+    fn set_list(&mut self, list: Vec<PartInfo>) -> Result<(), InvalidMultipart> {
+        self.parts = list;
+        Ok(())
+    }
+}
+
+impl PartList {
+    /// 文件路径
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 任务 id
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// 当前页的分片
+    pub fn parts(&self) -> &[PartInfo] {
+        &self.parts
+    }
+
+    /// 转化为分片列表
+    pub fn into_parts(self) -> Vec<PartInfo> {
+        self.parts
+    }
+
+    /// 下一页的 `part-number-marker`，没有下一页时返回 `None`
+    pub fn next_marker(&self) -> Option<u32> {
+        self.is_truncated.then_some(self.next_part_number_marker)
+    }
+}
+
+impl ClientArc {
+    /// # 列出默认 bucket 中未完成的分片上传任务
+    ///
+    /// 返回第一页的数据，之后的数据可以通过 [`MultipartUploadList::into_stream`] 获取
+    pub async fn list_multipart_uploads(
+        &self,
+        query: ListUploadsQuery,
+    ) -> Result<MultipartUploadList, FileError> {
+        let bucket = self.get_bucket_base();
+        let mut url = bucket.to_url();
+        url.set_query(Some(&query.to_query()));
+        let canonicalized = CanonicalizedResource::new(format!("/{}/?uploads", bucket.name()));
+
+        let xml = self
+            .builder(Method::GET, url, canonicalized)?
+            .send_adjust_error()
+            .await?
+            .text()
+            .await?;
+
+        let mut list = MultipartUploadList {
+            client: self.clone(),
+            query,
+            ..Default::default()
+        };
+        list.decode(&xml, |_| Some(MultipartUpload::default()))?;
+        Ok(list)
+    }
+
+    /// # 列出某个分片上传任务已上传的分片
+    ///
+    /// `part_number_marker` 为上一页返回的 [`PartList::next_marker`]
+    pub async fn list_parts<P>(
+        &self,
+        path: P,
+        upload_id: &str,
+        part_number_marker: Option<u32>,
+    ) -> Result<PartList, FileError>
+    where
+        Self: GetStdWithPath<P>,
+    {
+        list_parts(self, path, upload_id, part_number_marker).await
+    }
+
+    /// # 取消分片上传任务，并删除已上传的分片
+    pub async fn abort_multipart_upload<P>(&self, path: P, upload_id: &str) -> Result<(), FileError>
+    where
+        Self: GetStdWithPath<P>,
+    {
+        let (mut url, canonicalized) = self
+            .get_std_with_path(path)
+            .ok_or_else(FileError::not_found_canonicalized)?;
+
+        let canonicalized =
+            CanonicalizedResource::new(format!("{canonicalized}?uploadId={upload_id}"));
+        url.query_pairs_mut().append_pair("uploadId", upload_id);
+
+        self.builder(Method::DELETE, url, canonicalized)?
+            .send_adjust_error()
+            .await?;
+        Ok(())
+    }
+
+    /// # 取消默认 bucket 中创建时间早于 `older_than` 之前的分片上传任务
+    ///
+    /// 返回被取消的任务，已经完成或已被取消的任务会被忽略
+    pub async fn abort_stale_uploads(
+        &self,
+        older_than: Duration,
+    ) -> Result<Vec<MultipartUpload>, FileError> {
+        let deadline = Utc::now() - older_than;
+
+        let first = self
+            .list_multipart_uploads(ListUploadsQuery::default())
+            .await?;
+        let mut uploads = first.uploads.clone();
+        let rest = first.into_stream();
+        pin_mut!(rest);
+        while let Some(list) = rest.try_next().await? {
+            uploads.extend(list.into_uploads());
+        }
+
+        let mut aborted = Vec::new();
+        for upload in uploads {
+            if upload.initiated >= deadline {
+                continue;
+            }
+            match self
+                .abort_multipart_upload(upload.key.as_str(), &upload.upload_id)
+                .await
+            {
+                Ok(()) => aborted.push(upload),
+                Err(e) if e.oss_code() == Some(NO_SUCH_UPLOAD) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(aborted)
+    }
+}
+
+/// 列出已上传的分片，可用于 Client，Bucket 等实现了 [`Files`] 的类型
+///
+/// [`Files`]: crate::file::Files
+pub(crate) async fn list_parts<B, P>(
+    builder: &B,
+    path: P,
+    upload_id: &str,
+    part_number_marker: Option<u32>,
+) -> Result<PartList, FileError>
+where
+    B: AlignBuilder + GetStdWithPath<P>,
+{
+    let (mut url, canonicalized) = builder
+        .get_std_with_path(path)
+        .ok_or_else(FileError::not_found_canonicalized)?;
+
+    // part-number-marker 不是子资源，不参与签名
+    let canonicalized = CanonicalizedResource::new(format!("{canonicalized}?uploadId={upload_id}"));
+    url.query_pairs_mut().append_pair("uploadId", upload_id);
+    if let Some(marker) = part_number_marker {
+        url.query_pairs_mut()
+            .append_pair("part-number-marker", &marker.to_string());
+    }
+
+    let xml = builder
+        .builder(Method::GET, url, canonicalized)?
+        .send_adjust_error()
+        .await?
+        .text()
+        .await?;
+
+    let mut list = PartList::default();
+    list.decode(&xml, |_| Some(PartInfo::default()))?;
+    Ok(list)
+}
+
+/// 解析分片上传任务或分片列表时的错误
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidMultipart {
+    source: String,
+    kind: InvalidMultipartKind,
+}
+
+impl Display for InvalidMultipart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InvalidMultipartKind::*;
+        let kind = match &self.kind {
+            Initiated(_) => "initiated",
+            LastModified(_) => "last-modified",
+            Etag(_) => "etag",
+            PartNumber(_) => "part-number",
+            Size(_) => "size",
+        };
+        write!(f, "parse {kind} failed, gived str: {}", self.source)
+    }
+}
+
+impl Error for InvalidMultipart {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use InvalidMultipartKind::*;
+        match &self.kind {
+            Initiated(e) | LastModified(e) => Some(e),
+            PartNumber(e) | Size(e) => Some(e),
+            Etag(e) => Some(e),
+        }
+    }
+}
+
+impl ListError for InvalidMultipart {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum InvalidMultipartKind {
+    Initiated(chrono::ParseError),
+    LastModified(chrono::ParseError),
+    PartNumber(ParseIntError),
+    Size(ParseIntError),
+    Etag(quick_xml::escape::EscapeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use futures::{pin_mut, TryStreamExt};
+    use http::StatusCode;
+    use reqwest::{Request, Response};
+
+    use super::*;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        errors::OssService,
+        Client,
+    };
+
+    fn uploads_xml(truncated: bool, uploads: &[(&str, &str, &str)]) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListMultipartUploadsResult>
+  <Bucket>bar</Bucket>
+  <KeyMarker></KeyMarker>
+  <UploadIdMarker></UploadIdMarker>
+  <NextKeyMarker>b.txt</NextKeyMarker>
+  <NextUploadIdMarker>id2</NextUploadIdMarker>
+  <Delimiter></Delimiter>
+  <Prefix></Prefix>
+  <MaxUploads>2</MaxUploads>
+  <IsTruncated>{truncated}</IsTruncated>"#
+        );
+        for (key, id, initiated) in uploads {
+            xml.push_str(&format!(
+                "<Upload><Key>{key}</Key><UploadId>{id}</UploadId><StorageClass>Standard</StorageClass><Initiated>{initiated}</Initiated></Upload>"
+            ));
+        }
+        xml.push_str("</ListMultipartUploadsResult>");
+        xml
+    }
+
+    #[test]
+    fn decode_uploads() {
+        let xml = uploads_xml(
+            true,
+            &[
+                ("a.txt", "id1", "2023-01-01T00:00:00.000Z"),
+                ("b.txt", "id2", "2023-01-02T00:00:00.000Z"),
+            ],
+        );
+        let mut list = MultipartUploadList::default();
+        list.decode(&xml, |_| Some(MultipartUpload::default()))
+            .unwrap();
+
+        assert_eq!(list.bucket(), "bar");
+        assert_eq!(list.uploads().len(), 2);
+        assert_eq!(list.uploads()[1].key(), "b.txt");
+        assert_eq!(list.uploads()[1].upload_id(), "id2");
+        assert_eq!(
+            list.uploads()[0].initiated().to_rfc3339(),
+            "2023-01-01T00:00:00+00:00"
+        );
+
+        let query = list.next_query().unwrap();
+        assert_eq!(
+            query.to_query(),
+            "uploads&key-marker=b.txt&upload-id-marker=id2"
+        );
+
+        let xml = uploads_xml(false, &[("a.txt", "id1", "abc")]);
+        let err = MultipartUploadList::default()
+            .decode(&xml, |_| Some(MultipartUpload::default()))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "parse initiated failed, gived str: abc");
+    }
+
+    #[test]
+    fn decode_parts() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListPartsResult>
+  <Bucket>bar</Bucket>
+  <Key>a.txt</Key>
+  <UploadId>id1</UploadId>
+  <PartNumberMarker></PartNumberMarker>
+  <NextPartNumberMarker>2</NextPartNumberMarker>
+  <MaxParts>2</MaxParts>
+  <IsTruncated>true</IsTruncated>
+  <Part>
+    <PartNumber>1</PartNumber>
+    <LastModified>2023-01-01T00:00:00.000Z</LastModified>
+    <ETag>"3349DC700140D7F86A0784842780****"</ETag>
+    <Size>6291456</Size>
+  </Part>
+  <Part>
+    <PartNumber>2</PartNumber>
+    <LastModified>2023-01-01T00:00:01.000Z</LastModified>
+    <ETag>"3149DC700140D7F86A0784842780****"</ETag>
+    <Size>1024</Size>
+  </Part>
+</ListPartsResult>"#;
+
+        let mut list = PartList::default();
+        list.decode(xml, |_| Some(PartInfo::default())).unwrap();
+
+        assert_eq!(list.key(), "a.txt");
+        assert_eq!(list.upload_id(), "id1");
+        assert_eq!(list.next_marker(), Some(2));
+        assert_eq!(list.parts().len(), 2);
+        assert_eq!(list.parts()[0].part_number(), 1);
+        assert_eq!(list.parts()[0].etag(), "3349DC700140D7F86A0784842780****");
+        assert_eq!(list.parts()[1].size(), 1024);
+    }
+
+    /// 第一页有两个任务，第二页有一个任务，其中 b.txt 已被其他客户端完成
+    #[derive(Debug, Default)]
+    struct MockUploads {
+        requests: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Middleware for MockUploads {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let url = request.url().clone();
+            self.requests.lock().unwrap().push(format!(
+                "{} {}{}",
+                request.method(),
+                url.path(),
+                url.query().map(|q| format!("?{q}")).unwrap_or_default()
+            ));
+
+            let old = (Utc::now() - Duration::days(10)).to_rfc3339();
+            let new = Utc::now().to_rfc3339();
+            let body = match (request.method().as_str(), url.query()) {
+                ("GET", Some("uploads")) => {
+                    uploads_xml(true, &[("a.txt", "id1", &old), ("b.txt", "id2", &old)])
+                }
+                ("GET", Some(_)) => uploads_xml(false, &[("c.txt", "id3", &new)]),
+                ("DELETE", Some("uploadId=id2")) => {
+                    return Err(OssService::new2(
+                        "<Error><Code>NoSuchUpload</Code><Message>m</Message><RequestId>r</RequestId></Error>".to_string(),
+                        &StatusCode::NOT_FOUND,
+                        url,
+                    )
+                    .into())
+                }
+                _ => String::new(),
+            };
+            let response = http::Response::builder().status(200).body(body).unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn list_stream() {
+        let client = Client::test_init().middleware(Arc::new(MockUploads::default()));
+
+        let first = client
+            .list_multipart_uploads(ListUploadsQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(first.uploads().len(), 2);
+
+        let stream = first.into_stream();
+        pin_mut!(stream);
+        let second = stream.try_next().await.unwrap().unwrap();
+        assert_eq!(second.uploads()[0].key(), "c.txt");
+        assert!(stream.try_next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn abort_stale() {
+        let mock = Arc::new(MockUploads::default());
+        let client = Client::test_init().middleware(mock.clone());
+
+        let aborted = client.abort_stale_uploads(Duration::days(7)).await.unwrap();
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].key(), "a.txt");
+
+        assert_eq!(
+            *mock.requests.lock().unwrap(),
+            [
+                "GET /?uploads",
+                "GET /?uploads&key-marker=b.txt&upload-id-marker=id2",
+                "DELETE /a.txt?uploadId=id1",
+                "DELETE /b.txt?uploadId=id2",
+            ]
+        );
+    }
+}
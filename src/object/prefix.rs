@@ -0,0 +1,564 @@
+//! # 目录操作
+//!
+//! 对某个前缀（目录）下的全部文件执行删除、复制和移动，列举文件的同时以限定的并发数执行操作
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, object::{PrefixOptions, PrefixProgress}};
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let progress = |p: &PrefixProgress| println!("finished: {}, failed: {}", p.finished(), p.failed());
+//! let options = PrefixOptions {
+//!     concurrency: 20,
+//!     progress: Some(&progress),
+//! };
+//! let summary = client
+//!     .rename_prefix("logs/2023/", "archive/2023/", &options)
+//!     .await
+//!     .unwrap();
+//! for failure in summary.failures() {
+//!     println!("{}: {}", failure.key(), failure.reason());
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+
+use async_stream::try_stream;
+use futures::{pin_mut, stream, Stream, TryStreamExt};
+
+use crate::{
+    client::ClientArc,
+    file::{FileError, Files},
+    object::{delete::MAX_DELETE_KEYS, CopyObject, Object},
+    Query, QueryKey,
+};
+
+/// 默认的并发数
+const DEFAULT_CONCURRENCY: usize = 10;
+
+/// 目录操作的参数
+pub struct PrefixOptions<'a> {
+    /// 同时进行的请求数量
+    pub concurrency: usize,
+    /// 进度回调，每完成一批文件调用一次
+    pub progress: Option<&'a (dyn Fn(&PrefixProgress) + Send + Sync)>,
+}
+
+impl Default for PrefixOptions<'_> {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            progress: None,
+        }
+    }
+}
+
+impl fmt::Debug for PrefixOptions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefixOptions")
+            .field("concurrency", &self.concurrency)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// 目录操作的进度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefixProgress {
+    finished: u64,
+    failed: u64,
+}
+
+impl PrefixProgress {
+    /// 已成功处理的文件数量
+    pub fn finished(&self) -> u64 {
+        self.finished
+    }
+
+    /// 处理失败的文件数量
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+}
+
+/// 处理失败的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixFailure {
+    key: String,
+    reason: String,
+}
+
+impl PrefixFailure {
+    /// 文件路径
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// 失败原因，OSS 返回错误时为错误码
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// 目录操作的结果汇总
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixSummary {
+    finished: u64,
+    failures: Vec<PrefixFailure>,
+}
+
+impl PrefixSummary {
+    /// 处理的文件总数
+    pub fn total(&self) -> u64 {
+        self.finished + self.failures.len() as u64
+    }
+
+    /// 成功处理的文件数量
+    pub fn finished(&self) -> u64 {
+        self.finished
+    }
+
+    /// 处理失败的文件
+    pub fn failures(&self) -> &[PrefixFailure] {
+        &self.failures
+    }
+
+    /// 是否全部成功
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// 记录进度，并在每次更新后调用回调
+struct Tracker<'a> {
+    progress: PrefixProgress,
+    failures: Vec<PrefixFailure>,
+    callback: Option<&'a (dyn Fn(&PrefixProgress) + Send + Sync)>,
+}
+
+impl<'a> Tracker<'a> {
+    fn new(options: &PrefixOptions<'a>) -> Self {
+        Self {
+            progress: PrefixProgress::default(),
+            failures: Vec::new(),
+            callback: options.progress,
+        }
+    }
+
+    fn finish(&mut self, count: u64) {
+        self.progress.finished += count;
+    }
+
+    fn fail(&mut self, key: String, reason: String) {
+        self.progress.failed += 1;
+        self.failures.push(PrefixFailure { key, reason });
+    }
+
+    fn report(&self) {
+        if let Some(callback) = self.callback {
+            callback(&self.progress);
+        }
+    }
+
+    fn into_summary(self) -> PrefixSummary {
+        PrefixSummary {
+            finished: self.progress.finished,
+            failures: self.failures,
+        }
+    }
+}
+
+/// 失败原因，优先使用 OSS 返回的错误码
+fn reason(error: &FileError) -> String {
+    error
+        .oss_code()
+        .map_or_else(|| error.to_string(), str::to_owned)
+}
+
+impl ClientArc {
+    /// # 删除目录下的全部文件
+    ///
+    /// `prefix` 按目录处理，会补全结尾的 `/`，`abc` 不会匹配到 `abcd/e.txt`，空字符串表示整个 bucket
+    ///
+    /// 列举文件失败时返回 Err，单个文件删除失败会记录在 [`PrefixSummary`] 中
+    pub async fn delete_prefix(
+        &self,
+        prefix: &str,
+        options: &PrefixOptions<'_>,
+    ) -> Result<PrefixSummary, FileError> {
+        let prefix = &dir_prefix(prefix);
+        let mut tracker = Tracker::new(options);
+
+        let deletes = self
+            .prefix_pages(prefix)
+            .map_ok(|keys| async move {
+                let res = self
+                    .delete_objects(keys.iter().map(String::as_str), true)
+                    .await;
+                Ok::<_, FileError>((res, keys))
+            })
+            .try_buffer_unordered(options.concurrency.max(1));
+        pin_mut!(deletes);
+
+        while let Some((res, keys)) = deletes.try_next().await? {
+            match res {
+                Ok(results) => {
+                    for item in results {
+                        match item.error_code() {
+                            Some(code) => tracker.fail(item.key().to_owned(), code.to_owned()),
+                            None => tracker.finish(1),
+                        }
+                    }
+                }
+                Err(e) => {
+                    let reason = reason(&e);
+                    for key in keys {
+                        tracker.fail(key, reason.clone());
+                    }
+                }
+            }
+            tracker.report();
+        }
+
+        Ok(tracker.into_summary())
+    }
+
+    /// # 复制目录下的全部文件
+    ///
+    /// 将 `src` 下的文件复制到 `dest` 下，如 `a/b.txt` 复制到 `c/b.txt`，`dest` 不能位于 `src` 之下
+    ///
+    /// 列举文件失败时返回 Err，单个文件复制失败会记录在 [`PrefixSummary`] 中
+    pub async fn copy_prefix(
+        &self,
+        src: &str,
+        dest: &str,
+        options: &PrefixOptions<'_>,
+    ) -> Result<PrefixSummary, FileError> {
+        self.transfer_prefix(src, dest, false, options).await
+    }
+
+    /// # 移动目录下的全部文件
+    ///
+    /// 先复制文件到 `dest` 下，复制成功后再删除源文件，`dest` 不能位于 `src` 之下
+    ///
+    /// 列举文件失败时返回 Err，单个文件移动失败会记录在 [`PrefixSummary`] 中
+    pub async fn rename_prefix(
+        &self,
+        src: &str,
+        dest: &str,
+        options: &PrefixOptions<'_>,
+    ) -> Result<PrefixSummary, FileError> {
+        self.transfer_prefix(src, dest, true, options).await
+    }
+
+    async fn transfer_prefix(
+        &self,
+        src: &str,
+        dest: &str,
+        remove_source: bool,
+        options: &PrefixOptions<'_>,
+    ) -> Result<PrefixSummary, FileError> {
+        // 按目录处理，`logs` 与 `logs-old/` 互不包含
+        let src = &dir_prefix(src);
+        let dest = &dir_prefix(dest);
+        if dest.starts_with(src.as_str()) {
+            return Err(FileError::nested_prefix());
+        }
+        let mut tracker = Tracker::new(options);
+
+        let transfers = self
+            .prefix_pages(src)
+            .map_ok(|keys| stream::iter(keys.into_iter().map(Ok)))
+            .try_flatten()
+            .map_ok(|key| async move {
+                let target = format!("{dest}{}", key.strip_prefix(src).unwrap_or(&key));
+                let res = self.transfer_object(&key, target, remove_source).await;
+                Ok::<_, FileError>((res, key))
+            })
+            .try_buffer_unordered(options.concurrency.max(1));
+        pin_mut!(transfers);
+
+        while let Some((res, key)) = transfers.try_next().await? {
+            match res {
+                Ok(()) => tracker.finish(1),
+                Err(e) => tracker.fail(key, reason(&e)),
+            }
+            tracker.report();
+        }
+
+        Ok(tracker.into_summary())
+    }
+
+    async fn transfer_object(
+        &self,
+        key: &str,
+        target: String,
+        remove_source: bool,
+    ) -> Result<(), FileError> {
+        let options = CopyObject {
+            copy_source: key,
+            ..Default::default()
+        };
+        self.copy_object(&options, target).await?;
+        if remove_source {
+            self.delete_object(key.to_owned()).await?;
+        }
+        Ok(())
+    }
+
+    /// 分页列举前缀下的全部文件
+    fn prefix_pages<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Stream<Item = Result<Vec<String>, FileError>> + 'a {
+        let keys = |objects: &[Object]| -> Vec<String> {
+            objects.iter().map(Object::path_string).collect()
+        };
+        try_stream! {
+            let mut query = Query::new();
+            query.insert(QueryKey::PREFIX, prefix.to_owned());
+            query.insert(QueryKey::MAX_KEYS, MAX_DELETE_KEYS as u16);
+
+            // ExtractListError 不是 Send，需要立即转换
+            let list = self.get_object_list2(query).await.map_err(FileError::from)?;
+            yield keys(&list.object_list);
+
+            let pages = list.into_stream().map_err(FileError::from);
+            pin_mut!(pages);
+            while let Some(list) = pages.try_next().await? {
+                yield keys(&list.object_list);
+            }
+        }
+    }
+}
+
+/// 补全结尾的 `/`，空字符串（整个 bucket）保持不变
+fn dir_prefix(prefix: &str) -> String {
+    if prefix.is_empty() || prefix.ends_with('/') {
+        prefix.to_owned()
+    } else {
+        format!("{prefix}/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    };
+
+    use async_trait::async_trait;
+    use http::{Method, StatusCode};
+    use reqwest::{Request, Response};
+
+    use super::*;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        errors::OssService,
+    };
+
+    /// 模拟两页文件列表，`dir/b.txt` 复制失败，`dir/c.txt` 删除失败
+    ///
+    /// 列举的前缀不是 `dir/` 时，第二页会多出同级目录下的 `dir2/d.txt`
+    #[derive(Debug, Default)]
+    struct MockPrefix {
+        requests: Mutex<Vec<String>>,
+    }
+
+    const PAGE_1: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>bar</Name>
+          <Prefix>dir/</Prefix>
+          <MaxKeys>1000</MaxKeys>
+          <IsTruncated>true</IsTruncated>
+          <NextContinuationToken>token</NextContinuationToken>
+          <Contents><Key>dir/a.txt</Key><LastModified>2022-06-26T09:53:21.000Z</LastModified><ETag>"A"</ETag><Type>Normal</Type><Size>1</Size><StorageClass>Standard</StorageClass></Contents>
+          <Contents><Key>dir/b.txt</Key><LastModified>2022-06-26T09:53:21.000Z</LastModified><ETag>"B"</ETag><Type>Normal</Type><Size>1</Size><StorageClass>Standard</StorageClass></Contents>
+          <KeyCount>2</KeyCount>
+        </ListBucketResult>"#;
+
+    const PAGE_2: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>bar</Name>
+          <Prefix>dir/</Prefix>
+          <MaxKeys>1000</MaxKeys>
+          <IsTruncated>false</IsTruncated>
+          <Contents><Key>dir/c.txt</Key><LastModified>2022-06-26T09:53:21.000Z</LastModified><ETag>"C"</ETag><Type>Normal</Type><Size>1</Size><StorageClass>Standard</StorageClass></Contents>
+          <KeyCount>1</KeyCount>
+        </ListBucketResult>"#;
+
+    #[async_trait]
+    impl Middleware for MockPrefix {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let url = request.url().clone();
+            let copy_source = request
+                .headers()
+                .get("x-oss-copy-source")
+                .map(|v| v.to_str().unwrap().to_owned());
+            self.requests.lock().unwrap().push(format!(
+                "{} {}{}{}",
+                request.method(),
+                url.path(),
+                url.query()
+                    .filter(|_| request.method() == Method::GET)
+                    .map(|q| format!("?{q}"))
+                    .unwrap_or_default(),
+                copy_source
+                    .map(|s| format!(" from {s}"))
+                    .unwrap_or_default()
+            ));
+
+            let error = |code: &str| -> Result<Response, BuilderError> {
+                let body = format!("<Error><Code>{code}</Code><Message>m</Message><RequestId>r</RequestId></Error>");
+                Err(OssService::new2(body, &StatusCode::FORBIDDEN, url.clone()).into())
+            };
+
+            let body = match (request.method(), url.path()) {
+                (&Method::GET, "/") if url.query().unwrap().contains("continuation-token") => {
+                    if url.query().unwrap().contains("prefix=dir/") {
+                        PAGE_2.to_owned()
+                    } else {
+                        PAGE_2.replace("</Contents>", "</Contents><Contents><Key>dir2/d.txt</Key><LastModified>2022-06-26T09:53:21.000Z</LastModified><ETag>\"D\"</ETag><Type>Normal</Type><Size>1</Size><StorageClass>Standard</StorageClass></Contents>")
+                    }
+                }
+                (&Method::GET, "/") => PAGE_1.to_owned(),
+                (&Method::POST, "/") => {
+                    let body = request.body().unwrap().as_bytes().unwrap();
+                    if std::str::from_utf8(body).unwrap().contains("dir/c.txt") {
+                        "<DeleteResult><Error><Key>dir/c.txt</Key><Code>AccessDenied</Code></Error></DeleteResult>".to_owned()
+                    } else {
+                        "<DeleteResult></DeleteResult>".to_owned()
+                    }
+                }
+                (&Method::PUT, "/new/b.txt") => return error("AccessDenied"),
+                (&Method::PUT, _) => {
+                    "<CopyObjectResult><ETag>\"E\"</ETag><LastModified>2023-01-01T00:00:00.000Z</LastModified></CopyObjectResult>".to_owned()
+                }
+                (&Method::DELETE, _) => String::new(),
+                _ => unreachable!("unexpected request: {}", url),
+            };
+            let response = http::Response::builder().status(200).body(body).unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn delete_prefix() {
+        let middleware = Arc::new(MockPrefix::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        let calls = AtomicU64::new(0);
+        let progress = |_: &PrefixProgress| {
+            calls.fetch_add(1, Ordering::SeqCst);
+        };
+        let options = PrefixOptions {
+            concurrency: 2,
+            progress: Some(&progress),
+        };
+        let summary = client.delete_prefix("dir/", &options).await.unwrap();
+
+        assert_eq!(summary.total(), 3);
+        assert_eq!(summary.finished(), 2);
+        assert!(!summary.is_success());
+        assert_eq!(summary.failures()[0].key(), "dir/c.txt");
+        assert_eq!(summary.failures()[0].reason(), "AccessDenied");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn delete_prefix_without_slash() {
+        let middleware = Arc::new(MockPrefix::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        let summary = client
+            .delete_prefix("dir", &PrefixOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(summary.total(), 3);
+
+        // 只列举 `dir/` 下的文件，不会匹配到 `dir2/`
+        let requests = middleware.requests.lock().unwrap();
+        let lists: Vec<_> = requests.iter().filter(|r| r.starts_with("GET")).collect();
+        assert_eq!(lists.len(), 2);
+        for list in lists {
+            assert!(list.split(['?', '&']).any(|p| p == "prefix=dir/"), "{list}");
+        }
+    }
+
+    #[tokio::test]
+    async fn rename_prefix() {
+        let middleware = Arc::new(MockPrefix::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        let summary = client
+            .rename_prefix("dir/", "new/", &PrefixOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(summary.total(), 3);
+        assert_eq!(summary.finished(), 2);
+        assert_eq!(summary.failures()[0].key(), "dir/b.txt");
+        assert_eq!(summary.failures()[0].reason(), "AccessDenied");
+
+        let requests = middleware.requests.lock().unwrap();
+        assert!(requests.contains(&"PUT /new/a.txt from /bar/dir/a.txt".to_owned()));
+        assert!(requests.contains(&"DELETE /dir/a.txt".to_owned()));
+        assert!(requests.contains(&"DELETE /dir/c.txt".to_owned()));
+        assert!(!requests.contains(&"DELETE /dir/b.txt".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn nested_prefix() {
+        let client = ClientArc::test_init();
+        let res = client
+            .copy_prefix("dir/", "dir/sub/", &PrefixOptions::default())
+            .await;
+        assert!(res.is_err());
+
+        let res = client
+            .copy_prefix("dir", "dir/sub", &PrefixOptions::default())
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn sibling_prefix() {
+        let middleware = Arc::new(MockPrefix::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        fn assert_send<T: Send>(_: &T) {}
+        let options = PrefixOptions::default();
+        let copy = client.copy_prefix("dir", "dir-old/", &options);
+        assert_send(&copy);
+        let summary = copy.await.unwrap();
+        assert_eq!(summary.total(), 3);
+
+        let requests = middleware.requests.lock().unwrap();
+        assert!(requests.contains(&"PUT /dir-old/a.txt from /bar/dir/a.txt".to_owned()));
+        assert!(requests
+            .iter()
+            .filter(|r| r.starts_with("GET"))
+            .all(|r| r.split(['?', '&']).any(|p| p == "prefix=dir/")));
+    }
+
+    #[tokio::test]
+    async fn copy_prefix_without_slash() {
+        let middleware = Arc::new(MockPrefix::default());
+        let client = ClientArc::test_init().middleware(middleware.clone());
+
+        let summary = client
+            .copy_prefix("dir", "dir2", &PrefixOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(summary.total(), 3);
+
+        let requests = middleware.requests.lock().unwrap();
+        assert!(requests.contains(&"PUT /dir2/a.txt from /bar/dir/a.txt".to_owned()));
+        assert!(requests.contains(&"PUT /dir2/c.txt from /bar/dir/c.txt".to_owned()));
+        assert!(!requests.iter().any(|r| r.starts_with("PUT /dir2a.txt")));
+    }
+}
@@ -0,0 +1,807 @@
+//! # 断点续传
+//!
+//! [`ResumableUpload`] 将本地文件分片上传到 OSS，上传进度（upload id，分片大小，文件的大小和修改时间，
+//! 已完成的分片）保存在本地的 checkpoint 文件中。
+//!
+//! 进程中断后再次上传时，会通过 ListParts 校验已上传的分片，只上传缺少的分片，上传完成后删除 checkpoint 文件
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, object::ResumableUpload};
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let mut upload = ResumableUpload::new(client, "video.mp4", "videos/video.mp4".parse().unwrap());
+//! upload
+//!     .set_part_size(10 * 1024 * 1024)
+//!     .unwrap()
+//!     .set_concurrency(4);
+//! upload.upload().await.unwrap();
+//! # }
+//! ```
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/resumable-upload)
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use futures::{pin_mut, stream, StreamExt, TryStreamExt};
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderValue, Method,
+};
+
+use crate::{
+    crc64::{combine, Crc64},
+    decode::decode_text,
+    file::{
+        check_crc64, content_md5_header, header_crc64, std_with_query, FileError, Files,
+        DEFAULT_CONTENT_TYPE,
+    },
+    types::CanonicalizedResource,
+    ObjectPath,
+};
+
+use super::{
+    checkpoint::{with_suffix, CheckpointFile, DEFAULT_CONCURRENCY, DEFAULT_PART_SIZE},
+    content::{complete_xml, Inner},
+    multipart::list_parts,
+};
+
+/// 分片上传已失效时，OSS 返回的错误码
+const NO_SUCH_UPLOAD: &str = "NoSuchUpload";
+
+/// # 断点续传的句柄
+#[derive(Debug, Clone)]
+pub struct ResumableUpload<C> {
+    client: C,
+    file: PathBuf,
+    path: ObjectPath,
+    checkpoint: PathBuf,
+    part_size: u64,
+    concurrency: usize,
+    content_type: String,
+}
+
+impl<C: Files<ObjectPath>> ResumableUpload<C> {
+    /// 初始化，checkpoint 文件默认保存在本地文件旁边，即 `{file}.checkpoint`
+    pub fn new<P: Into<PathBuf>>(client: C, file: P, path: ObjectPath) -> Self {
+        let file = file.into();
+        Self {
+            client,
+            checkpoint: with_suffix(&file, ".checkpoint"),
+            file,
+            path,
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            content_type: DEFAULT_CONTENT_TYPE.to_owned(),
+        }
+    }
+
+    /// 设置 checkpoint 文件的路径
+    pub fn set_checkpoint<P: Into<PathBuf>>(&mut self, checkpoint: P) -> &mut Self {
+        self.checkpoint = checkpoint.into();
+        self
+    }
+
+    /// 设置分片大小，需要在 100KB 到 5GB 之间
+    pub fn set_part_size(&mut self, part_size: u64) -> Result<&mut Self, ResumableError> {
+        if !usize::try_from(part_size).map_or(false, Inner::valid_part_size) {
+            return Err(ResumableError::new(ResumableErrorKind::OverflowPartSize));
+        }
+        self.part_size = part_size;
+        Ok(self)
+    }
+
+    /// 设置同时上传的分片数量，最小为 1
+    pub fn set_concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// 设置文件类型
+    pub fn set_content_type(&mut self, content_type: &str) -> &mut Self {
+        self.content_type = content_type.to_owned();
+        self
+    }
+
+    /// checkpoint 文件的路径
+    pub fn checkpoint(&self) -> &Path {
+        &self.checkpoint
+    }
+
+    /// 上传文件
+    ///
+    /// 中途失败时，已完成的分片会保留在 checkpoint 文件中，再次调用时从断点继续上传
+    pub async fn upload(&self) -> Result<(), ResumableError> {
+        let meta = fs::metadata(&self.file)?;
+        let mut checkpoint = Checkpoint {
+            path: self.path.as_ref().to_owned(),
+            part_size: self.part_size,
+            file_size: meta.len(),
+            modified: modified(&meta)?,
+            ..Default::default()
+        };
+
+        let count = checkpoint.part_count();
+        if count > Inner::MAX_PARTS_COUNT as u64 {
+            return Err(ResumableError::new(
+                ResumableErrorKind::OverflowMaxPartsCount,
+            ));
+        }
+
+        match Checkpoint::load(&self.checkpoint) {
+            Some(saved) if saved.same_file(&checkpoint) => {
+                match self.list_parts(&saved.upload_id).await {
+                    Ok(uploaded) => {
+                        checkpoint.parts = saved
+                            .parts
+                            .into_iter()
+                            .filter(|(number, etag)| {
+                                uploaded.get(number).map(String::as_str)
+                                    == Some(etag.trim_matches('"'))
+                            })
+                            .collect();
+                        checkpoint.upload_id = saved.upload_id;
+                    }
+                    // 分片上传已完成或已取消，需要重新上传
+                    Err(e) if e.oss_code() == Some(NO_SUCH_UPLOAD) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            // 本地文件或目标路径已变化，之前上传的分片无法再使用，在原路径上取消
+            Some(saved) => {
+                if let Ok(path) = saved.path.parse() {
+                    let _ = self.abort(path, &saved.upload_id).await;
+                }
+            }
+            None => {}
+        }
+
+        if checkpoint.upload_id.is_empty() {
+            checkpoint.upload_id = self.init().await?;
+        }
+        checkpoint.save(&self.checkpoint)?;
+
+        let missing: Vec<u16> = (1..=count as u16)
+            .filter(|number| !checkpoint.parts.contains_key(number))
+            .collect();
+
+        let upload_id = checkpoint.upload_id.clone();
+        let file_size = checkpoint.file_size;
+
+        // 断点之前上传的分片没有记录 CRC64，需要从本地文件重新计算
+        let crc64 = self.client.crc64_enabled();
+        let mut crc64_list = BTreeMap::new();
+        if crc64 {
+            for number in checkpoint.parts.keys() {
+                let buf = self.read_part(*number, file_size)?;
+                crc64_list.insert(*number, Crc64::checksum(&buf));
+            }
+        }
+
+        let uploads = stream::iter(missing)
+            .map(|number| {
+                let upload_id = &upload_id;
+                async move {
+                    let buf = self.read_part(number, file_size)?;
+                    let crc = crc64.then(|| Crc64::checksum(&buf));
+                    let etag = self.upload_part(upload_id, number, buf).await?;
+                    Ok::<_, ResumableError>((number, etag, crc))
+                }
+            })
+            .buffer_unordered(self.concurrency);
+        pin_mut!(uploads);
+
+        while let Some((number, etag, crc)) = uploads.try_next().await? {
+            checkpoint.parts.insert(number, etag);
+            if let Some(crc) = crc {
+                crc64_list.insert(number, crc);
+            }
+            checkpoint.save(&self.checkpoint)?;
+        }
+
+        let crc64 = crc64.then(|| self.parts_crc64(&crc64_list, file_size));
+        self.complete(&checkpoint, crc64).await?;
+        fs::remove_file(&self.checkpoint)?;
+
+        Ok(())
+    }
+
+    /// 读取本地文件中的某个分片，分片编号从 1 开始
+    fn read_part(&self, number: u16, file_size: u64) -> io::Result<Vec<u8>> {
+        let offset = (number as u64 - 1) * self.part_size;
+        let len = self.part_size.min(file_size.saturating_sub(offset));
+
+        let mut file = File::open(&self.file)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buf = vec![0; len as usize];
+        file.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// 按分片编号合并各分片的 CRC64，得到整个文件的值
+    fn parts_crc64(&self, crc64_list: &BTreeMap<u16, u64>, file_size: u64) -> u64 {
+        crc64_list.iter().fold(0, |crc, (number, part)| {
+            let offset = (*number as u64 - 1) * self.part_size;
+            let len = self.part_size.min(file_size.saturating_sub(offset));
+            combine(crc, *part, len)
+        })
+    }
+
+    fn std_with_query(&self, query: &str) -> Result<(url::Url, CanonicalizedResource), FileError> {
+        self.std_with_path_query(self.path.clone(), query)
+    }
+
+    fn std_with_path_query(
+        &self,
+        path: ObjectPath,
+        query: &str,
+    ) -> Result<(url::Url, CanonicalizedResource), FileError> {
+        let (url, canonicalized) = self
+            .client
+            .get_std_with_path(path)
+            .ok_or(FileError::not_found_canonicalized())?;
+        Ok(std_with_query(url, canonicalized, query))
+    }
+
+    /// 初始化分片上传，返回 upload id
+    async fn init(&self) -> Result<String, ResumableError> {
+        let (url, resource) = self.std_with_query("uploads")?;
+        let content_type = HeaderValue::from_str(&self.content_type)
+            .map_err(|_| ResumableError::new(ResumableErrorKind::InvalidContentType))?;
+
+        let xml = self
+            .client
+            .builder_with_header(Method::POST, url, resource, [(CONTENT_TYPE, content_type)])
+            .map_err(FileError::from)?
+            .send_adjust_error()
+            .await
+            .map_err(FileError::from)?
+            .text()
+            .await
+            .map_err(FileError::from)?;
+
+        decode_text(&xml, b"UploadId")
+            .map_err(FileError::from)?
+            .ok_or(ResumableError::new(ResumableErrorKind::NoFoundUploadId))
+    }
+
+    /// 上传一个分片，返回分片的 etag
+    async fn upload_part(
+        &self,
+        upload_id: &str,
+        number: u16,
+        buf: Vec<u8>,
+    ) -> Result<String, ResumableError> {
+        let (url, resource) =
+            self.std_with_query(&format!("partNumber={number}&uploadId={upload_id}"))?;
+
+        let mut headers = vec![(CONTENT_LENGTH, HeaderValue::from(buf.len()))];
+        if self.client.content_md5_enabled() {
+            headers.push(content_md5_header(&buf));
+        }
+
+        let response = self
+            .client
+            .builder_with_header(Method::PUT, url, resource, headers)
+            .map_err(FileError::from)?
+            .body(buf)
+            .send_adjust_error()
+            .await
+            .map_err(FileError::from)?;
+
+        response
+            .headers()
+            .get("ETag")
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToOwned::to_owned)
+            .ok_or(ResumableError::new(ResumableErrorKind::NoFoundEtag))
+    }
+
+    /// 通过 ListParts 获取已上传的分片及其 etag（不含引号）
+    async fn list_parts(&self, upload_id: &str) -> Result<BTreeMap<u16, String>, FileError> {
+        let mut parts = BTreeMap::new();
+        let mut marker = None;
+
+        loop {
+            let list = list_parts(&self.client, self.path.clone(), upload_id, marker).await?;
+            marker = list.next_marker();
+            for part in list.into_parts() {
+                if let Ok(number) = u16::try_from(part.part_number()) {
+                    parts.insert(number, part.etag().to_owned());
+                }
+            }
+            if marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(parts)
+    }
+
+    /// 完成分片上传，`crc64` 不为空时，与 OSS 返回的整个文件的 CRC64 比对
+    async fn complete(&self, checkpoint: &Checkpoint, crc64: Option<u64>) -> Result<(), FileError> {
+        let (url, resource) = self.std_with_query(&format!("uploadId={}", checkpoint.upload_id))?;
+
+        let xml = complete_xml(
+            checkpoint
+                .parts
+                .iter()
+                .map(|(number, etag)| (*number, etag.as_str())),
+        );
+
+        let resp = self
+            .client
+            .builder_with_header(
+                Method::POST,
+                url,
+                resource,
+                [(CONTENT_LENGTH, HeaderValue::from(xml.len()))],
+            )?
+            .body(xml)
+            .send_adjust_error()
+            .await?;
+
+        if let Some(crc64) = crc64 {
+            check_crc64(header_crc64(resp.headers()), crc64)?;
+        }
+
+        Ok(())
+    }
+
+    /// 取消分片上传
+    async fn abort(&self, path: ObjectPath, upload_id: &str) -> Result<(), FileError> {
+        let (url, resource) = self.std_with_path_query(path, &format!("uploadId={upload_id}"))?;
+
+        self.client
+            .builder(Method::DELETE, url, resource)?
+            .send_adjust_error()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// checkpoint 文件中保存的上传进度
+///
+/// 每行一个字段，如 `upload_id=xxx`，已完成的分片为 `part={number} {etag}`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Checkpoint {
+    upload_id: String,
+    path: String,
+    part_size: u64,
+    file_size: u64,
+    modified: String,
+    parts: BTreeMap<u16, String>,
+}
+
+impl CheckpointFile for Checkpoint {}
+
+impl Checkpoint {
+    /// 是否为同一次上传（目标路径，分片大小，本地文件均未变化）
+    fn same_file(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.part_size == other.part_size
+            && self.file_size == other.file_size
+            && self.modified == other.modified
+    }
+
+    /// 分片数量，空文件也需要上传一个分片
+    fn part_count(&self) -> u64 {
+        ((self.file_size + self.part_size - 1) / self.part_size).max(1)
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "upload_id={}", self.upload_id)?;
+        writeln!(f, "path={}", self.path)?;
+        writeln!(f, "part_size={}", self.part_size)?;
+        writeln!(f, "file_size={}", self.file_size)?;
+        writeln!(f, "modified={}", self.modified)?;
+        for (number, etag) in self.parts.iter() {
+            writeln!(f, "part={number} {etag}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Checkpoint {
+    type Err = ResumableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ResumableError::new(ResumableErrorKind::InvalidCheckpoint);
+
+        let mut checkpoint = Self::default();
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            match key {
+                "upload_id" => checkpoint.upload_id = value.to_owned(),
+                "path" => checkpoint.path = value.to_owned(),
+                "part_size" => checkpoint.part_size = value.parse().map_err(|_| invalid())?,
+                "file_size" => checkpoint.file_size = value.parse().map_err(|_| invalid())?,
+                "modified" => checkpoint.modified = value.to_owned(),
+                "part" => {
+                    let (number, etag) = value.split_once(' ').ok_or_else(invalid)?;
+                    let number = number.parse().map_err(|_| invalid())?;
+                    checkpoint.parts.insert(number, etag.to_owned());
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        if checkpoint.upload_id.is_empty() || checkpoint.part_size == 0 {
+            return Err(invalid());
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// 文件修改时间，精确到纳秒
+fn modified(meta: &fs::Metadata) -> io::Result<String> {
+    let duration = meta
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    Ok(format!(
+        "{}.{:09}",
+        duration.as_secs(),
+        duration.subsec_nanos()
+    ))
+}
+
+/// 断点续传的错误
+#[derive(Debug)]
+pub struct ResumableError {
+    kind: ResumableErrorKind,
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum ResumableErrorKind {
+    Io(io::Error),
+    File(FileError),
+    NoFoundUploadId,
+    NoFoundEtag,
+    InvalidContentType,
+    InvalidCheckpoint,
+    OverflowMaxPartsCount,
+    OverflowPartSize,
+}
+
+impl ResumableError {
+    fn new(kind: ResumableErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for ResumableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ResumableErrorKind::*;
+        match &self.kind {
+            Io(_) => "io error".fmt(f),
+            File(e) => e.fmt(f),
+            NoFoundUploadId => "not found upload id".fmt(f),
+            NoFoundEtag => "not found etag".fmt(f),
+            InvalidContentType => "invalid content type".fmt(f),
+            InvalidCheckpoint => "invalid checkpoint file".fmt(f),
+            OverflowMaxPartsCount => "overflow max parts count".fmt(f),
+            OverflowPartSize => "part size must be between 100k and 5G".fmt(f),
+        }
+    }
+}
+
+impl Error for ResumableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use ResumableErrorKind::*;
+        match &self.kind {
+            Io(e) => Some(e),
+            File(e) => Some(e),
+            NoFoundUploadId
+            | NoFoundEtag
+            | InvalidContentType
+            | InvalidCheckpoint
+            | OverflowMaxPartsCount
+            | OverflowPartSize => None,
+        }
+    }
+}
+
+impl From<io::Error> for ResumableError {
+    fn from(value: io::Error) -> Self {
+        Self::new(ResumableErrorKind::Io(value))
+    }
+}
+
+impl From<FileError> for ResumableError {
+    fn from(value: FileError) -> Self {
+        Self::new(ResumableErrorKind::File(value))
+    }
+}
+
+impl From<ResumableError> for io::Error {
+    fn from(ResumableError { kind }: ResumableError) -> Self {
+        use io::ErrorKind::*;
+        use ResumableErrorKind::*;
+        match kind {
+            Io(e) => e,
+            File(e) => e.into(),
+            NoFoundUploadId | NoFoundEtag => Self::new(NotFound, ResumableError { kind }),
+            InvalidContentType | InvalidCheckpoint => {
+                Self::new(InvalidData, ResumableError { kind })
+            }
+            OverflowMaxPartsCount | OverflowPartSize => {
+                Self::new(InvalidInput, ResumableError { kind })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use http::{Method, StatusCode};
+    use reqwest::{Request, Response};
+
+    use super::*;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        errors::OssService,
+        Client,
+    };
+
+    /// 记录收到的请求，ListParts 返回第 1，2 个分片
+    #[derive(Debug, Default)]
+    struct MockMulti {
+        requests: Mutex<Vec<String>>,
+        complete: Mutex<String>,
+        no_such_upload: bool,
+        crc64: Option<u64>,
+    }
+
+    #[async_trait]
+    impl Middleware for MockMulti {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let query = request.url().query().unwrap_or_default().to_owned();
+            // 取消上传时同时记录路径
+            let record = match *request.method() {
+                Method::DELETE => format!("DELETE {} {query}", request.url().path()),
+                ref method => format!("{method} {query}"),
+            };
+            self.requests.lock().unwrap().push(record);
+
+            let response = http::Response::builder().status(200);
+            let response = match *request.method() {
+                Method::GET if self.no_such_upload => {
+                    let body = "<Error><Code>NoSuchUpload</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                    let url = request.url().clone();
+                    return Err(OssService::new2(body.to_owned(), &StatusCode::NOT_FOUND, url).into());
+                }
+                Method::GET => response.body(
+                    "<ListPartsResult><IsTruncated>false</IsTruncated>\
+                    <Part><PartNumber>1</PartNumber><ETag>&quot;e1&quot;</ETag><Size>10</Size></Part>\
+                    <Part><PartNumber>2</PartNumber><ETag>&quot;other&quot;</ETag><Size>10</Size></Part>\
+                    </ListPartsResult>",
+                ),
+                Method::POST if query == "uploads" => response
+                    .body("<InitiateMultipartUploadResult><UploadId>new_id</UploadId></InitiateMultipartUploadResult>"),
+                Method::POST => {
+                    let body = request.body().unwrap().as_bytes().unwrap();
+                    *self.complete.lock().unwrap() = String::from_utf8(body.to_vec()).unwrap();
+                    match self.crc64 {
+                        Some(crc64) => response.header("x-oss-hash-crc64ecma", crc64).body(""),
+                        None => response.body(""),
+                    }
+                }
+                Method::PUT => {
+                    let number = query.split('&').next().unwrap().trim_start_matches("partNumber=");
+                    response
+                        .header("ETag", format!("\"e{number}\""))
+                        .body("")
+                }
+                _ => response.body(""),
+            };
+            Ok(Response::from(response.unwrap()))
+        }
+    }
+
+    fn local_file(name: &str) -> PathBuf {
+        let file = env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        fs::write(&file, [1u8; 25]).unwrap();
+        file
+    }
+
+    #[test]
+    fn checkpoint_parse() {
+        let mut checkpoint = Checkpoint {
+            upload_id: "abc".to_owned(),
+            path: "a/b.txt".to_owned(),
+            part_size: 10,
+            file_size: 25,
+            modified: "1.000000001".to_owned(),
+            ..Default::default()
+        };
+        checkpoint.parts.insert(2, "\"e2\"".to_owned());
+        checkpoint.parts.insert(1, "\"e1\"".to_owned());
+
+        let text = checkpoint.to_string();
+        assert_eq!(
+            text,
+            "upload_id=abc\npath=a/b.txt\npart_size=10\nfile_size=25\nmodified=1.000000001\npart=1 \"e1\"\npart=2 \"e2\"\n"
+        );
+        assert_eq!(text.parse::<Checkpoint>().unwrap(), checkpoint);
+        assert_eq!(checkpoint.part_count(), 3);
+
+        let err = "foo".parse::<Checkpoint>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid checkpoint file");
+        assert!("path=a\n".parse::<Checkpoint>().is_err());
+    }
+
+    #[tokio::test]
+    async fn resume() {
+        let file = local_file("aliyun-oss-client-resume");
+        let middleware = Arc::new(MockMulti::default());
+        let client = Client::test_init().middleware(middleware.clone());
+
+        let mut upload = ResumableUpload::new(client, &file, "abc.bin".parse().unwrap());
+        upload.set_part_size(10).unwrap();
+
+        let mut checkpoint = Checkpoint {
+            upload_id: "old_id".to_owned(),
+            path: "abc.bin".to_owned(),
+            part_size: 10,
+            file_size: 25,
+            modified: modified(&fs::metadata(&file).unwrap()).unwrap(),
+            ..Default::default()
+        };
+        checkpoint.parts.insert(1, "\"e1\"".to_owned());
+        checkpoint.parts.insert(2, "\"e2\"".to_owned());
+        checkpoint.save(upload.checkpoint()).unwrap();
+
+        upload.upload().await.unwrap();
+
+        // 第 2 个分片的 etag 与服务端不一致，需要重新上传
+        let mut requests = middleware.requests.lock().unwrap().clone();
+        requests.sort();
+        assert_eq!(
+            requests,
+            [
+                "GET uploadId=old_id",
+                "POST uploadId=old_id",
+                "PUT partNumber=2&uploadId=old_id",
+                "PUT partNumber=3&uploadId=old_id",
+            ]
+        );
+        assert_eq!(
+            *middleware.complete.lock().unwrap(),
+            "<CompleteMultipartUpload><Part><PartNumber>1</PartNumber><ETag>\"e1\"</ETag></Part><Part><PartNumber>2</PartNumber><ETag>\"e2\"</ETag></Part><Part><PartNumber>3</PartNumber><ETag>\"e3\"</ETag></Part></CompleteMultipartUpload>"
+        );
+        assert!(!upload.checkpoint().exists());
+        fs::remove_file(file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn restart() {
+        let file = local_file("aliyun-oss-client-restart");
+        let middleware = Arc::new(MockMulti {
+            no_such_upload: true,
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware.clone());
+
+        let mut upload = ResumableUpload::new(client, &file, "abc.bin".parse().unwrap());
+        upload.set_part_size(10).unwrap().set_concurrency(1);
+
+        // 本地文件已变化，取消之前的上传
+        let checkpoint = Checkpoint {
+            upload_id: "old_id".to_owned(),
+            path: "abc.bin".to_owned(),
+            part_size: 10,
+            file_size: 8,
+            modified: "1.000000000".to_owned(),
+            ..Default::default()
+        };
+        checkpoint.save(upload.checkpoint()).unwrap();
+
+        upload.upload().await.unwrap();
+        assert_eq!(
+            *middleware.requests.lock().unwrap(),
+            [
+                "DELETE /abc.bin uploadId=old_id",
+                "POST uploads",
+                "PUT partNumber=1&uploadId=new_id",
+                "PUT partNumber=2&uploadId=new_id",
+                "PUT partNumber=3&uploadId=new_id",
+                "POST uploadId=new_id",
+            ]
+        );
+        assert!(!upload.checkpoint().exists());
+
+        // upload id 已失效，重新初始化
+        middleware.requests.lock().unwrap().clear();
+        let checkpoint = Checkpoint {
+            file_size: 25,
+            modified: modified(&fs::metadata(&file).unwrap()).unwrap(),
+            ..checkpoint
+        };
+        checkpoint.save(upload.checkpoint()).unwrap();
+
+        upload.upload().await.unwrap();
+        {
+            let requests = middleware.requests.lock().unwrap();
+            assert_eq!(requests[0], "GET uploadId=old_id");
+            assert_eq!(requests[1], "POST uploads");
+            assert_eq!(requests.len(), 6);
+        }
+
+        // checkpoint 属于其他目标路径，在原路径上取消
+        middleware.requests.lock().unwrap().clear();
+        let checkpoint = Checkpoint {
+            path: "other.bin".to_owned(),
+            ..checkpoint
+        };
+        checkpoint.save(upload.checkpoint()).unwrap();
+
+        upload.upload().await.unwrap();
+        assert_eq!(
+            middleware.requests.lock().unwrap()[0],
+            "DELETE /other.bin uploadId=old_id"
+        );
+        fs::remove_file(file).unwrap();
+    }
+
+    #[tokio::test]
+    async fn resume_crc64() {
+        let file = local_file("aliyun-oss-client-resume-crc64");
+        let middleware = Arc::new(MockMulti {
+            crc64: Some(Crc64::checksum(&[1u8; 25])),
+            ..Default::default()
+        });
+        let mut client = Client::test_init().middleware(middleware.clone());
+        client.enable_crc64(true);
+
+        let mut upload = ResumableUpload::new(client, &file, "abc.bin".parse().unwrap());
+        upload.set_part_size(10).unwrap();
+
+        // 第 1 个分片在断点之前已上传
+        let mut checkpoint = Checkpoint {
+            upload_id: "old_id".to_owned(),
+            path: "abc.bin".to_owned(),
+            part_size: 10,
+            file_size: 25,
+            modified: modified(&fs::metadata(&file).unwrap()).unwrap(),
+            ..Default::default()
+        };
+        checkpoint.parts.insert(1, "\"e1\"".to_owned());
+        checkpoint.save(upload.checkpoint()).unwrap();
+        upload.upload().await.unwrap();
+        assert!(!upload.checkpoint().exists());
+
+        let middleware = Arc::new(MockMulti {
+            crc64: Some(1),
+            ..Default::default()
+        });
+        let mut client = Client::test_init().middleware(middleware);
+        client.enable_crc64(true);
+        let upload = ResumableUpload::new(client, &file, "abc.bin".parse().unwrap());
+        let err = upload.upload().await.unwrap_err();
+        assert!(err.to_string().contains("crc64"));
+        let source = err.source().unwrap().downcast_ref::<FileError>().unwrap();
+        assert!(source.to_string().contains("crc64 mismatch"));
+        fs::remove_file(upload.checkpoint()).unwrap();
+        fs::remove_file(file).unwrap();
+    }
+}
@@ -0,0 +1,556 @@
+//! # 可随机读取的文件
+//!
+//! [`SeekableReader`] 实现了 [`AsyncRead`] 和 [`AsyncSeek`]，按块通过 Range 请求读取文件内容，
+//! 每次至少读取一个块（即预读），并缓存最近读取的几个块，适用于 Parquet，zip 等需要随机读取的文件
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, object::SeekableReader};
+//! use futures::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let mut reader = SeekableReader::open(client, "data.zip".parse().unwrap())
+//!     .await
+//!     .unwrap();
+//! reader.set_block_size(256 * 1024).set_cache_blocks(8);
+//!
+//! // 读取 zip 文件末尾的目录
+//! let mut buf = [0u8; 22];
+//! reader.seek(SeekFrom::End(-22)).await.unwrap();
+//! reader.read_exact(&mut buf).await.unwrap();
+//! # }
+//! ```
+//!
+//! [`AsyncRead`]: futures::io::AsyncRead
+//! [`AsyncSeek`]: futures::io::AsyncSeek
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    io::{self, SeekFrom},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{
+    io::{AsyncRead, AsyncSeek},
+    ready,
+};
+use http::{
+    header::{HeaderName, IF_MATCH, RANGE},
+    HeaderValue, Method,
+};
+
+use super::download::PRECONDITION_FAILED;
+use crate::{
+    builder::BuilderError,
+    file::{FileError, Files},
+    types::ContentRange,
+    ObjectPath,
+};
+
+/// 默认的块大小 1MB
+const DEFAULT_BLOCK_SIZE: u64 = 1024 * 1024;
+
+/// 默认缓存的块数量
+const DEFAULT_CACHE_BLOCKS: usize = 4;
+
+type BlockFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, FileError>> + Send>>;
+
+/// # 可随机读取的文件
+///
+/// 打开时通过 HEAD 请求获取文件大小和 etag，之后按需读取，
+/// 读取时文件已被修改会返回错误，避免拼接出不同版本的内容
+pub struct SeekableReader<C> {
+    client: C,
+    path: ObjectPath,
+    size: u64,
+    etag: Option<HeaderValue>,
+    pos: u64,
+    cache: BlockCache,
+    pending: Option<(u64, BlockFuture)>,
+}
+
+impl<C> fmt::Debug for SeekableReader<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeekableReader")
+            .field("path", &self.path)
+            .field("size", &self.size)
+            .field("pos", &self.pos)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Files<ObjectPath> + Clone + 'static> SeekableReader<C> {
+    /// 打开文件
+    pub async fn open(client: C, path: ObjectPath) -> Result<Self, FileError> {
+        let meta = client.head_object(path.clone()).await?;
+        Ok(Self {
+            client,
+            path,
+            size: meta.content_length(),
+            etag: meta
+                .etag()
+                .and_then(|etag| HeaderValue::from_str(etag).ok()),
+            pos: 0,
+            cache: BlockCache::new(DEFAULT_BLOCK_SIZE, DEFAULT_CACHE_BLOCKS),
+            pending: None,
+        })
+    }
+
+    /// 设置块大小，即每次请求读取的字节数，会清空缓存
+    pub fn set_block_size(&mut self, block_size: u64) -> &mut Self {
+        self.cache = BlockCache::new(block_size, self.cache.capacity);
+        self.pending = None;
+        self
+    }
+
+    /// 设置缓存的块数量，最小为 1
+    pub fn set_cache_blocks(&mut self, blocks: usize) -> &mut Self {
+        self.cache.set_capacity(blocks);
+        self
+    }
+
+    /// 文件大小
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// 当前读取的位置
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    fn fetch(&self, index: u64) -> BlockFuture {
+        let (start, end) = self.cache.block_range(index, self.size);
+        let client = self.client.clone();
+        let path = self.path.clone();
+        let headers = range_headers(start, end, self.etag.clone());
+        Box::pin(async move {
+            let (url, resource) = client
+                .get_std_with_path(path)
+                .ok_or_else(FileError::not_found_canonicalized)?;
+            let content = client
+                .builder_with_header(Method::GET, url, resource, headers)?
+                .send_adjust_error()
+                .await
+                .map_err(changed_error)?
+                .bytes()
+                .await?;
+            Ok(content.to_vec())
+        })
+    }
+}
+
+impl<C: Files<ObjectPath> + Clone + Unpin + 'static> AsyncRead for SeekableReader<C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.pos >= this.size || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let index = this.cache.index(this.pos);
+        loop {
+            if let Some(len) = this.cache.read(this.pos, buf) {
+                this.pos += len as u64;
+                return Poll::Ready(Ok(len));
+            }
+
+            if !matches!(this.pending, Some((pending, _)) if pending == index) {
+                this.pending = Some((index, this.fetch(index)));
+            }
+            if let Some((_, future)) = this.pending.as_mut() {
+                let res = ready!(future.as_mut().poll(cx));
+                this.pending = None;
+                this.cache.insert(index, res?);
+            }
+        }
+    }
+}
+
+impl<C: Files<ObjectPath> + Clone + Unpin + 'static> AsyncSeek for SeekableReader<C> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        Poll::Ready(seek(&mut this.pos, this.size, pos))
+    }
+}
+
+/// 读取 `start..=end` 区间的请求头，打开时获取到 etag 则带上 If-Match
+fn range_headers(
+    start: u64,
+    end: u64,
+    etag: Option<HeaderValue>,
+) -> Vec<(HeaderName, HeaderValue)> {
+    let mut headers = vec![(RANGE, HeaderValue::from(ContentRange::from(start..end)))];
+    if let Some(etag) = etag {
+        headers.push((IF_MATCH, etag));
+    }
+    headers
+}
+
+/// If-Match 不满足时 OSS 返回 PreconditionFailed，即文件已被修改
+fn changed_error(e: BuilderError) -> FileError {
+    match FileError::from(e) {
+        e if e.oss_code() == Some(PRECONDITION_FAILED) => FileError::object_changed(),
+        e => e,
+    }
+}
+
+/// 计算新的读取位置
+fn seek(current: &mut u64, size: u64, pos: SeekFrom) -> io::Result<u64> {
+    let (base, offset) = match pos {
+        SeekFrom::Start(n) => (n, 0),
+        SeekFrom::End(n) => (size, n),
+        SeekFrom::Current(n) => (*current, n),
+    };
+    let new = if offset >= 0 {
+        base.checked_add(offset as u64)
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    }
+    .ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })?;
+    *current = new;
+    Ok(new)
+}
+
+/// 最近读取的块，按读取顺序排列，最近的在前
+#[derive(Debug)]
+struct BlockCache {
+    block_size: u64,
+    capacity: usize,
+    blocks: VecDeque<(u64, Vec<u8>)>,
+}
+
+impl BlockCache {
+    fn new(block_size: u64, capacity: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+            capacity: capacity.max(1),
+            blocks: VecDeque::new(),
+        }
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.blocks.truncate(self.capacity);
+    }
+
+    fn index(&self, pos: u64) -> u64 {
+        pos / self.block_size
+    }
+
+    /// 第 index 个块的起止位置（包含 end）
+    fn block_range(&self, index: u64, size: u64) -> (u64, u64) {
+        let start = index * self.block_size;
+        let end = (start + self.block_size).min(size) - 1;
+        (start, end)
+    }
+
+    /// 从缓存中读取，未命中时返回 None
+    fn read(&mut self, pos: u64, buf: &mut [u8]) -> Option<usize> {
+        let index = self.index(pos);
+        let i = self.blocks.iter().position(|(n, _)| *n == index)?;
+        let block = self.blocks.remove(i)?;
+
+        let offset = (pos - index * self.block_size) as usize;
+        let len = buf.len().min(block.1.len().saturating_sub(offset));
+        buf[..len].copy_from_slice(&block.1[offset..offset + len]);
+
+        self.blocks.push_front(block);
+        Some(len)
+    }
+
+    fn insert(&mut self, index: u64, block: Vec<u8>) {
+        self.blocks.retain(|(n, _)| *n != index);
+        self.blocks.push_front((index, block));
+        self.blocks.truncate(self.capacity);
+    }
+}
+
+/// 同步的随机读取
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use std::io::{self, Read, Seek, SeekFrom};
+
+    use http::{HeaderValue, Method};
+
+    use super::{
+        changed_error, range_headers, seek, BlockCache, DEFAULT_BLOCK_SIZE, DEFAULT_CACHE_BLOCKS,
+    };
+    use crate::{
+        file::{BlockingFiles, FileError},
+        ObjectPath,
+    };
+
+    /// # 可随机读取的文件
+    ///
+    /// 打开时通过 HEAD 请求获取文件大小和 etag，之后按需读取，
+    /// 读取时文件已被修改会返回错误
+    #[derive(Debug)]
+    pub struct SeekableReader<C> {
+        client: C,
+        path: ObjectPath,
+        size: u64,
+        etag: Option<HeaderValue>,
+        pos: u64,
+        cache: BlockCache,
+    }
+
+    impl<C: BlockingFiles<ObjectPath>> SeekableReader<C> {
+        /// 打开文件
+        pub fn open(client: C, path: ObjectPath) -> Result<Self, FileError> {
+            let meta = client.head_object(path.clone())?;
+            Ok(Self {
+                client,
+                path,
+                size: meta.content_length(),
+                etag: meta
+                    .etag()
+                    .and_then(|etag| HeaderValue::from_str(etag).ok()),
+                pos: 0,
+                cache: BlockCache::new(DEFAULT_BLOCK_SIZE, DEFAULT_CACHE_BLOCKS),
+            })
+        }
+
+        /// 设置块大小，即每次请求读取的字节数，会清空缓存
+        pub fn set_block_size(&mut self, block_size: u64) -> &mut Self {
+            self.cache = BlockCache::new(block_size, self.cache.capacity);
+            self
+        }
+
+        /// 设置缓存的块数量，最小为 1
+        pub fn set_cache_blocks(&mut self, blocks: usize) -> &mut Self {
+            self.cache.set_capacity(blocks);
+            self
+        }
+
+        /// 文件大小
+        pub fn size(&self) -> u64 {
+            self.size
+        }
+
+        /// 当前读取的位置
+        pub fn position(&self) -> u64 {
+            self.pos
+        }
+
+        fn fetch(&self, index: u64) -> Result<Vec<u8>, FileError> {
+            let (start, end) = self.cache.block_range(index, self.size);
+            let (url, resource) = self
+                .client
+                .get_std_with_path(self.path.clone())
+                .ok_or_else(FileError::not_found_canonicalized)?;
+            let headers = range_headers(start, end, self.etag.clone());
+            let content = self
+                .client
+                .builder_with_header(Method::GET, url, resource, headers)?
+                .send_adjust_error()
+                .map_err(changed_error)?
+                .bytes()?;
+            Ok(content.to_vec())
+        }
+    }
+
+    impl<C: BlockingFiles<ObjectPath>> Read for SeekableReader<C> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pos >= self.size || buf.is_empty() {
+                return Ok(0);
+            }
+
+            if let Some(len) = self.cache.read(self.pos, buf) {
+                self.pos += len as u64;
+                return Ok(len);
+            }
+
+            let index = self.cache.index(self.pos);
+            let block = self.fetch(index)?;
+            self.cache.insert(index, block);
+
+            let len = self.cache.read(self.pos, buf).unwrap_or_default();
+            self.pos += len as u64;
+            Ok(len)
+        }
+    }
+
+    impl<C: BlockingFiles<ObjectPath>> Seek for SeekableReader<C> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            seek(&mut self.pos, self.size, pos)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use futures::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+    use http::{Method, StatusCode};
+    use reqwest::{Request, Response};
+
+    use super::SeekableReader;
+    use crate::{
+        builder::{BuilderError, Middleware},
+        errors::OssService,
+        Client,
+    };
+
+    const CONTENT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    /// 模拟 36 个字节的文件，记录 Range 请求，changed 为 true 时模拟文件已被修改
+    #[derive(Debug, Default)]
+    struct MockObject {
+        ranges: Mutex<Vec<String>>,
+        changed: bool,
+    }
+
+    #[async_trait]
+    impl Middleware for MockObject {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            let response = http::Response::builder().status(200);
+            if request.method() == Method::HEAD {
+                let response = response
+                    .header("content-length", CONTENT.len().to_string())
+                    .header("etag", "\"etag\"");
+                return Ok(Response::from(response.body(Vec::new()).unwrap()));
+            }
+
+            assert_eq!(request.headers().get("If-Match").unwrap(), "\"etag\"");
+            if self.changed {
+                let body = "<Error><Code>PreconditionFailed</Code><Message>m</Message><RequestId>r</RequestId></Error>";
+                let url = request.url().clone();
+                return Err(OssService::new2(
+                    body.to_owned(),
+                    &StatusCode::PRECONDITION_FAILED,
+                    url,
+                )
+                .into());
+            }
+
+            let range = request.headers().get("Range").unwrap().to_str().unwrap();
+            self.ranges.lock().unwrap().push(range.to_owned());
+            let (start, end) = range.trim_start_matches("bytes=").split_once('-').unwrap();
+            let (start, end): (usize, usize) = (start.parse().unwrap(), end.parse().unwrap());
+            Ok(Response::from(
+                response.body(CONTENT[start..=end].to_vec()).unwrap(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn read_and_seek() {
+        let middleware = Arc::new(MockObject::default());
+        let client = Client::test_init().middleware(middleware.clone());
+
+        let mut reader = SeekableReader::open(client, "abc.bin".parse().unwrap())
+            .await
+            .unwrap();
+        reader.set_block_size(10).set_cache_blocks(2);
+        assert_eq!(reader.size(), 36);
+
+        let mut buf = [0u8; 4];
+        reader.seek(SeekFrom::End(-4)).await.unwrap();
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"wxyz");
+
+        // 跨越两个块
+        reader.seek(SeekFrom::Start(8)).await.unwrap();
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"89ab");
+        assert_eq!(reader.position(), 12);
+
+        // 命中缓存
+        reader.seek(SeekFrom::Current(-12)).await.unwrap();
+        reader.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"0123");
+
+        assert_eq!(
+            *middleware.ranges.lock().unwrap(),
+            ["bytes=30-35", "bytes=0-9", "bytes=10-19"]
+        );
+
+        let err = reader.seek(SeekFrom::Current(-10)).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        reader.seek(SeekFrom::Start(40)).await.unwrap();
+        assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn object_changed() {
+        let middleware = Arc::new(MockObject {
+            changed: true,
+            ..Default::default()
+        });
+        let client = Client::test_init().middleware(middleware);
+
+        let mut reader = SeekableReader::open(client, "abc.bin".parse().unwrap())
+            .await
+            .unwrap();
+        let mut buf = [0u8; 4];
+        let err = reader.read_exact(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "object has been changed");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_read_and_seek() {
+        use std::{
+            io::{Read, Seek},
+            rc::Rc,
+        };
+
+        use super::blocking::SeekableReader;
+        use crate::blocking::builder::Middleware;
+        use crate::ClientRc;
+        use reqwest::blocking::{Request, Response};
+
+        #[derive(Debug)]
+        struct MockObject {}
+
+        impl Middleware for MockObject {
+            fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+                let response = http::Response::builder().status(200);
+                if request.method() == Method::HEAD {
+                    let response = response
+                        .header("content-length", CONTENT.len().to_string())
+                        .header("etag", "\"etag\"");
+                    return Ok(Response::from(response.body(Vec::new()).unwrap()));
+                }
+                assert_eq!(request.headers().get("If-Match").unwrap(), "\"etag\"");
+                let range = request.headers().get("Range").unwrap().to_str().unwrap();
+                let (start, end) = range.trim_start_matches("bytes=").split_once('-').unwrap();
+                let (start, end): (usize, usize) = (start.parse().unwrap(), end.parse().unwrap());
+                Ok(Response::from(
+                    response.body(CONTENT[start..=end].to_vec()).unwrap(),
+                ))
+            }
+        }
+
+        let client = ClientRc::test_init().middleware(Rc::new(MockObject {}));
+        let mut reader = SeekableReader::open(client, "abc.bin".parse().unwrap()).unwrap();
+        reader.set_block_size(10);
+
+        reader.seek(std::io::SeekFrom::Start(28)).unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "stuvwxyz");
+    }
+}
@@ -0,0 +1,209 @@
+//! # 流式读取文件内容
+//!
+//! [`ObjectStream`] 同时实现了 [`Stream`] 和 [`AsyncRead`]，读取文件时不需要将全部内容保存在内存中
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, file::Files};
+//! use futures::TryStreamExt;
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let mut stream = client.get_object_stream("video.mp4", ..).await.unwrap();
+//! while let Some(chunk) = stream.try_next().await.unwrap() {
+//!     println!("{}", chunk.len());
+//! }
+//! # }
+//! ```
+//!
+//! [`Stream`]: futures::Stream
+//! [`AsyncRead`]: futures::io::AsyncRead
+
+use std::{
+    fmt, io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_stream::try_stream;
+use bytes::{Buf, Bytes};
+use futures::{io::AsyncRead, ready, Stream};
+use reqwest::Response;
+
+use crate::file::FileError;
+
+/// # 文件内容的流
+///
+/// 每次返回响应体的一个分块
+pub struct ObjectStream {
+    inner: Pin<Box<dyn Stream<Item = Result<Bytes, FileError>> + Send>>,
+    /// 作为 AsyncRead 使用时，未读完的分块
+    chunk: Bytes,
+}
+
+impl ObjectStream {
+    pub(crate) fn new(mut response: Response) -> Self {
+        let inner = try_stream! {
+            while let Some(chunk) = response.chunk().await? {
+                yield chunk;
+            }
+        };
+
+        Self {
+            inner: Box::pin(inner),
+            chunk: Bytes::new(),
+        }
+    }
+}
+
+impl fmt::Debug for ObjectStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObjectStream").finish_non_exhaustive()
+    }
+}
+
+impl Stream for ObjectStream {
+    type Item = Result<Bytes, FileError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if !this.chunk.is_empty() {
+            return Poll::Ready(Some(Ok(std::mem::take(&mut this.chunk))));
+        }
+        this.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl AsyncRead for ObjectStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        while this.chunk.is_empty() {
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => this.chunk = chunk,
+                Some(Err(e)) => return Poll::Ready(Err(e.into())),
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let len = buf.len().min(this.chunk.len());
+        buf[..len].copy_from_slice(&this.chunk[..len]);
+        this.chunk.advance(len);
+        Poll::Ready(Ok(len))
+    }
+}
+
+/// 同步的流式读取
+#[cfg(feature = "blocking")]
+pub mod blocking {
+    use std::io::{Read, Result as IoResult};
+
+    use reqwest::blocking::Response;
+
+    /// # 文件内容的 Reader
+    ///
+    /// 实现了 std [`Read`]，直接读取响应体
+    #[derive(Debug)]
+    pub struct ObjectReader {
+        response: Response,
+    }
+
+    impl ObjectReader {
+        pub(crate) fn new(response: Response) -> Self {
+            Self { response }
+        }
+    }
+
+    impl Read for ObjectReader {
+        fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+            self.response.read(buf)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use futures::{io::AsyncReadExt, TryStreamExt};
+    use reqwest::{Request, Response};
+
+    use crate::{
+        builder::{BuilderError, Middleware},
+        file::Files,
+        Client,
+    };
+
+    #[derive(Debug)]
+    struct MockGet {}
+
+    #[async_trait]
+    impl Middleware for MockGet {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(request.headers().get("Range").unwrap(), "bytes=0-");
+            let response = http::Response::builder()
+                .status(200)
+                .body("0123456789")
+                .unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    #[tokio::test]
+    async fn stream() {
+        let client = Client::test_init().middleware(Arc::new(MockGet {}));
+
+        let stream = client.get_object_stream("abc.txt", ..).await.unwrap();
+        let chunks: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), b"0123456789");
+    }
+
+    #[tokio::test]
+    async fn async_read() {
+        let client = Client::test_init().middleware(Arc::new(MockGet {}));
+
+        let mut stream = client.get_object_stream("abc.txt", ..).await.unwrap();
+        let mut buf = [0u8; 4];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"0123");
+
+        let mut rest = Vec::new();
+        stream.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"456789");
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_read() {
+        use std::{io::Read, rc::Rc};
+
+        use crate::blocking::builder::Middleware;
+        use crate::file::BlockingFiles;
+        use crate::ClientRc;
+        use reqwest::blocking::{Request, Response};
+
+        #[derive(Debug)]
+        struct MockGet {}
+
+        impl Middleware for MockGet {
+            fn handle(&self, _request: Request) -> Result<Response, BuilderError> {
+                let response = http::Response::builder()
+                    .status(200)
+                    .body("0123456789")
+                    .unwrap();
+                Ok(Response::from(response))
+            }
+        }
+
+        let client = ClientRc::test_init().middleware(Rc::new(MockGet {}));
+        let mut reader = client.get_object_reader("abc.txt", ..).unwrap();
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        assert_eq!(content, "0123456789");
+    }
+}
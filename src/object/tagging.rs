@@ -0,0 +1,314 @@
+//! # 对象标签
+//!
+//! [`Tagging`] 是一组键值对，可以在上传时通过 `x-oss-tagging` 设置，
+//! 也可以通过 [`Files`] 中的 `get_object_tagging`，`put_object_tagging`，`delete_object_tagging` 读取和修改
+//!
+//! ```rust,no_run
+//! # use aliyun_oss_client::{Client, file::Files, object::{PutObject, Tagging}};
+//! # #[tokio::main]
+//! # async fn main() {
+//! dotenv::dotenv().ok();
+//! let client = Client::from_env().unwrap();
+//!
+//! let mut tagging = Tagging::new();
+//! tagging.insert("project", "blue").unwrap();
+//!
+//! // 上传时设置标签
+//! let tags = tagging.to_string();
+//! let options = PutObject {
+//!     tagging: Some(&tags),
+//!     ..Default::default()
+//! };
+//! client
+//!     .put_object_with(b"abc".to_vec(), "text/plain", "abc.txt", &options)
+//!     .await
+//!     .unwrap();
+//!
+//! tagging.insert("expire", "30d").unwrap();
+//! client.put_object_tagging("abc.txt", &tagging).await.unwrap();
+//!
+//! let tagging = client.get_object_tagging("abc.txt").await.unwrap();
+//! assert_eq!(tagging.get("expire"), Some("30d"));
+//! # }
+//! ```
+//! [aliyun docs](https://help.aliyun.com/zh/oss/developer-reference/putobjecttagging)
+//!
+//! [`Files`]: crate::file::Files
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use quick_xml::escape::escape;
+
+use crate::decode::{InnerItemError, RefineTagging};
+
+/// # 对象的标签集合
+///
+/// 最多 10 个标签，标签键长度为 1~128 个字符，标签值最长 256 个字符，标签键不能重复
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tagging {
+    tags: Vec<(String, String)>,
+}
+
+impl Tagging {
+    /// 最多的标签数量
+    pub const MAX_TAGS: usize = 10;
+    /// 标签键的最大长度
+    pub const MAX_KEY_LEN: usize = 128;
+    /// 标签值的最大长度
+    pub const MAX_VALUE_LEN: usize = 256;
+
+    /// 初始化空的标签集合
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # 添加标签
+    /// 标签键已存在时，会覆盖原来的值
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Result<(), InvalidTagging> {
+        let (key, value) = (key.into(), value.into());
+
+        let key_len = key.chars().count();
+        if key_len == 0 || key_len > Self::MAX_KEY_LEN {
+            return Err(InvalidTagging::new(key, InvalidTaggingKind::Key));
+        }
+        if value.chars().count() > Self::MAX_VALUE_LEN {
+            return Err(InvalidTagging::new(key, InvalidTaggingKind::Value));
+        }
+
+        if let Some((_, v)) = self.tags.iter_mut().find(|(k, _)| *k == key) {
+            *v = value;
+        } else if self.tags.len() >= Self::MAX_TAGS {
+            return Err(InvalidTagging::new(key, InvalidTaggingKind::TooMany));
+        } else {
+            self.tags.push((key, value));
+        }
+        Ok(())
+    }
+
+    /// 获取标签的值
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 删除标签，返回原来的值
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.tags.iter().position(|(k, _)| k == key)?;
+        Some(self.tags.remove(index).1)
+    }
+
+    /// 标签数量
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// 是否没有标签
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// 按添加顺序遍历标签
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// 生成 PutObjectTagging 接口的请求体
+    pub(crate) fn to_xml(&self) -> String {
+        let mut body = String::from(r#"<?xml version="1.0" encoding="UTF-8"?><Tagging><TagSet>"#);
+        for (key, value) in &self.tags {
+            body.push_str("<Tag><Key>");
+            body.push_str(&escape(key));
+            body.push_str("</Key><Value>");
+            body.push_str(&escape(value));
+            body.push_str("</Value></Tag>");
+        }
+        body.push_str("</TagSet></Tagging>");
+        body
+    }
+
+    /// 解析 GetObjectTagging 接口的响应
+    pub(crate) fn from_xml(xml: &str) -> Result<Self, InnerItemError> {
+        let mut tagging = Self::new();
+        tagging.decode(xml)?;
+        Ok(tagging)
+    }
+}
+
+impl RefineTagging<InvalidTagging> for Tagging {
+    fn set_tag(&mut self, key: &str, value: &str) -> Result<(), InvalidTagging> {
+        self.insert(key, value)
+    }
+}
+
+impl Display for Tagging {
+    /// `x-oss-tagging` 使用的格式，键和值经过 URL 编码
+    ///
+    /// ```
+    /// # use aliyun_oss_client::object::Tagging;
+    /// let tagging = Tagging::try_from([("a", "1"), ("b c", "2")]).unwrap();
+    /// assert_eq!(tagging.to_string(), "a=1&b%20c=2");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.tags.iter().enumerate() {
+            if index > 0 {
+                f.write_str("&")?;
+            }
+            write!(
+                f,
+                "{}={}",
+                utf8_percent_encode(key, NON_ALPHANUMERIC),
+                utf8_percent_encode(value, NON_ALPHANUMERIC)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, V, const N: usize> TryFrom<[(K, V); N]> for Tagging
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    type Error = InvalidTagging;
+
+    fn try_from(tags: [(K, V); N]) -> Result<Self, Self::Error> {
+        let mut tagging = Self::new();
+        for (key, value) in tags {
+            tagging.insert(key, value)?;
+        }
+        Ok(tagging)
+    }
+}
+
+/// 标签不符合 OSS 限制，或解析标签失败时的错误
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidTagging {
+    source: String,
+    kind: InvalidTaggingKind,
+}
+
+impl InvalidTagging {
+    fn new(source: String, kind: InvalidTaggingKind) -> Self {
+        Self { source, kind }
+    }
+}
+
+impl Display for InvalidTagging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use InvalidTaggingKind::*;
+        match self.kind {
+            Key => write!(
+                f,
+                "tag key must be 1 to {} characters, gived: {}",
+                Tagging::MAX_KEY_LEN,
+                self.source
+            ),
+            Value => write!(
+                f,
+                "tag value must be at most {} characters, key: {}",
+                Tagging::MAX_VALUE_LEN,
+                self.source
+            ),
+            TooMany => write!(
+                f,
+                "at most {} tags, can not add: {}",
+                Tagging::MAX_TAGS,
+                self.source
+            ),
+        }
+    }
+}
+
+impl Error for InvalidTagging {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+enum InvalidTaggingKind {
+    Key,
+    Value,
+    TooMany,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tagging;
+
+    #[test]
+    fn insert() {
+        let mut tagging = Tagging::new();
+        tagging.insert("a", "1").unwrap();
+        tagging.insert("b", "").unwrap();
+        tagging.insert("a", "2").unwrap();
+        assert_eq!(tagging.len(), 2);
+        assert_eq!(tagging.get("a"), Some("2"));
+        assert_eq!(tagging.iter().collect::<Vec<_>>(), [("a", "2"), ("b", "")]);
+
+        let err = tagging.insert("", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "tag key must be 1 to 128 characters, gived: "
+        );
+        tagging.insert("k".repeat(128), "1").unwrap();
+        tagging.insert("k".repeat(129), "1").unwrap_err();
+        tagging.insert("c", "v".repeat(256)).unwrap();
+        tagging.insert("d", "v".repeat(257)).unwrap_err();
+
+        for i in tagging.len()..Tagging::MAX_TAGS {
+            tagging.insert(i.to_string(), "1").unwrap();
+        }
+        let err = tagging.insert("e", "1").unwrap_err();
+        assert_eq!(err.to_string(), "at most 10 tags, can not add: e");
+        // 修改已有的标签不受数量限制
+        tagging.insert("a", "3").unwrap();
+
+        assert_eq!(tagging.remove("a"), Some("3".to_owned()));
+        assert_eq!(tagging.remove("a"), None);
+        tagging.insert("e", "1").unwrap();
+    }
+
+    #[test]
+    fn xml() {
+        let tagging = Tagging::try_from([("a&b", "<1>"), ("c", "")]).unwrap();
+        let xml = tagging.to_xml();
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?><Tagging><TagSet><Tag><Key>a&amp;b</Key><Value>&lt;1&gt;</Value></Tag><Tag><Key>c</Key><Value></Value></Tag></TagSet></Tagging>"#
+        );
+        assert_eq!(Tagging::from_xml(&xml).unwrap(), tagging);
+
+        let xml = "<Tagging><TagSet><Tag><Key>a</Key><Value>1</Value></Tag><Tag><Key>b</Key><Value /></Tag></TagSet></Tagging>";
+        let tagging = Tagging::from_xml(xml).unwrap();
+        assert_eq!(tagging.iter().collect::<Vec<_>>(), [("a", "1"), ("b", "")]);
+
+        let tagging = Tagging::from_xml("<Tagging><TagSet></TagSet></Tagging>").unwrap();
+        assert!(tagging.is_empty());
+
+        let err =
+            Tagging::from_xml("<Tagging><TagSet><Tag><Value>1</Value></Tag></TagSet></Tagging>")
+                .unwrap_err();
+        assert_eq!(err.to_string(), "not found tag: Key");
+
+        let err =
+            Tagging::from_xml("<Tagging><TagSet><Tag><Key>a</Value></Tag></TagSet></Tagging>")
+                .unwrap_err();
+        assert!(err.get_source().is_some());
+    }
+
+    #[test]
+    fn header() {
+        let tagging = Tagging::try_from([("a", "1"), ("k/v", "x+y")]).unwrap();
+        assert_eq!(tagging.to_string(), "a=1&k%2Fv=x%2By");
+        assert_eq!(Tagging::new().to_string(), "");
+    }
+}
@@ -222,6 +222,31 @@ mod tests {
         let object2 = builder.build();
         assert_eq!(object2.base.bucket_name().as_ref(), "bucket-name2");
     }
+
+    #[test]
+    fn test_object_set_meta() {
+        use crate::object::ObjectMeta;
+        use http::{HeaderMap, HeaderValue};
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-length", HeaderValue::from_static("10"));
+        headers.insert("etag", HeaderValue::from_static("foo1"));
+        headers.insert(
+            "last-modified",
+            HeaderValue::from_static("Fri, 02 Jan 1970 10:10:00 GMT"),
+        );
+        headers.insert("x-oss-object-type", HeaderValue::from_static("Normal"));
+        headers.insert("x-oss-storage-class", HeaderValue::from_static("IA"));
+        let meta = ObjectMeta::from_headers(&headers).unwrap();
+
+        let mut object = Object::<ArcPointer>::default();
+        object.set_meta(&meta);
+        assert_eq!(object.last_modified.to_string(), "1970-01-02 10:10:00 UTC");
+        assert_eq!(object.etag, "foo1");
+        assert_eq!(object._type, "Normal");
+        assert_eq!(object.size, 10);
+        assert_eq!(object.storage_class, StorageClass::IA);
+    }
 }
 
 #[cfg(feature = "blocking")]
@@ -454,6 +479,9 @@ fn test_storage_class_new() {
     let value = StorageClass::new("ColdArchive").unwrap();
     assert_eq!(value.kind, StorageClassKind::ColdArchive);
 
+    let value = StorageClass::new("DeepColdArchive").unwrap();
+    assert_eq!(value.kind, StorageClassKind::DeepColdArchive);
+
     assert!(StorageClass::new("eeeeee").is_none());
 }
 
@@ -625,3 +653,132 @@ fn to_sign_url() {
     let url = object.to_sign_url(&"key".into(), &"secret".into(), 12345678);
     assert_eq!(url.as_str(), "https://abc.oss-cn-shanghai.aliyuncs.com/img1.png?OSSAccessKeyId=key&Expires=12345678&Signature=v0HY%2FAKa4c8lnwzUvN9vWlMaem0%3D");
 }
+
+mod copy_object {
+    use crate::object::{CopyDirective, CopyObject, Encryption, ObjectAcl, StorageClass};
+
+    #[test]
+    fn source() {
+        let mut options = CopyObject {
+            copy_source: "dir/abc 1.txt",
+            ..Default::default()
+        };
+        assert_eq!(options.source("bar"), "/bar/dir/abc%201.txt");
+
+        options.copy_source = "/other/abc.txt?versionId=CAEQ";
+        assert_eq!(options.source("bar"), "/other/abc.txt?versionId=CAEQ");
+    }
+
+    #[test]
+    fn source_parts() {
+        let mut options = CopyObject {
+            copy_source: "dir/abc.txt",
+            ..Default::default()
+        };
+        assert_eq!(options.source_parts("bar"), ("bar", "dir/abc.txt", None));
+
+        options.copy_source = "/other/dir/abc.txt?versionId=CAEQ";
+        assert_eq!(
+            options.source_parts("bar"),
+            ("other", "dir/abc.txt", Some("CAEQ"))
+        );
+    }
+
+    #[test]
+    fn headers() {
+        let options = CopyObject {
+            copy_source: "abc.txt",
+            ..Default::default()
+        };
+        let headers = options.headers("bar").unwrap();
+        let list: Vec<_> = headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            list,
+            vec![
+                ("x-oss-copy-source", "/bar/abc.txt"),
+                ("x-oss-metadata-directive", "COPY"),
+                ("x-oss-tagging-directive", "COPY"),
+            ]
+        );
+
+        let options = CopyObject {
+            forbid_overwrite: true,
+            copy_source: "abc.txt",
+            copy_source_if_match: Some("etag1"),
+            copy_source_if_modified_since: Some("Fri, 24 Feb 2012 06:07:48 GMT"),
+            metadata_directive: CopyDirective::Replace,
+            user_meta: vec![("author", "tu6ge")],
+            server_side_encryption: Some(Encryption::Kms),
+            server_side_encryption_key_id: Some("key1"),
+            object_acl: ObjectAcl::PublicRead,
+            storage_class: Some(StorageClass::IA),
+            tagging: Some("a=1"),
+            tagging_directive: CopyDirective::Replace,
+            ..Default::default()
+        };
+        let headers = options.headers("bar").unwrap();
+        let list: Vec<_> = headers
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+            .collect();
+        assert_eq!(
+            list,
+            vec![
+                ("x-oss-copy-source", "/bar/abc.txt"),
+                ("x-oss-metadata-directive", "REPLACE"),
+                ("x-oss-tagging-directive", "REPLACE"),
+                ("x-oss-forbid-overwrite", "true"),
+                ("x-oss-copy-source-if-match", "etag1"),
+                (
+                    "x-oss-copy-source-if-modified-since",
+                    "Fri, 24 Feb 2012 06:07:48 GMT"
+                ),
+                ("x-oss-server-side-encryption", "KMS"),
+                ("x-oss-server-side-encryption-key-id", "key1"),
+                ("x-oss-storage-class", "IA"),
+                ("x-oss-tagging", "a=1"),
+                ("x-oss-object-acl", "public-read"),
+                ("x-oss-meta-author", "tu6ge"),
+            ]
+        );
+
+        let options = CopyObject {
+            copy_source: "abc.txt",
+            user_meta: vec![("a b", "c")],
+            ..Default::default()
+        };
+        assert!(options.headers("bar").is_err());
+    }
+}
+
+#[test]
+fn put_object_headers() {
+    use crate::object::PutObject;
+
+    assert!(PutObject::default().headers().unwrap().is_empty());
+
+    let options = PutObject {
+        forbid_overwrite: true,
+        object_acl: super::ObjectAcl::PublicRead,
+        content_encoding: Some("gzip"),
+        user_meta: vec![("author", "tu6ge")],
+        ..Default::default()
+    };
+    let headers = options.headers().unwrap();
+    let list: Vec<_> = headers
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.to_str().unwrap()))
+        .collect();
+    assert_eq!(
+        list,
+        vec![
+            ("x-oss-forbid-overwrite", "true"),
+            ("x-oss-object-acl", "public-read"),
+            ("content-encoding", "gzip"),
+            ("x-oss-meta-author", "tu6ge"),
+        ]
+    );
+}
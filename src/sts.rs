@@ -21,9 +21,15 @@
 //! println!("{:?}", builder);
 //! # }
 //! ```
+//!
+//! 也可以通过 [`AssumeRoleProvider`] 或 [`AssumeRoleWithOidcProvider`] 调用 STS 接口获取临时凭证，并自动续期
 
 use http::{header::InvalidHeaderValue, HeaderValue};
 
+pub mod assume_role;
+
+pub use assume_role::{AssumeRoleProvider, AssumeRoleWithOidcProvider};
+
 use crate::{auth::AuthBuilder, client::Client, BucketName, EndPoint, KeyId, KeySecret};
 
 /// 给 Client 增加 STS 能力
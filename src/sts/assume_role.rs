@@ -0,0 +1,518 @@
+//! # 通过 STS 服务获取临时凭证
+//!
+//! - [`AssumeRoleProvider`] 使用 RAM 用户的 AccessKey 扮演 RAM 角色
+//! - [`AssumeRoleWithOidcProvider`] 使用 OIDC token 扮演 RAM 角色，如 Kubernetes 的 RRSA
+//!
+//! 两者都实现了 [`CredentialsProvider`]，凭证会在过期前自动续期
+//!
+//! [aliyun docs](https://help.aliyun.com/zh/ram/developer-reference/api-sts-2015-04-01-assumerole)
+//!
+//! ## 用法
+//! ```
+//! # async fn run() {
+//! use aliyun_oss_client::sts::AssumeRoleProvider;
+//!
+//! let mut provider = AssumeRoleProvider::new(
+//!     "key".into(),
+//!     "secret".into(),
+//!     "acs:ram::123456789012****:role/adminrole",
+//! );
+//! provider.session_name("my-session").duration_seconds(3600);
+//!
+//! let client = provider
+//!     .into_client("qingdao".parse().unwrap(), "my-bucket".parse().unwrap())
+//!     .await
+//!     .unwrap();
+//! let buckets = client.get_bucket_list().await;
+//! # }
+//! ```
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client as HttpClient, Url};
+use serde::Deserialize;
+
+use super::STS;
+use crate::{
+    auth::v4::uri_encode,
+    client::Client,
+    credentials::{Credentials, CredentialsCache, CredentialsError, CredentialsProvider},
+    BucketName, EndPoint, KeyId, KeySecret,
+};
+
+const ENDPOINT: &str = "https://sts.aliyuncs.com";
+const VERSION: &str = "2015-04-01";
+const TIMESTAMP: &str = "%Y-%m-%dT%H:%M:%SZ";
+const DEFAULT_DURATION: u32 = 3600;
+
+/// STS 服务无法访问时，避免获取凭证的请求一直挂起
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+const ENV_ROLE_ARN: &str = "ALIBABA_CLOUD_ROLE_ARN";
+const ENV_OIDC_PROVIDER_ARN: &str = "ALIBABA_CLOUD_OIDC_PROVIDER_ARN";
+const ENV_OIDC_TOKEN_FILE: &str = "ALIBABA_CLOUD_OIDC_TOKEN_FILE";
+const ENV_SESSION_NAME: &str = "ALIBABA_CLOUD_ROLE_SESSION_NAME";
+
+/// 两种扮演角色方式的公共参数
+#[derive(Debug, Clone)]
+struct RoleSession {
+    client: HttpClient,
+    endpoint: String,
+    role_arn: String,
+    session_name: String,
+    policy: Option<String>,
+    duration: u32,
+}
+
+impl RoleSession {
+    fn new(role_arn: String) -> Self {
+        Self {
+            client: HttpClient::builder()
+                .connect_timeout(CONNECT_TIMEOUT)
+                .timeout(TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            endpoint: ENDPOINT.to_owned(),
+            role_arn,
+            session_name: format!("aliyun-oss-client-{}", Utc::now().timestamp()),
+            policy: None,
+            duration: DEFAULT_DURATION,
+        }
+    }
+
+    fn params(&self, action: &str, now: DateTime<Utc>) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("Action", action.to_owned()),
+            ("Format", "JSON".to_owned()),
+            ("Version", VERSION.to_owned()),
+            ("Timestamp", now.format(TIMESTAMP).to_string()),
+            ("RoleArn", self.role_arn.to_owned()),
+            ("RoleSessionName", self.session_name.to_owned()),
+            ("DurationSeconds", self.duration.to_string()),
+        ];
+        if let Some(policy) = &self.policy {
+            params.push(("Policy", policy.to_owned()));
+        }
+        params
+    }
+
+    async fn send(&self, params: &[(&str, String)]) -> Result<Credentials, CredentialsError> {
+        let url = format!(
+            "{}/?{}",
+            self.endpoint.trim_end_matches('/'),
+            to_query(params)
+        );
+        let url: Url = url.parse().map_err(CredentialsError::new)?;
+
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            let error: StsError = serde_json::from_str(&text).unwrap_or_default();
+            return Err(CredentialsError::new(format!(
+                "sts response {}, code: {}, message: {}",
+                status, error.code, error.message,
+            )));
+        }
+        parse(&text)
+    }
+}
+
+/// 使用 RAM 用户的 AccessKey 调用 `AssumeRole` 接口，获取 RAM 角色的临时凭证
+#[derive(Debug, Clone)]
+pub struct AssumeRoleProvider {
+    key: KeyId,
+    secret: KeySecret,
+    session: RoleSession,
+}
+
+impl AssumeRoleProvider {
+    /// 使用 RAM 用户的 AccessKey 和要扮演的角色 ARN 初始化
+    pub fn new<R: Into<String>>(key: KeyId, secret: KeySecret, role_arn: R) -> Self {
+        Self {
+            key,
+            secret,
+            session: RoleSession::new(role_arn.into()),
+        }
+    }
+
+    /// 设置 STS 服务地址，默认为 `https://sts.aliyuncs.com`
+    pub fn endpoint<E: Into<String>>(&mut self, endpoint: E) -> &mut Self {
+        self.session.endpoint = endpoint.into();
+        self
+    }
+
+    /// 设置角色会话名称
+    pub fn session_name<N: Into<String>>(&mut self, name: N) -> &mut Self {
+        self.session.session_name = name.into();
+        self
+    }
+
+    /// 设置会话权限策略，用于进一步限制临时凭证的权限
+    pub fn policy<P: Into<String>>(&mut self, policy: P) -> &mut Self {
+        self.session.policy = Some(policy.into());
+        self
+    }
+
+    /// 设置临时凭证的有效时间，单位秒，默认为 3600
+    pub fn duration_seconds(&mut self, seconds: u32) -> &mut Self {
+        self.session.duration = seconds;
+        self
+    }
+
+    /// 获取临时凭证，并用它初始化 [`Client`]，之后的请求会自动续期
+    ///
+    /// [`Client`]: crate::client::Client
+    pub async fn into_client(
+        self,
+        endpoint: EndPoint,
+        bucket: BucketName,
+    ) -> Result<Client, CredentialsError> {
+        into_client(self, endpoint, bucket).await
+    }
+
+    fn signed_params(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(&'static str, String)>, CredentialsError> {
+        let mut params = self.session.params("AssumeRole", now);
+        params.extend([
+            ("AccessKeyId", self.key.as_ref().to_owned()),
+            ("SignatureMethod", "HMAC-SHA1".to_owned()),
+            ("SignatureVersion", "1.0".to_owned()),
+            ("SignatureNonce", nonce(now)),
+        ]);
+        let signature = self
+            .secret
+            .encryption_rpc(string_to_sign(&params).as_bytes())
+            .map_err(CredentialsError::new)?;
+        params.push(("Signature", signature));
+        Ok(params)
+    }
+}
+
+#[async_trait]
+impl CredentialsProvider for AssumeRoleProvider {
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        let params = self.signed_params(Utc::now())?;
+        self.session.send(&params).await
+    }
+}
+
+/// 使用 OIDC token 调用 `AssumeRoleWithOIDC` 接口，获取 RAM 角色的临时凭证
+///
+/// token 文件会在每次获取凭证时重新读取
+#[derive(Debug, Clone)]
+pub struct AssumeRoleWithOidcProvider {
+    oidc_provider_arn: String,
+    token_file: PathBuf,
+    session: RoleSession,
+}
+
+impl AssumeRoleWithOidcProvider {
+    /// 使用角色 ARN，OIDC 身份提供商 ARN 和 OIDC token 文件路径初始化
+    pub fn new<R, O, F>(role_arn: R, oidc_provider_arn: O, token_file: F) -> Self
+    where
+        R: Into<String>,
+        O: Into<String>,
+        F: Into<PathBuf>,
+    {
+        Self {
+            oidc_provider_arn: oidc_provider_arn.into(),
+            token_file: token_file.into(),
+            session: RoleSession::new(role_arn.into()),
+        }
+    }
+
+    /// 通过 RRSA 注入的环境变量初始化
+    ///
+    /// - `ALIBABA_CLOUD_ROLE_ARN`
+    /// - `ALIBABA_CLOUD_OIDC_PROVIDER_ARN`
+    /// - `ALIBABA_CLOUD_OIDC_TOKEN_FILE`
+    /// - `ALIBABA_CLOUD_ROLE_SESSION_NAME` 可选
+    pub fn from_env() -> Result<Self, CredentialsError> {
+        let mut provider = Self::new(
+            env::var(ENV_ROLE_ARN)?,
+            env::var(ENV_OIDC_PROVIDER_ARN)?,
+            env::var(ENV_OIDC_TOKEN_FILE)?,
+        );
+        if let Ok(name) = env::var(ENV_SESSION_NAME) {
+            provider.session_name(name);
+        }
+        Ok(provider)
+    }
+
+    /// 设置 STS 服务地址，默认为 `https://sts.aliyuncs.com`
+    pub fn endpoint<E: Into<String>>(&mut self, endpoint: E) -> &mut Self {
+        self.session.endpoint = endpoint.into();
+        self
+    }
+
+    /// 设置角色会话名称
+    pub fn session_name<N: Into<String>>(&mut self, name: N) -> &mut Self {
+        self.session.session_name = name.into();
+        self
+    }
+
+    /// 设置会话权限策略，用于进一步限制临时凭证的权限
+    pub fn policy<P: Into<String>>(&mut self, policy: P) -> &mut Self {
+        self.session.policy = Some(policy.into());
+        self
+    }
+
+    /// 设置临时凭证的有效时间，单位秒，默认为 3600
+    pub fn duration_seconds(&mut self, seconds: u32) -> &mut Self {
+        self.session.duration = seconds;
+        self
+    }
+
+    /// 获取临时凭证，并用它初始化 [`Client`]，之后的请求会自动续期
+    ///
+    /// [`Client`]: crate::client::Client
+    pub async fn into_client(
+        self,
+        endpoint: EndPoint,
+        bucket: BucketName,
+    ) -> Result<Client, CredentialsError> {
+        into_client(self, endpoint, bucket).await
+    }
+}
+
+#[async_trait]
+impl CredentialsProvider for AssumeRoleWithOidcProvider {
+    async fn credentials(&self) -> Result<Credentials, CredentialsError> {
+        let token = fs::read_to_string(&self.token_file).map_err(CredentialsError::new)?;
+        let mut params = self.session.params("AssumeRoleWithOIDC", Utc::now());
+        params.extend([
+            ("OIDCProviderArn", self.oidc_provider_arn.to_owned()),
+            ("OIDCToken", token.trim().to_owned()),
+        ]);
+        self.session.send(&params).await
+    }
+}
+
+/// 用首次获取的凭证初始化 Client，并由 provider 负责之后的续期
+async fn into_client<P: CredentialsProvider>(
+    provider: P,
+    endpoint: EndPoint,
+    bucket: BucketName,
+) -> Result<Client, CredentialsError> {
+    let cred = provider.credentials().await?;
+    let mut client = Client::new_with_sts(
+        cred.key().clone(),
+        cred.secret().clone(),
+        endpoint,
+        bucket,
+        cred.security_token().unwrap_or_default(),
+    )?;
+
+    let cache = CredentialsCache::new(provider);
+    cache.set(cred);
+    client.credentials_cache(cache);
+    Ok(client)
+}
+
+/// 每次请求唯一的随机数
+fn nonce(now: DateTime<Utc>) -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    format!(
+        "{}{}",
+        now.timestamp_micros(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// 按参数名排序后编码
+fn to_query(params: &[(&str, String)]) -> String {
+    let mut list: Vec<_> = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", uri_encode(k), uri_encode(v)))
+        .collect();
+    list.sort();
+    list.join("&")
+}
+
+/// RPC 风格接口的待签名字符串
+fn string_to_sign(params: &[(&str, String)]) -> String {
+    format!("GET&{}&{}", uri_encode("/"), uri_encode(&to_query(params)))
+}
+
+/// STS 接口返回的错误
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct StsError {
+    code: String,
+    message: String,
+}
+
+/// STS 接口返回的内容，只解析其中的凭证
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StsResponse {
+    credentials: StsCredentials,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StsCredentials {
+    access_key_id: String,
+    access_key_secret: String,
+    security_token: String,
+    expiration: String,
+}
+
+/// 解析 STS 接口返回的凭证
+fn parse(text: &str) -> Result<Credentials, CredentialsError> {
+    let StsResponse { credentials } = serde_json::from_str(text).map_err(CredentialsError::new)?;
+
+    let expiration = DateTime::parse_from_rfc3339(&credentials.expiration)
+        .map_err(CredentialsError::new)?
+        .with_timezone(&Utc);
+
+    Ok(Credentials::new_with_sts(
+        credentials.access_key_id.into(),
+        credentials.access_key_secret.into(),
+        credentials.security_token,
+        Some(expiration),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::credentials::mock_server;
+
+    const BODY: &str = r#"{"RequestId":"6894B13B-6D71-4EF5-88FA-F32781734A7F","AssumedRoleUser":{"Arn":"acs:ram::123456789012****:role/adminrole/alice","AssumedRoleId":"34458433936495****:alice"},"Credentials":{"SecurityToken":"token","Expiration":"2015-04-09T11:52:19Z","AccessKeySecret":"secret","AccessKeyId":"STS.key"}}"#;
+
+    fn provider() -> AssumeRoleProvider {
+        let mut provider =
+            AssumeRoleProvider::new("ak".into(), "sk".into(), "acs:ram::1:role/admin");
+        provider.session_name("alice").policy("{\"a\":1}");
+        provider
+    }
+
+    #[test]
+    fn test_string_to_sign() {
+        let params = [
+            ("Action", "AssumeRole".to_owned()),
+            ("RoleArn", "acs:ram::1:role/admin".to_owned()),
+        ];
+        assert_eq!(
+            string_to_sign(&params),
+            "GET&%2F&Action%3DAssumeRole%26RoleArn%3Dacs%253Aram%253A%253A1%253Arole%252Fadmin"
+        );
+    }
+
+    #[test]
+    fn test_signed_params() {
+        let now = Utc.with_ymd_and_hms(2023, 12, 3, 12, 0, 0).unwrap();
+        let params = provider().signed_params(now).unwrap();
+
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("Timestamp"), Some("2023-12-03T12:00:00Z"));
+        assert_eq!(get("RoleSessionName"), Some("alice"));
+        assert_eq!(get("Policy"), Some("{\"a\":1}"));
+        assert_eq!(get("DurationSeconds"), Some("3600"));
+
+        let expected = KeySecret::new("sk&")
+            .encryption(string_to_sign(&params[..params.len() - 1]).as_bytes())
+            .unwrap();
+        assert_eq!(get("Signature"), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let cred = parse(BODY).unwrap();
+        assert_eq!(cred.key().as_ref(), "STS.key");
+        assert_eq!(cred.secret(), &"secret".into());
+        assert_eq!(cred.security_token(), Some("token"));
+        assert_eq!(
+            cred.expiration(),
+            Some(&Utc.with_ymd_and_hms(2015, 4, 9, 11, 52, 19).unwrap())
+        );
+
+        assert!(parse(r#"{"RequestId":"1","Credentials":{"AccessKeyId":"STS.key"}}"#).is_err());
+        assert!(parse(r#"{"Code":"InvalidParameter","Message":"bad"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn assume_role() {
+        let endpoint = mock_server(|request| {
+            if request.starts_with("GET /?")
+                && request.contains("Action=AssumeRole&")
+                && request.contains("Signature=")
+            {
+                ("200 OK", BODY.to_owned())
+            } else {
+                (
+                    "400 Bad Request",
+                    r#"{"Code":"InvalidParameter","Message":"bad"}"#.to_owned(),
+                )
+            }
+        });
+        let mut provider = provider();
+        provider.endpoint(format!("{endpoint}other"));
+        let err = provider.credentials().await.unwrap_err();
+        let message = std::error::Error::source(&err).unwrap().to_string();
+        assert!(message.contains("code: InvalidParameter, message: bad"));
+
+        provider.endpoint(endpoint);
+        let client = provider
+            .into_client(EndPoint::CN_QINGDAO, "foo4".parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(client.get_key().as_ref(), "STS.key");
+    }
+
+    #[tokio::test]
+    async fn assume_role_with_oidc() {
+        let endpoint = mock_server(|request| {
+            if request.contains("Action=AssumeRoleWithOIDC&")
+                && request.contains("OIDCToken=oidc-token")
+                && !request.contains("Signature=")
+            {
+                ("200 OK", BODY.to_owned())
+            } else {
+                (
+                    "400 Bad Request",
+                    r#"{"Code":"InvalidParameter","Message":"bad"}"#.to_owned(),
+                )
+            }
+        });
+        let token_file = env::temp_dir().join(format!(
+            "aliyun-oss-client-oidc-token-{}",
+            std::process::id()
+        ));
+        fs::write(&token_file, "oidc-token\n").unwrap();
+
+        let mut provider = AssumeRoleWithOidcProvider::new(
+            "acs:ram::1:role/admin",
+            "acs:ram::1:oidc-provider/ack",
+            &token_file,
+        );
+        provider.endpoint(endpoint.clone());
+        let cred = provider.credentials().await.unwrap();
+        assert_eq!(cred.security_token(), Some("token"));
+        fs::remove_file(token_file).unwrap();
+
+        let mut provider =
+            AssumeRoleWithOidcProvider::new("a", "b", env::temp_dir().join("not-exists"));
+        provider.endpoint(endpoint);
+        assert!(provider.credentials().await.is_err());
+    }
+}
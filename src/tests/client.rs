@@ -64,6 +64,101 @@ fn test_builder_with_header() {
     );
 }
 
+#[test]
+fn test_builder_with_header_v4() {
+    use crate::auth::SignVersion;
+
+    let mut client = Client::<ClientWithMiddleware>::new(
+        "foo1".into(),
+        "foo2".into(),
+        EndPoint::CN_QINGDAO,
+        "foo4".parse().unwrap(),
+    );
+    client.sign_version(SignVersion::V4);
+    let url = "https://foo4.oss-cn-qingdao.aliyuncs.com/abc.txt?uploads"
+        .parse()
+        .unwrap();
+    let resource = CanonicalizedResource::new("/foo4/abc.txt?uploads");
+    let headers = vec![(CONTENT_TYPE, HeaderValue::from_static("application/json"))];
+    let request = client
+        .builder_with_header(Method::POST, url, resource, headers)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        request.headers().get("x-oss-date"),
+        Some(&HeaderValue::from_static("20221006T204000Z"))
+    );
+    assert_eq!(
+        request.headers().get("x-oss-content-sha256"),
+        Some(&HeaderValue::from_static("UNSIGNED-PAYLOAD"))
+    );
+    assert!(request.headers().get("canonicalizedresource").is_none());
+    assert_eq!(
+        request.headers().get("authorization"),
+        Some(&HeaderValue::from_static("OSS4-HMAC-SHA256 Credential=foo1/20221006/cn-qingdao/oss/aliyun_v4_request,Signature=dc14170a470a78495f93b6909a34be2e706689a43014f71f3e899c6642d847a3"))
+    );
+}
+
+#[tokio::test]
+async fn test_credentials_provider() {
+    use crate::builder::{BuilderError, Middleware};
+    use crate::credentials::{Credentials, StaticProvider};
+    use async_trait::async_trait;
+    use reqwest::{Request, Response};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct MyMiddleware {}
+
+    #[async_trait]
+    impl Middleware for MyMiddleware {
+        async fn handle(&self, request: Request) -> Result<Response, BuilderError> {
+            assert_eq!(
+                request.headers().get("accesskeyid"),
+                Some(&HeaderValue::from_static("sts_key"))
+            );
+            assert_eq!(
+                request.headers().get("x-oss-security-token"),
+                Some(&HeaderValue::from_static("sts_token"))
+            );
+            assert_eq!(
+                request.headers()["authorization"].to_str().unwrap(),
+                "OSS sts_key:gXVV0YVRCruax5629muNYTAlbBg="
+            );
+            let response = http::Response::builder().status(200).body("").unwrap();
+            Ok(Response::from(response))
+        }
+    }
+
+    let mut client = Client::<ClientWithMiddleware>::new(
+        "foo1".into(),
+        "foo2".into(),
+        EndPoint::CN_QINGDAO,
+        "foo4".parse().unwrap(),
+    )
+    .middleware(Arc::new(MyMiddleware {}));
+    client.credentials_provider(StaticProvider::from(Credentials::new_with_sts(
+        "sts_key".into(),
+        "sts_secret".into(),
+        "sts_token",
+        None,
+    )));
+
+    let url = "https://foo4.oss-cn-qingdao.aliyuncs.com/abc.txt"
+        .parse()
+        .unwrap();
+    let resource = CanonicalizedResource::new("/foo4/abc.txt");
+    let response = client
+        .builder(Method::GET, url, resource)
+        .unwrap()
+        .send_adjust_error()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
 #[cfg(feature = "blocking")]
 #[test]
 fn test_blocking_builder_with_header() {
@@ -115,6 +210,123 @@ fn test_blocking_builder_with_header() {
     );
 }
 
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_credentials_provider() {
+    use crate::blocking::builder::ClientWithMiddleware;
+    use crate::credentials::{Credentials, StaticProvider};
+    use crate::file::blocking::AlignBuilder;
+
+    let mut client = Client::<ClientWithMiddleware>::new(
+        "foo1".into(),
+        "foo2".into(),
+        EndPoint::CN_QINGDAO,
+        "foo4".parse().unwrap(),
+    );
+    assert_eq!(client.get_credentials().unwrap().key().as_ref(), "foo1");
+
+    client.credentials_provider(StaticProvider::from(Credentials::new_with_sts(
+        "sts_key".into(),
+        "sts_secret".into(),
+        "sts_token",
+        None,
+    )));
+    let credentials = client.get_credentials().unwrap();
+    assert_eq!(credentials.security_token(), Some("sts_token"));
+
+    let url = "https://foo4.oss-cn-qingdao.aliyuncs.com/abc.txt"
+        .parse()
+        .unwrap();
+    let resource = CanonicalizedResource::new("/foo4/abc.txt");
+    let request = client
+        .builder(Method::GET, url, resource)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.headers().get("accesskeyid"),
+        Some(&HeaderValue::from_static("sts_key"))
+    );
+    assert_eq!(
+        request.headers().get("x-oss-security-token"),
+        Some(&HeaderValue::from_static("sts_token"))
+    );
+    assert_eq!(
+        request.headers()["authorization"].to_str().unwrap(),
+        "OSS sts_key:gXVV0YVRCruax5629muNYTAlbBg="
+    );
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_blocking_ecs_provider() {
+    use crate::blocking::builder::ClientWithMiddleware;
+    use crate::credentials::{mock_server, EcsRamRoleProvider};
+    use crate::file::blocking::AlignBuilder;
+
+    let url = mock_server(|request| {
+        if request.starts_with("GET /latest/meta-data/ram/security-credentials/my-role ") {
+            let body = r#"{
+  "AccessKeyId" : "STS.key",
+  "AccessKeySecret" : "secret",
+  "Expiration" : "2099-12-03T13:00:00Z",
+  "SecurityToken" : "token",
+  "Code" : "Success"
+}"#;
+            ("200 OK", body.to_owned())
+        } else {
+            ("404 Not Found", String::new())
+        }
+    });
+    let mut provider = EcsRamRoleProvider::new();
+    provider.metadata_url(url).role("my-role");
+
+    let mut client = Client::<ClientWithMiddleware>::new(
+        "foo1".into(),
+        "foo2".into(),
+        EndPoint::CN_QINGDAO,
+        "foo4".parse().unwrap(),
+    );
+    client.credentials_provider(provider);
+    assert_eq!(client.get_credentials().unwrap().key().as_ref(), "STS.key");
+
+    let url = "https://foo4.oss-cn-qingdao.aliyuncs.com/abc.txt"
+        .parse()
+        .unwrap();
+    let resource = CanonicalizedResource::new("/foo4/abc.txt");
+    let request = client
+        .builder(Method::GET, url, resource)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.headers().get("accesskeyid"),
+        Some(&HeaderValue::from_static("STS.key"))
+    );
+    assert_eq!(
+        request.headers().get("x-oss-security-token"),
+        Some(&HeaderValue::from_static("token"))
+    );
+}
+
+#[cfg(feature = "sts")]
+#[tokio::test]
+async fn test_get_credentials() {
+    use crate::sts::STS;
+
+    let client = Client::<ClientWithMiddleware>::new_with_sts(
+        "foo1".into(),
+        "foo2".into(),
+        EndPoint::CN_QINGDAO,
+        "foo4".parse().unwrap(),
+        "token",
+    )
+    .unwrap();
+    let credentials = client.get_credentials().await.unwrap();
+    assert_eq!(credentials.key().as_ref(), "foo1");
+    assert_eq!(credentials.security_token(), Some("token"));
+}
+
 mod handle_error {
     use crate::builder::{check_http_status, BuilderError, BuilderErrorKind};
     use crate::errors::OssService;
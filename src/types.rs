@@ -7,8 +7,10 @@ use std::{
     str::FromStr,
 };
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::{DateTime, TimeZone};
 use http::header::{HeaderValue, InvalidHeaderValue, ToStrError};
+use md5::{Digest, Md5};
 use url::Url;
 
 #[cfg(feature = "core")]
@@ -182,6 +184,57 @@ impl<'a> InnerKeySecret<'a> {
 
         Ok(STANDARD.encode(sha1))
     }
+
+    /// # V4 版本的加密
+    /// 由 `date`，`region` 派生出签名秘钥，再对数据进行 HMAC-SHA256 加密，返回十六进制字符串
+    pub fn encryption_v4(
+        &self,
+        date: &str,
+        region: &str,
+        data: &[u8],
+    ) -> Result<String, hmac::digest::crypto_common::InvalidLength> {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+        type HmacSha256 = Hmac<Sha256>;
+
+        fn sign(
+            key: &[u8],
+            data: &[u8],
+        ) -> Result<Vec<u8>, hmac::digest::crypto_common::InvalidLength> {
+            let mut mac = HmacSha256::new_from_slice(key)?;
+            mac.update(data);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+
+        let secret = format!("aliyun_v4{}", self.0);
+        let key = sign(secret.as_bytes(), date.as_bytes())?;
+        let key = sign(&key, region.as_bytes())?;
+        let key = sign(&key, b"oss")?;
+        let key = sign(&key, b"aliyun_v4_request")?;
+
+        Ok(to_hex(&sign(&key, data)?))
+    }
+
+    /// # RPC 风格接口（如 STS）的加密
+    /// 秘钥为 secret 加上 `&`，返回 base64 编码
+    #[cfg(feature = "sts")]
+    pub(crate) fn encryption_rpc(
+        &self,
+        data: &[u8],
+    ) -> Result<String, hmac::digest::crypto_common::InvalidLength> {
+        InnerKeySecret::new(format!("{}&", self.0)).encryption(data)
+    }
+}
+
+/// 将字节转化为小写的十六进制字符串
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            let _ = write!(s, "{:02x}", b);
+            s
+        })
 }
 
 //===================================================================================================
@@ -904,6 +957,19 @@ impl<'a> InnerContentMd5<'a> {
     }
 }
 
+impl ContentMd5 {
+    /// 计算请求体的 Content-MD5，即 MD5 值的 base64 编码
+    ///
+    /// ```
+    /// # use aliyun_oss_client::types::ContentMd5;
+    /// let md5 = ContentMd5::from_body(b"abc");
+    /// assert_eq!(md5.as_ref(), "kAFQmDzST7DWlj99KOF/cg==");
+    /// ```
+    pub fn from_body(body: &[u8]) -> Self {
+        Self(Cow::Owned(STANDARD.encode(Md5::digest(body))))
+    }
+}
+
 //===================================================================================================
 
 /// aliyun OSS 的配置 ContentType
@@ -8,7 +8,7 @@ use super::{
     InvalidObjectPath, ObjectPath, SetObjectPath,
 };
 use crate::{
-    auth::query::QueryAuth,
+    auth::query::{QueryAuth, SignUrlOptions},
     builder::{ArcPointer, PointerFamily},
     EndPoint, KeyId, KeySecret,
 };
@@ -188,6 +188,20 @@ impl ObjectBase<ArcPointer> {
     pub fn to_sign_url(&self, key: &KeyId, secret: &KeySecret, expires: i64) -> Url {
         let auth = QueryAuth::new_with_bucket(key, secret, &self.bucket);
         auth.to_url(&self.path, expires)
+            .expect("V1 signature does not fail")
+    }
+
+    /// 带签名的 Url 链接，可自定义请求方式，header 及子资源
+    pub fn to_sign_url_with(
+        &self,
+        key: &KeyId,
+        secret: &KeySecret,
+        options: &SignUrlOptions,
+        expires: i64,
+    ) -> Url {
+        let auth = QueryAuth::new_with_bucket(key, secret, &self.bucket);
+        auth.to_url_with(&self.path, options, expires)
+            .expect("V1 signature does not fail")
     }
 }
 
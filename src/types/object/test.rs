@@ -188,6 +188,22 @@ mod test_core {
         let url = object.to_sign_url(&"key".into(), &"secret".into(), 1234567890);
         assert_eq!(url.as_str(), "https://abc.oss-cn-beijing.aliyuncs.com/path1.png?OSSAccessKeyId=key&Expires=1234567890&Signature=Kpqvd4gWgHNlkCfcYzRiHmDO%2Fvw%3D");
     }
+
+    #[test]
+    fn to_sign_url_with() {
+        use crate::auth::SignUrlOptions;
+        use http::Method;
+
+        let object = ObjectBase::<ArcPointer>::from_bucket(
+            BucketBase::new("abc".parse().unwrap(), EndPoint::CN_BEIJING),
+            "path1.png",
+        )
+        .unwrap();
+
+        let options = SignUrlOptions::new(Method::HEAD);
+        let url = object.to_sign_url_with(&"key".into(), &"secret".into(), &options, 1234567890);
+        assert_eq!(url.as_str(), "https://abc.oss-cn-beijing.aliyuncs.com/path1.png?OSSAccessKeyId=key&Expires=1234567890&Signature=dqTgylG0JJ%2Bcyb2gzGX05egzXFo%3D");
+    }
 }
 
 #[cfg(feature = "blocking")]
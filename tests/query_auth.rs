@@ -13,7 +13,9 @@ async fn run() {
     let auth = QueryAuth::from(&config);
 
     let time = Utc::now().timestamp() + 3600;
-    let url = auth.to_url(&"babel.config.js".parse().unwrap(), time);
+    let url = auth
+        .to_url(&"babel.config.js".parse().unwrap(), time)
+        .unwrap();
 
     let client = Client::new().get(url).send().await.unwrap();
     assert!(client.status().is_success());